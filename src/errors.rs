@@ -10,6 +10,12 @@ pub enum AppError {
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
+    #[error("No mods found in: {0}")]
+    NoModsFound(String),
+
+    #[error("Arma 3 not found in the Steam libraries of: {0}")]
+    SteamNotFound(String),
+
     #[error("Path conversion error: {0}")]
     PathConversionError(String),
 
@@ -22,9 +28,59 @@ pub enum AppError {
     #[error("Unsupported platform")]
     UnsupportedPlatform,
 
+    #[cfg(feature = "network")]
     #[error("Network error: {0}")]
     NetworkError(String),
 
+    #[cfg(feature = "network")]
+    #[error("Timed out fetching workshop item {0}")]
+    NetworkTimeout(String),
+
+    #[cfg(feature = "network")]
+    #[error("Rate limited by the Steam Workshop, try again later")]
+    RateLimited,
+
+    #[cfg(feature = "network")]
+    #[error("Workshop item {0} not found, it may be private or removed")]
+    WorkshopItemNotFound(String),
+
+    #[cfg(feature = "network")]
+    #[error("Offline, the Steam Workshop is not checked")]
+    Offline,
+
     #[error("Regex error")]
     RegexError,
+
+    #[error("Unsupported preset format: {0}")]
+    UnsupportedPresetFormat(String),
+
+    #[error("Invalid server address, expected host:port: {0}")]
+    InvalidServerAddress(String),
+
+    #[error("Arma 3 is still running (pid {0})")]
+    GameRunning(u32),
+
+    #[error("Invalid page number: {0}")]
+    InvalidPageNumber(String),
+
+    #[error("Invalid page size, expected at least 1: {0}")]
+    InvalidPageSize(String),
+
+    #[error("Could not open {0}: {1}")]
+    OpenFailed(String, std::io::Error),
+
+    #[error("Missing value for {0}")]
+    MissingArgument(String),
+
+    #[error("The config at {0} can't be read: {1}")]
+    CorruptConfig(String, String),
+
+    #[error("Zip error: {0}")]
+    ZipError(#[from] zip::result::ZipError),
+
+    #[error("No mod found in {0}, expected an addons folder with .pbo files")]
+    NotAMod(String),
+
+    #[error("A mod is already installed at {0}")]
+    ModAlreadyInstalled(String),
 }
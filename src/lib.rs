@@ -0,0 +1,23 @@
+//! Scanning, selecting and launching Arma 3 mods, shared by the terminal UI and other frontends.
+//!
+//! ```no_run
+//! use arma3_mod_manager_console::{AppResult, ModManager};
+//!
+//! fn main() -> AppResult<()> {
+//!     let mut manager = ModManager::new()?;
+//!     for m in manager.mods() {
+//!         println!("{} ({})", m.name, m.identifier);
+//!     }
+//!
+//!     manager.set_enabled("450814997", true);
+//!     manager.save()?;
+//!     manager.launch()?;
+//!     Ok(())
+//! }
+//! ```
+
+pub mod errors;
+pub mod mod_manager;
+
+pub use errors::{AppError, AppResult};
+pub use mod_manager::{config::Config, Mod, ModCategory, ModManager};
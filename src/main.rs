@@ -1,16 +1,127 @@
-use errors::AppResult;
-use std::env;
+use regex::Regex;
+use std::{env, path::Path};
 
-use mod_manager::ModManager;
+use arma3_mod_manager_console::{
+    mod_manager::{config::LinkMode, launcher},
+    AppError, AppResult, Config, ModCategory, ModManager,
+};
 
-mod errors;
-mod mod_manager;
+/// Flags that work without the terminal UI, and so without asking anything
+const CLI_ACTIONS: [&str; 6] = [
+    "--repair",
+    "--list",
+    "--enable",
+    "--disable",
+    "--launch",
+    "--dry-run",
+];
 
-fn main() -> AppResult<()> {
-    let mut manager = ModManager::new(15)?;
+/// Command line actions that save the config
+const SAVING_ACTIONS: [&str; 3] = ["--repair", "--enable", "--disable"];
 
+fn main() -> AppResult<()> {
     let args: Vec<String> = env::args().collect();
+
+    if let Some(path) = flag_value(&args, "--config")? {
+        Config::set_save_path(Path::new(path))?;
+    }
+
+    if let Some(path) = flag_value(&args, "--export-config")? {
+        let portable = args.contains(&"--portable".to_string());
+        Config::read()?.export_to(Path::new(path), portable)?;
+        println!("Config exported to {}", path);
+        return Ok(());
+    }
+
+    if let Some(path) = flag_value(&args, "--import-config")? {
+        let (config, missing_paths) = Config::import_from(Path::new(path))?;
+        config.save()?;
+        println!("Config imported from {}", path);
+        for message in &missing_paths {
+            println!("Warning: {}", message);
+        }
+        if !missing_paths.is_empty() {
+            println!("Run with --repair to detect the Steam paths on this machine");
+        }
+        return Ok(());
+    }
+
+    if let Some(page_size) = flag_value(&args, "--page-size")? {
+        match page_size.parse::<usize>() {
+            Ok(page_size) if page_size > 0 => Config::set_page_size_override(page_size),
+            _ => return Err(AppError::InvalidPageSize(page_size.clone())),
+        }
+    }
+
+    if args.contains(&"--offline".to_string()) {
+        Config::set_offline_override();
+    }
+
+    if args.contains(&"--verify".to_string()) {
+        if !verify()? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Scripts read the backup of a corrupt config instead of getting a prompt
+    let interactive = !args.iter().any(|arg| CLI_ACTIONS.contains(&arg.as_str()));
+    let mut manager = if interactive {
+        match ModManager::new_or_recover()? {
+            Some(manager) => manager,
+            None => {
+                println!(
+                    "Fix or remove {} and start again",
+                    Config::get_save_path()?.display()
+                );
+                return Ok(());
+            }
+        }
+    } else {
+        match ModManager::new() {
+            Err(AppError::CorruptConfig(path, message)) if Config::has_backup()? => {
+                println!(
+                    "Warning: {} can't be read ({}), using its backup",
+                    path, message
+                );
+                let manager = ModManager::from_backup()?;
+                // Saving would replace the config without keeping a backup of it
+                if args
+                    .iter()
+                    .any(|arg| SAVING_ACTIONS.contains(&arg.as_str()))
+                {
+                    let corrupt_path = Config::set_aside()?;
+                    println!("The config was kept as {}", corrupt_path.display());
+                }
+                manager
+            }
+            result => result?,
+        }
+    };
+
+    if args.contains(&"--repair".to_string()) {
+        manager.repair_paths()?;
+        println!("Config repaired:");
+        println!("- Game path: {}", manager.config.get_game_path().display());
+        println!(
+            "- Workshop path: {}",
+            manager.config.get_workshop_path().display()
+        );
+        return Ok(());
+    }
+
     if args.contains(&"--list".to_string()) {
+        if args.contains(&"--json".to_string()) {
+            let json = serde_json::to_string_pretty(manager.loaded_mods.all_items())?;
+            println!("{}", json);
+            return Ok(());
+        }
+
+        if args.contains(&"--verbose".to_string()) {
+            list_verbose(&manager);
+            return Ok(());
+        }
+
         println!("Found {} mods:", manager.loaded_mods.all_items().len());
         for mod_item in manager.loaded_mods.all_items() {
             println!("- {}", mod_item.name);
@@ -18,7 +129,292 @@ fn main() -> AppResult<()> {
         return Ok(());
     }
 
+    if args
+        .iter()
+        .any(|arg| arg == "--enable" || arg == "--disable")
+    {
+        if let Err(e) = toggle_mods(&mut manager, &args) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let dry_run = args.contains(&"--dry-run".to_string());
+    if dry_run || args.contains(&"--launch".to_string()) {
+        for (old, new) in manager.take_case_corrections() {
+            println!(
+                "Warning: {} is installed in the folder {}, launching with {}",
+                old, new, new
+            );
+        }
+        if let Err(e) = launch(&manager, dry_run) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     manager.start()?;
 
     Ok(())
 }
+
+/// The value following the flag, `None` when the flag isn't given
+fn flag_value<'a>(args: &'a [String], flag: &str) -> AppResult<Option<&'a String>> {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => args
+            .get(index + 1)
+            .map(Some)
+            .ok_or_else(|| AppError::MissingArgument(flag.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Prints the config, paths and mods the manager would use, to check the setup.
+/// Returns false when the game can't be launched with it.
+fn verify() -> AppResult<bool> {
+    println!("Platform: {} ({})", env::consts::OS, env::consts::ARCH);
+
+    let config_path = Config::get_save_path()?;
+    let config = match Config::read() {
+        Ok(config) => {
+            println!("Config: {} (ok)", config_path.display());
+            config
+        }
+        Err(AppError::IoError(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!(
+                "Config: {} (not found, start the manager to set it up)",
+                config_path.display()
+            );
+            return Ok(false);
+        }
+        Err(AppError::CorruptConfig(_, message)) => {
+            println!(
+                "Config: {} (not valid JSON: {})",
+                config_path.display(),
+                message
+            );
+            return Ok(false);
+        }
+        Err(e) => {
+            println!("Config: {} ({})", config_path.display(), e);
+            return Ok(false);
+        }
+    };
+
+    let status = |path: &Path| if path.exists() { "ok" } else { "not found" };
+
+    let game_path = config.get_game_path();
+    println!("Game path: {} ({})", game_path.display(), status(game_path));
+
+    let workshop_path = config.get_workshop_path();
+    println!(
+        "Workshop path: {} ({})",
+        workshop_path.display(),
+        status(workshop_path)
+    );
+
+    let custom_mods_paths = config.get_custom_mods_paths();
+    if custom_mods_paths.is_empty() {
+        println!("Custom mods path: not set");
+    }
+    for path in custom_mods_paths {
+        println!("Custom mods path: {} ({})", path.display(), status(path));
+    }
+
+    let executable_path = launcher::get_executable_path(game_path, config.get_executable_name());
+    println!(
+        "Executable: {} ({})",
+        executable_path.display(),
+        status(&executable_path)
+    );
+
+    let battleye_path = launcher::get_battleye_path(game_path);
+    if config.get_use_battleye() {
+        println!(
+            "BattlEye launcher: {} ({})",
+            battleye_path.display(),
+            status(&battleye_path)
+        );
+    }
+
+    if config.is_valid() {
+        let manager = ModManager::new()?;
+        let categories = [
+            ModCategory::Workshop,
+            ModCategory::Custom,
+            ModCategory::Cdlc,
+        ];
+        let counts: Vec<String> = categories
+            .iter()
+            .map(|category| {
+                let count = manager
+                    .mods()
+                    .iter()
+                    .filter(|m| category.contains(m))
+                    .count();
+                format!("{} {}", count, category.name())
+            })
+            .collect();
+        println!("Mods: {}", counts.join(", "));
+    }
+
+    Ok(config.is_valid()
+        && executable_path.exists()
+        && (!config.get_use_battleye() || battleye_path.exists()))
+}
+
+/// Print every mod with its enabled state and where it comes from, in columns,
+/// followed by the counts per category
+fn list_verbose(manager: &ModManager) {
+    let mods = manager.mods();
+    let name_width = mods
+        .iter()
+        .map(|m| m.name.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    for m in mods {
+        let source = if m.is_missing {
+            "missing"
+        } else if m.is_cdlc {
+            "CDLC"
+        } else if m.is_custom {
+            "custom"
+        } else {
+            "workshop"
+        };
+        let server_mod = if m.is_server_mod { ", server mod" } else { "" };
+        println!(
+            "[{}] {:<width$} ({} {}{})",
+            if m.enabled { "X" } else { " " },
+            m.name,
+            source,
+            m.identifier,
+            server_mod,
+            width = name_width
+        );
+    }
+
+    let categories = [
+        ModCategory::Workshop,
+        ModCategory::Custom,
+        ModCategory::Cdlc,
+        ModCategory::ServerMod,
+    ];
+    let mut counts: Vec<String> = categories
+        .iter()
+        .map(|category| {
+            let count = mods.iter().filter(|m| category.contains(m)).count();
+            format!("{} {}", count, category.name())
+        })
+        .collect();
+    let missing = mods.iter().filter(|m| m.is_missing).count();
+    if missing > 0 {
+        counts.push(format!("{} missing", missing));
+    }
+
+    println!();
+    println!(
+        "{} mods, {} enabled: {}",
+        mods.len(),
+        mods.iter().filter(|m| m.enabled).count(),
+        counts.join(", ")
+    );
+}
+
+/// Enable and disable the mods matching the `--enable` and `--disable` patterns in order,
+/// and save the selection
+fn toggle_mods(manager: &mut ModManager, args: &[String]) -> AppResult<()> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let enabled = match arg.as_str() {
+            "--enable" => true,
+            "--disable" => false,
+            _ => continue,
+        };
+
+        let pattern = args
+            .next()
+            .ok_or_else(|| AppError::MissingArgument(arg.clone()))?;
+        let pattern = Regex::new(pattern).map_err(|_| AppError::RegexError)?;
+
+        for name in manager.set_enabled_matching(&pattern, enabled) {
+            println!("{} {}", if enabled { "Enabled" } else { "Disabled" }, name);
+        }
+    }
+
+    manager.config.save()
+}
+
+/// Launch the game with the saved mod selection, without the terminal UI.
+/// A dry run only prints what launching would do.
+fn launch(manager: &ModManager, dry_run: bool) -> AppResult<()> {
+    let enabled_mods = manager.enabled_mods();
+
+    if dry_run {
+        println!("Dry run, nothing is linked or started");
+    }
+    println!("Launching with {} mods:", enabled_mods.len());
+    for mod_item in &enabled_mods {
+        if mod_item.is_missing {
+            println!("- {} (missing, skipped)", mod_item.identifier);
+        } else {
+            println!("- {} ({})", mod_item.name, mod_item.identifier);
+        }
+    }
+
+    for mod_item in launcher::invalid_mod_arguments(&manager.config, &enabled_mods) {
+        println!(
+            "Warning: {} ({}) is skipped, since ; or \" in its name would break -mod=",
+            mod_item.name, mod_item.identifier
+        );
+    }
+
+    for mod_item in enabled_mods.iter().filter(|m| m.is_unowned) {
+        println!(
+            "Warning: {} ({}) doesn't look owned, launching may fail",
+            mod_item.name, mod_item.identifier
+        );
+    }
+
+    if let Some(warning) = launcher::disk_space_warning(&manager.config, &enabled_mods) {
+        println!("Warning: {}", warning);
+    }
+
+    if dry_run {
+        let plan = launcher::plan_launch(&manager.config, &enabled_mods)?;
+        let action = match manager.config.get_link_mode() {
+            LinkMode::Symlink => Some("link"),
+            LinkMode::Copy => Some("copy"),
+            // Nothing is placed in the game directory
+            LinkMode::Paths => None,
+        };
+        if let Some(action) = action {
+            println!("Would {} {} mods:", action, plan.links.len());
+            for (from, to) in &plan.links {
+                println!("- {} -> {}", from.display(), to.display());
+            }
+        }
+        println!("{}", launcher::command_line(&plan.command));
+        return Ok(());
+    }
+
+    let mut command = launcher::prepare_launch(&manager.config, &enabled_mods)?;
+    println!("{}", launcher::command_line(&command));
+
+    match launcher::log_output(&mut command) {
+        Ok(log_path) => println!("Logging the game output to {}", log_path.display()),
+        Err(e) => println!("Warning: the game output is not logged: {}", e),
+    }
+
+    let mut game = command.spawn()?;
+
+    if manager.config.get_wait_for_game() && !launcher::launches_through_steam(&manager.config) {
+        println!("Waiting for the game to exit (pid {})...", game.id());
+        game.wait()?;
+    }
+
+    Ok(())
+}
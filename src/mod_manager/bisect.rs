@@ -0,0 +1,124 @@
+/// Guided search for a single mod causing a problem,
+/// by repeatedly halving the set of candidate mods.
+#[derive(Debug)]
+pub struct Bisection {
+    candidates: Vec<String>,
+    steps: usize,
+}
+
+impl Bisection {
+    pub fn new(candidates: Vec<String>) -> Self {
+        Bisection {
+            candidates,
+            steps: 0,
+        }
+    }
+
+    /// The mods to launch with in the current step
+    pub fn test_set(&self) -> &[String] {
+        &self.candidates[..self.candidates.len() / 2]
+    }
+
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn steps_left(&self) -> usize {
+        self.candidates.len().next_power_of_two().trailing_zeros() as usize
+    }
+
+    /// Record whether the problem occurred when launching with the current test set
+    pub fn record(&mut self, problem_occurred: bool) {
+        if self.is_done() {
+            return;
+        }
+
+        let half = self.candidates.len() / 2;
+        if problem_occurred {
+            self.candidates.truncate(half);
+        } else {
+            self.candidates.drain(..half);
+        }
+        self.steps += 1;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.candidates.len() <= 1
+    }
+
+    pub fn culprit(&self) -> Option<&str> {
+        match self.candidates.as_slice() {
+            [culprit] => Some(culprit),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_bisection(count: usize) -> Bisection {
+        Bisection::new((0..count).map(|i| i.to_string()).collect())
+    }
+
+    #[test]
+    fn test_set_is_first_half() {
+        let b = make_bisection(5);
+        assert_eq!(b.test_set(), &["0", "1"]);
+    }
+
+    #[test]
+    fn problem_occurred_keeps_test_set() {
+        let mut b = make_bisection(4);
+        b.record(true);
+        assert_eq!(b.candidates(), &["0", "1"]);
+        assert_eq!(b.steps(), 1);
+    }
+
+    #[test]
+    fn no_problem_keeps_other_half() {
+        let mut b = make_bisection(5);
+        b.record(false);
+        assert_eq!(b.candidates(), &["2", "3", "4"]);
+    }
+
+    #[test]
+    fn finds_culprit() {
+        let mut b = make_bisection(8);
+        // Culprit is "5"
+        while !b.is_done() {
+            let problem = b.test_set().contains(&"5".to_string());
+            b.record(problem);
+        }
+        assert_eq!(b.culprit(), Some("5"));
+        assert_eq!(b.steps(), 3);
+    }
+
+    #[test]
+    fn steps_left_rounds_up() {
+        assert_eq!(make_bisection(1).steps_left(), 0);
+        assert_eq!(make_bisection(2).steps_left(), 1);
+        assert_eq!(make_bisection(5).steps_left(), 3);
+        assert_eq!(make_bisection(8).steps_left(), 3);
+    }
+
+    #[test]
+    fn record_after_done_is_noop() {
+        let mut b = make_bisection(1);
+        b.record(true);
+        assert_eq!(b.culprit(), Some("0"));
+        assert_eq!(b.steps(), 0);
+    }
+
+    #[test]
+    fn empty_has_no_culprit() {
+        let b = make_bisection(0);
+        assert!(b.is_done());
+        assert_eq!(b.culprit(), None);
+    }
+}
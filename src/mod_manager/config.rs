@@ -1,58 +1,385 @@
 use std::collections::HashMap;
+use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+#[cfg(feature = "network")]
+use std::time::Duration;
 
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
 use crate::errors::AppError;
 use crate::errors::AppResult;
 
-use super::utils;
+use super::{file_handler, utils};
+
+/// How mods are made available in the game directory when launching
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    #[default]
+    Symlink,
+    /// For filesystems without symlink support. Uses hard links where possible.
+    Copy,
+    /// Nothing is placed in the game directory, the mods are passed by their full path
+    Paths,
+}
+
+/// How the game is started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchMethod {
+    /// Run the game executable directly
+    #[default]
+    Direct,
+    /// Run the game through Steam, so the overlay and DRM are initialized
+    Steam,
+}
+
+/// How many servers are kept in `recent_servers`
+const MAX_RECENT_SERVERS: usize = 5;
+
+/// A server to join when launching
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Server {
+    pub host: String,
+    pub port: u16,
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+impl Server {
+    /// Parses an address in the form `host:port`
+    pub fn parse(address: &str, password: Option<String>) -> AppResult<Self> {
+        let invalid = || AppError::InvalidServerAddress(address.to_string());
+
+        let (host, port) = address.trim().rsplit_once(':').ok_or_else(invalid)?;
+        if host.is_empty() || host.contains(char::is_whitespace) {
+            return Err(invalid());
+        }
+        let port = port
+            .parse::<u16>()
+            .ok()
+            .filter(|p| *p > 0)
+            .ok_or_else(invalid)?;
+
+        Ok(Server {
+            host: host.to_string(),
+            port,
+            password: password.filter(|p| !p.is_empty()),
+        })
+    }
+
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// How the mod list is navigated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListMode {
+    #[default]
+    Pages,
+    /// The list scrolls with the cursor
+    Scroll,
+}
+
+/// Order of the mod list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Name,
+    EnabledFirst,
+    /// Most recently updated first
+    Updated,
+}
+
+impl SortOrder {
+    pub fn next(self) -> Self {
+        match self {
+            SortOrder::Name => SortOrder::EnabledFirst,
+            SortOrder::EnabledFirst => SortOrder::Updated,
+            SortOrder::Updated => SortOrder::Name,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::Name => "Name",
+            SortOrder::EnabledFirst => "Enabled first",
+            SortOrder::Updated => "Last updated",
+        }
+    }
+}
+
+/// Which mods are shown in the mod list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModFilter {
+    #[default]
+    All,
+    Enabled,
+    Disabled,
+}
+
+impl ModFilter {
+    pub fn next(self) -> Self {
+        match self {
+            ModFilter::All => ModFilter::Enabled,
+            ModFilter::Enabled => ModFilter::Disabled,
+            ModFilter::Disabled => ModFilter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ModFilter::All => "All",
+            ModFilter::Enabled => "Enabled only",
+            ModFilter::Disabled => "Disabled only",
+        }
+    }
+
+    pub fn matches(self, enabled: bool) -> bool {
+        match self {
+            ModFilter::All => true,
+            ModFilter::Enabled => enabled,
+            ModFilter::Disabled => !enabled,
+        }
+    }
+}
 
 fn default_active_preset() -> String {
     "Default".to_string()
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     game_path: String,
     workshop_path: String,
-    custom_mods_path: Option<String>, // Optional
+    /// Folders of mods that are not from the workshop, `custom_mods_path` in older configs
+    #[serde(
+        default,
+        alias = "custom_mods_path",
+        deserialize_with = "deserialize_paths"
+    )]
+    custom_mods_paths: Vec<String>,
     #[serde(default = "default_executable_name")]
     executable_name: String,
     #[serde(deserialize_with = "deserialize_mods")]
-    enabled_mods: Vec<String>,
+    enabled_mods: Vec<ModEntry>,
     default_args: String,
-    #[serde(default)]
-    presets: HashMap<String, Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_presets")]
+    presets: HashMap<String, Vec<ModEntry>>,
     #[serde(default = "default_active_preset")]
     active_preset: String,
+    #[serde(default)]
+    sort_enabled_first: bool,
+    #[serde(default = "default_alternate_screen")]
+    alternate_screen: bool,
+    #[serde(default)]
+    auto_enable_dependencies: bool,
+    #[serde(default = "default_dependency_cache_hours")]
+    dependency_cache_hours: u64,
+    /// How often failed requests to the Steam Workshop are retried
+    #[serde(default = "default_network_retries")]
+    network_retries: u32,
+    /// Never contact the Steam Workshop, dependencies are only read from the cache
+    #[serde(default)]
+    offline: bool,
+    #[serde(default)]
+    link_mode: LinkMode,
+    #[serde(default)]
+    launch_method: LaunchMethod,
+    /// Start the game through the BattlEye launcher, which servers with anticheat require
+    #[serde(default)]
+    use_battleye: bool,
+    #[serde(default)]
+    list_mode: ListMode,
+    /// Most mods shown per page, the list fits the window when not set
+    #[serde(default)]
+    page_size: Option<usize>,
+    /// Wait for the game to exit after launching it
+    #[serde(default)]
+    wait_for_game: bool,
+    /// Sort order per preset
+    #[serde(default)]
+    sort_orders: HashMap<String, SortOrder>,
+    /// Shown mods per preset
+    #[serde(default)]
+    mod_filters: HashMap<String, ModFilter>,
+    /// Tag whose mods are listed, per preset
+    #[serde(default)]
+    tag_filters: HashMap<String, String>,
+    /// Whether only favorite mods are listed, per preset
+    #[serde(default)]
+    favorites_only: HashMap<String, bool>,
+    /// Launch arguments per preset, used instead of `default_args`
+    #[serde(default)]
+    preset_args: HashMap<String, String>,
+    /// Joined when launching
+    #[serde(default)]
+    server: Option<Server>,
+    #[serde(default)]
+    recent_servers: Vec<Server>,
+    /// Arma profile to play as, the default profile when not set
+    #[serde(default)]
+    profile: Option<String>,
+    /// Script run on startup with `-init=`, e.g. `playMission["","MyMission.Altis"]`
+    #[serde(default)]
+    init_command: Option<String>,
+    /// World loaded on startup with `-world=`, instead of the one in the launch arguments
+    #[serde(default)]
+    editor_world: Option<String>,
+    /// Listed at the top, in every preset
+    #[serde(default)]
+    favorites: Vec<String>,
+    /// Mod identifiers per user-defined tag
+    #[serde(default)]
+    tags: HashMap<String, Vec<String>>,
+    /// Page the manager was closed on
+    #[serde(default)]
+    last_page: usize,
+    /// Mod under the cursor when the manager was closed
+    #[serde(default)]
+    last_selected_mod: Option<String>,
+}
+
+/// A mod in a preset. Written as its id, or as `{ "id": "...", "enabled": false }`
+/// to keep it in the preset without loading it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ModEntry {
+    id: String,
+    enabled: bool,
+}
+
+impl ModEntry {
+    fn enabled(id: String) -> Self {
+        ModEntry { id, enabled: true }
+    }
+
+    /// Reads an id as a string or, in older configs, a number, or an object with an `id`
+    /// and `enabled`. Anything else, like a stray hand-written object, is skipped.
+    fn from_value(value: Value) -> Option<Self> {
+        let id = |value: &Value| match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        };
+
+        match &value {
+            Value::Object(map) => Some(ModEntry {
+                id: id(map.get("id")?)?,
+                enabled: map.get("enabled").and_then(Value::as_bool).unwrap_or(true),
+            }),
+            _ => id(&value).map(ModEntry::enabled),
+        }
+    }
+}
+
+impl Serialize for ModEntry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.enabled {
+            return serializer.serialize_str(&self.id);
+        }
+
+        let mut entry = serializer.serialize_struct("ModEntry", 2)?;
+        entry.serialize_field("id", &self.id)?;
+        entry.serialize_field("enabled", &false)?;
+        entry.end()
+    }
+}
+
+/// The ids of the enabled entries
+fn enabled_ids(entries: &[ModEntry]) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|entry| entry.enabled)
+        .map(|entry| entry.id.clone())
+        .collect()
 }
 
 // Backwards compatibility supports
 // Since previous configs the enabled mods array was only numbers.
-fn deserialize_mods<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+fn deserialize_mods<'de, D>(deserializer: D) -> Result<Vec<ModEntry>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let v = Vec::<Value>::deserialize(deserializer)?;
+    Ok(v.into_iter().filter_map(ModEntry::from_value).collect())
+}
 
-    let mut mods = Vec::new();
-    for item in v {
-        match item {
-            Value::String(s) => mods.push(s),
-            Value::Number(n) => mods.push(n.to_string()),
-            _ => {}
-        }
-    }
-    Ok(mods)
+// Older configs had a single optional custom mods path
+fn deserialize_paths<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(path) => vec![path],
+        Value::Array(paths) => paths
+            .into_iter()
+            .filter_map(|path| path.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    })
+}
+
+fn deserialize_presets<'de, D>(deserializer: D) -> Result<HashMap<String, Vec<ModEntry>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let presets = HashMap::<String, Vec<Value>>::deserialize(deserializer)?;
+    Ok(presets
+        .into_iter()
+        .map(|(name, v)| {
+            let entries = v.into_iter().filter_map(ModEntry::from_value).collect();
+            (name, entries)
+        })
+        .collect())
 }
 
 fn default_executable_name() -> String {
-    "arma3".to_string()
+    if cfg!(target_os = "windows") {
+        "arma3_x64".to_string()
+    } else {
+        "arma3".to_string()
+    }
+}
+
+fn default_alternate_screen() -> bool {
+    true
+}
+
+fn default_dependency_cache_hours() -> u64 {
+    24
 }
 
+fn default_network_retries() -> u32 {
+    2
+}
+
+/// Set with `--config`, replaces the default config location
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Set with `--page-size`, replaces `page_size` without saving it
+static PAGE_SIZE_OVERRIDE: OnceLock<usize> = OnceLock::new();
+
+/// Set with `--offline`, turns on `offline` without saving it
+static OFFLINE_OVERRIDE: OnceLock<()> = OnceLock::new();
+
+/// Mods per page before the list is fitted to the window
+const DEFAULT_PAGE_SIZE: usize = 15;
+
 fn get_config_path() -> AppResult<PathBuf> {
+    if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+        return Ok(path.clone());
+    }
+
     let home_path = utils::get_home_path()?;
 
     // Define OS-specific config paths
@@ -62,13 +389,40 @@ fn get_config_path() -> AppResult<PathBuf> {
             .join(".config")
             .join("arma3-mod-manager-console")
             .join("config.json"),
-        "linux" => Path::new(&home_path)
+        "linux" => {
+            let config_home = env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
+            linux_config_path(Path::new(&home_path), config_home)
+        }
+        _ => return Err(AppError::UnsupportedPlatform),
+    };
+    Ok(config_path)
+}
+
+/// The config in `$XDG_CONFIG_HOME`, unless there only is one in a previously used location
+fn linux_config_path(home_path: &Path, config_home: Option<PathBuf>) -> PathBuf {
+    // Relative paths are invalid according to the XDG spec and are ignored
+    let config_home = config_home
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| home_path.join(".config"));
+    let config_path = config_home
+        .join("arma3-mod-manager-console")
+        .join("config.json");
+
+    if config_path.exists() {
+        return config_path;
+    }
+
+    let legacy_paths = [
+        home_path
             .join(".config")
             .join("arma3-mod-manager-console")
             .join("config.json"),
-        _ => return Err(AppError::UnsupportedPlatform),
-    };
-    return Ok(config_path);
+        home_path.join("arma3-mod-manager-console-config.json"),
+    ];
+    legacy_paths
+        .into_iter()
+        .find(|path| path.exists())
+        .unwrap_or(config_path)
 }
 
 impl Config {
@@ -77,10 +431,19 @@ impl Config {
         Ok(config_path)
     }
 
+    /// Reads and saves the config at the path instead of the default location.
+    /// Has to be set before the config is first read.
+    pub fn set_save_path(path: &Path) -> AppResult<()> {
+        let path = std::path::absolute(path)?;
+        // Only fails when already set, which keeps the first path
+        let _ = CONFIG_PATH_OVERRIDE.set(path);
+        Ok(())
+    }
+
     pub fn new(
         game_path: String,
         workshop_path: String,
-        custom_mods_path: Option<String>,
+        custom_mods_paths: Vec<String>,
     ) -> AppResult<Self> {
         let mut presets = HashMap::new();
         presets.insert("Default".to_string(), Vec::new());
@@ -88,12 +451,38 @@ impl Config {
         let new_config = Config {
             game_path,
             workshop_path,
-            custom_mods_path,
+            custom_mods_paths,
             executable_name: default_executable_name(),
             enabled_mods: Vec::new(),
             default_args: "-noSplash -skipIntro -world=empty".to_string(),
             presets,
             active_preset: default_active_preset(),
+            sort_enabled_first: false,
+            alternate_screen: default_alternate_screen(),
+            auto_enable_dependencies: false,
+            dependency_cache_hours: default_dependency_cache_hours(),
+            network_retries: default_network_retries(),
+            offline: false,
+            link_mode: LinkMode::default(),
+            launch_method: LaunchMethod::default(),
+            use_battleye: false,
+            list_mode: ListMode::default(),
+            page_size: None,
+            wait_for_game: false,
+            sort_orders: HashMap::new(),
+            mod_filters: HashMap::new(),
+            tag_filters: HashMap::new(),
+            favorites_only: HashMap::new(),
+            preset_args: HashMap::new(),
+            server: None,
+            recent_servers: Vec::new(),
+            profile: None,
+            init_command: None,
+            editor_world: None,
+            favorites: Vec::new(),
+            tags: HashMap::new(),
+            last_page: 0,
+            last_selected_mod: None,
         };
 
         Ok(new_config)
@@ -121,21 +510,40 @@ impl Config {
         }
     }
 
+    /// Only the game path has to exist. The workshop folder is missing until Steam
+    /// downloaded workshop content, which just means there are no workshop mods yet.
     pub fn is_valid(&self) -> bool {
-        Path::new(&self.workshop_path).exists() && Path::new(&self.game_path).exists()
+        Path::new(&self.game_path).exists()
+    }
+
+    pub fn has_workshop_path(&self) -> bool {
+        Path::new(&self.workshop_path).is_dir()
     }
 
     pub fn get_enabled_mods(&self) -> Vec<String> {
-        self.presets
-            .get(&self.active_preset)
-            .cloned()
-            .unwrap_or_default()
+        self.get_preset_mods(&self.active_preset)
     }
 
+    /// Replaces the enabled mods of the active preset.
+    /// Disabled entries stay at the end, unless their mod is enabled again.
     pub fn update_mods(&mut self, mods: Vec<String>) {
+        let disabled: Vec<ModEntry> = self
+            .presets
+            .get(&self.active_preset)
+            .into_iter()
+            .flatten()
+            .filter(|entry| !entry.enabled && !mods.contains(&entry.id))
+            .cloned()
+            .collect();
+        let entries: Vec<ModEntry> = mods
+            .into_iter()
+            .map(ModEntry::enabled)
+            .chain(disabled)
+            .collect();
+
         self.presets
-            .insert(self.active_preset.clone(), mods.clone());
-        self.enabled_mods = mods;
+            .insert(self.active_preset.clone(), entries.clone());
+        self.enabled_mods = entries;
     }
 
     pub fn get_game_path(&self) -> &Path {
@@ -154,8 +562,23 @@ impl Config {
         self.workshop_path = path;
     }
 
-    pub fn get_custom_mods_path(&self) -> Option<&Path> {
-        self.custom_mods_path.as_deref().map(Path::new)
+    pub fn get_custom_mods_paths(&self) -> Vec<&Path> {
+        self.custom_mods_paths.iter().map(Path::new).collect()
+    }
+
+    /// Adds a custom mods folder, returns false when it already is one
+    pub fn add_custom_mods_path(&mut self, path: String) -> bool {
+        if self.custom_mods_paths.contains(&path) {
+            return false;
+        }
+        self.custom_mods_paths.push(path);
+        true
+    }
+
+    pub fn remove_custom_mods_path(&mut self, index: usize) {
+        if index < self.custom_mods_paths.len() {
+            self.custom_mods_paths.remove(index);
+        }
     }
 
     pub fn get_executable_name(&self) -> &str {
@@ -174,6 +597,274 @@ impl Config {
         self.default_args = args;
     }
 
+    pub fn get_preset_args(&self) -> Option<&str> {
+        self.preset_args
+            .get(&self.active_preset)
+            .map(String::as_str)
+    }
+
+    /// Sets the launch arguments of the active preset, `None` to use the default arguments
+    pub fn set_preset_args(&mut self, args: Option<String>) {
+        match args {
+            Some(args) => {
+                self.preset_args.insert(self.active_preset.clone(), args);
+            }
+            None => {
+                self.preset_args.remove(&self.active_preset);
+            }
+        }
+    }
+
+    /// The arguments of the active preset, falling back to the default arguments
+    pub fn get_launch_args(&self) -> &str {
+        self.get_preset_args().unwrap_or(&self.default_args)
+    }
+
+    pub fn get_alternate_screen(&self) -> bool {
+        self.alternate_screen
+    }
+
+    #[cfg(feature = "network")]
+    pub fn get_auto_enable_dependencies(&self) -> bool {
+        self.auto_enable_dependencies
+    }
+
+    /// How long fetched dependencies are cached
+    #[cfg(feature = "network")]
+    pub fn get_dependency_cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.dependency_cache_hours * 60 * 60)
+    }
+
+    #[cfg(feature = "network")]
+    pub fn get_network_retries(&self) -> u32 {
+        self.network_retries
+    }
+
+    #[cfg(feature = "network")]
+    pub fn get_offline(&self) -> bool {
+        self.offline || OFFLINE_OVERRIDE.get().is_some()
+    }
+
+    /// Doesn't contact the Steam Workshop in this run, regardless of the config
+    pub fn set_offline_override() {
+        let _ = OFFLINE_OVERRIDE.set(());
+    }
+
+    pub fn get_link_mode(&self) -> LinkMode {
+        self.link_mode
+    }
+
+    pub fn get_launch_method(&self) -> LaunchMethod {
+        self.launch_method
+    }
+
+    pub fn get_use_battleye(&self) -> bool {
+        self.use_battleye
+    }
+
+    pub fn set_use_battleye(&mut self, use_battleye: bool) {
+        self.use_battleye = use_battleye;
+    }
+
+    pub fn get_list_mode(&self) -> ListMode {
+        self.list_mode
+    }
+
+    /// Most mods shown per page, `None` when the list fits the window
+    pub fn get_page_size(&self) -> Option<usize> {
+        PAGE_SIZE_OVERRIDE
+            .get()
+            .copied()
+            .or(self.page_size)
+            // An empty page would never show any mods
+            .map(|page_size| page_size.max(1))
+    }
+
+    /// Mods per page until the list is fitted to the window, and outside the terminal UI
+    pub fn get_initial_page_size(&self) -> usize {
+        self.get_page_size().unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+
+    /// Shows at most this many mods per page in this run, regardless of the config
+    pub fn set_page_size_override(page_size: usize) {
+        // Only fails when already set, which keeps the first page size
+        let _ = PAGE_SIZE_OVERRIDE.set(page_size);
+    }
+
+    pub fn get_wait_for_game(&self) -> bool {
+        self.wait_for_game
+    }
+
+    /// Sort order of the active preset.
+    /// Presets without one fall back to `sort_enabled_first`.
+    pub fn get_sort_order(&self) -> SortOrder {
+        match self.sort_orders.get(&self.active_preset) {
+            Some(order) => *order,
+            None if self.sort_enabled_first => SortOrder::EnabledFirst,
+            None => SortOrder::Name,
+        }
+    }
+
+    pub fn set_sort_order(&mut self, order: SortOrder) {
+        self.sort_orders.insert(self.active_preset.clone(), order);
+    }
+
+    /// Which mods the active preset shows
+    pub fn get_mod_filter(&self) -> ModFilter {
+        self.mod_filters
+            .get(&self.active_preset)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_mod_filter(&mut self, filter: ModFilter) {
+        if filter == ModFilter::All {
+            self.mod_filters.remove(&self.active_preset);
+        } else {
+            self.mod_filters.insert(self.active_preset.clone(), filter);
+        }
+    }
+
+    /// Tag the active preset lists the mods of, `None` when it lists all mods
+    pub fn get_tag_filter(&self) -> Option<&str> {
+        self.tag_filters
+            .get(&self.active_preset)
+            .map(String::as_str)
+            .filter(|tag| self.tags.contains_key(*tag))
+    }
+
+    pub fn set_tag_filter(&mut self, tag: Option<String>) {
+        match tag {
+            Some(tag) => self.tag_filters.insert(self.active_preset.clone(), tag),
+            None => self.tag_filters.remove(&self.active_preset),
+        };
+    }
+
+    /// Whether the active preset only lists favorite mods
+    pub fn get_favorites_only(&self) -> bool {
+        self.favorites_only
+            .get(&self.active_preset)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_favorites_only(&mut self, favorites_only: bool) {
+        if favorites_only {
+            self.favorites_only.insert(self.active_preset.clone(), true);
+        } else {
+            self.favorites_only.remove(&self.active_preset);
+        }
+    }
+
+    pub fn get_server(&self) -> Option<&Server> {
+        self.server.as_ref()
+    }
+
+    /// Sets the server to join, adding it to the top of the recent servers
+    pub fn set_server(&mut self, server: Option<Server>) {
+        if let Some(server) = &server {
+            self.recent_servers
+                .retain(|s| s.address() != server.address());
+            self.recent_servers.insert(0, server.clone());
+            self.recent_servers.truncate(MAX_RECENT_SERVERS);
+        }
+        self.server = server;
+    }
+
+    pub fn get_profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        self.profile = profile;
+    }
+
+    pub fn get_init_command(&self) -> Option<&str> {
+        self.init_command.as_deref()
+    }
+
+    pub fn set_init_command(&mut self, command: Option<String>) {
+        self.init_command = command;
+    }
+
+    pub fn get_editor_world(&self) -> Option<&str> {
+        self.editor_world.as_deref()
+    }
+
+    pub fn set_editor_world(&mut self, world: Option<String>) {
+        self.editor_world = world;
+    }
+
+    /// Page and mod under the cursor when the manager was closed
+    pub fn get_last_position(&self) -> (usize, Option<&str>) {
+        (self.last_page, self.last_selected_mod.as_deref())
+    }
+
+    pub fn set_last_position(&mut self, page: usize, selected_mod: Option<String>) {
+        self.last_page = page;
+        self.last_selected_mod = selected_mod;
+    }
+
+    pub fn get_recent_servers(&self) -> &[Server] {
+        &self.recent_servers
+    }
+
+    pub fn remove_recent_server(&mut self, index: usize) {
+        if index < self.recent_servers.len() {
+            self.recent_servers.remove(index);
+        }
+    }
+
+    pub fn is_favorite(&self, identifier: &str) -> bool {
+        self.favorites.iter().any(|id| id == identifier)
+    }
+
+    /// Returns whether the mod is a favorite now
+    pub fn toggle_favorite(&mut self, identifier: &str) -> bool {
+        if self.is_favorite(identifier) {
+            self.favorites.retain(|id| id != identifier);
+            false
+        } else {
+            self.favorites.push(identifier.to_string());
+            true
+        }
+    }
+
+    /// Tag names, sorted
+    pub fn get_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.tags.keys().cloned().collect();
+        tags.sort_by_key(|tag| tag.to_lowercase());
+        tags
+    }
+
+    /// Identifiers of the mods with the tag
+    pub fn get_tagged_mods(&self, tag: &str) -> &[String] {
+        self.tags.get(tag).map_or(&[], |mods| mods.as_slice())
+    }
+
+    pub fn has_tag(&self, tag: &str, identifier: &str) -> bool {
+        self.get_tagged_mods(tag).iter().any(|id| id == identifier)
+    }
+
+    /// Adds the tag to the mod or removes it. A tag without mods is removed.
+    /// Returns whether the mod has the tag now.
+    pub fn toggle_tag(&mut self, tag: &str, identifier: &str) -> bool {
+        let mods = self.tags.entry(tag.to_string()).or_default();
+        let tagged = if mods.iter().any(|id| id == identifier) {
+            mods.retain(|id| id != identifier);
+            false
+        } else {
+            mods.push(identifier.to_string());
+            true
+        };
+
+        if mods.is_empty() {
+            self.tags.remove(tag);
+            self.tag_filters.retain(|_, filter| filter != tag);
+        }
+        tagged
+    }
+
     // Preset methods
 
     pub fn get_active_preset_name(&self) -> &str {
@@ -186,8 +877,39 @@ impl Config {
         names
     }
 
+    /// The enabled mods of the preset
+    pub fn get_preset_mods(&self, name: &str) -> Vec<String> {
+        self.presets
+            .get(name)
+            .map(|entries| enabled_ids(entries))
+            .unwrap_or_default()
+    }
+
+    /// Replaces the identifier of a mod in every preset, the favorites and the tags
+    pub fn replace_mod_identifier(&mut self, old: &str, new: &str) {
+        for entry in self
+            .presets
+            .values_mut()
+            .flatten()
+            .chain(self.enabled_mods.iter_mut())
+        {
+            if entry.id == old {
+                entry.id = new.to_string();
+            }
+        }
+        for id in self
+            .favorites
+            .iter_mut()
+            .chain(self.tags.values_mut().flatten())
+        {
+            if id == old {
+                *id = new.to_string();
+            }
+        }
+    }
+
     pub fn get_preset_mod_count(&self, name: &str) -> usize {
-        self.presets.get(name).map(|v| v.len()).unwrap_or(0)
+        self.get_preset_mods(name).len()
     }
 
     pub fn set_active_preset(&mut self, name: &str) {
@@ -199,12 +921,28 @@ impl Config {
     }
 
     pub fn save_preset(&mut self, name: String, mods: Vec<String>) {
-        self.presets.insert(name, mods);
+        self.presets
+            .insert(name, mods.into_iter().map(ModEntry::enabled).collect());
     }
 
     pub fn rename_preset(&mut self, old: &str, new: String) -> bool {
         if let Some(mods) = self.presets.remove(old) {
             let was_active = self.active_preset == old;
+            if let Some(order) = self.sort_orders.remove(old) {
+                self.sort_orders.insert(new.clone(), order);
+            }
+            if let Some(args) = self.preset_args.remove(old) {
+                self.preset_args.insert(new.clone(), args);
+            }
+            if let Some(filter) = self.mod_filters.remove(old) {
+                self.mod_filters.insert(new.clone(), filter);
+            }
+            if let Some(tag) = self.tag_filters.remove(old) {
+                self.tag_filters.insert(new.clone(), tag);
+            }
+            if let Some(favorites_only) = self.favorites_only.remove(old) {
+                self.favorites_only.insert(new.clone(), favorites_only);
+            }
             self.presets.insert(new.clone(), mods);
             if was_active {
                 self.active_preset = new;
@@ -221,6 +959,11 @@ impl Config {
             return false;
         }
         if self.presets.remove(name).is_some() {
+            self.sort_orders.remove(name);
+            self.preset_args.remove(name);
+            self.mod_filters.remove(name);
+            self.tag_filters.remove(name);
+            self.favorites_only.remove(name);
             // If we deleted the active preset, switch to another one
             if self.active_preset == name {
                 self.active_preset = self
@@ -242,16 +985,123 @@ impl Config {
     }
 
     pub fn save(&self) -> AppResult<()> {
-        let config_path = &Config::get_save_path()?;
+        self.save_to(&Config::get_save_path()?)
+    }
+
+    pub fn read() -> AppResult<Self> {
+        Config::read_from(&Config::get_save_path()?)
+    }
+
+    /// Reads the backup of the config, kept by the last save
+    pub fn read_backup() -> AppResult<Self> {
+        Config::read_backup_at(&Config::get_save_path()?)
+    }
+
+    /// Whether there is a backup of the config that can be read
+    pub fn has_backup() -> AppResult<bool> {
+        let backup_path = file_handler::with_suffix(&Config::get_save_path()?, ".bak");
+        Ok(file_handler::read_json::<Config>(&backup_path).is_ok())
+    }
+
+    /// Replaces the config with its backup
+    pub fn restore_backup() -> AppResult<()> {
+        Config::restore_backup_at(&Config::get_save_path()?)
+    }
+
+    /// Moves the config out of the way to start over with a new one, keeping it as
+    /// `config.json.corrupt`. Returns where it was moved to.
+    pub fn set_aside() -> AppResult<PathBuf> {
+        Config::set_aside_at(&Config::get_save_path()?)
+    }
+
+    fn read_backup_at(config_path: &Path) -> AppResult<Self> {
+        Config::read_from(&file_handler::with_suffix(config_path, ".bak"))
+    }
+
+    fn restore_backup_at(config_path: &Path) -> AppResult<()> {
+        let backup_path = file_handler::with_suffix(config_path, ".bak");
+        // Only a backup that can be read replaces the config
+        let backup: Config = file_handler::read_json(&backup_path)?;
+        file_handler::write_json(config_path, &backup)
+    }
+
+    fn set_aside_at(config_path: &Path) -> AppResult<PathBuf> {
+        let corrupt_path = file_handler::with_suffix(config_path, ".corrupt");
+        fs::rename(config_path, &corrupt_path)?;
+        Ok(corrupt_path)
+    }
+
+    /// Saves the config, keeping the previous one as a backup
+    fn save_to(&self, config_path: &Path) -> AppResult<()> {
         if let Some(parent) = config_path.parent() {
             utils::ensure_directory_exists(&parent.to_path_buf())?;
         }
-        super::file_handler::write_json(config_path, self)?;
+
+        // A config that can't be read would replace a good backup
+        if file_handler::read_json::<Config>(config_path).is_ok() {
+            fs::copy(config_path, file_handler::with_suffix(config_path, ".bak"))?;
+        }
+
+        file_handler::write_json(config_path, self)?;
         Ok(())
     }
 
-    pub fn read() -> AppResult<Self> {
-        let mut config: Config = super::file_handler::read_json(&Config::get_save_path()?)?;
+    /// Writes the whole config to the file, to set it up on another machine. With `portable`
+    /// the paths in the home directory are written relative to `~`.
+    pub fn export_to(&self, path: &Path, portable: bool) -> AppResult<()> {
+        let mut config = self.clone();
+        if portable {
+            let home_path = PathBuf::from(utils::get_home_path()?);
+            config.map_paths(|path| utils::contract_home(path, &home_path));
+        }
+        file_handler::write_json(path, &config)
+    }
+
+    /// Reads a config written by `export_to`. Paths that don't exist on this machine are
+    /// returned with it, so they can be pointed out instead of refusing the config.
+    pub fn import_from(path: &Path) -> AppResult<(Self, Vec<String>)> {
+        let mut config: Config = file_handler::read_json(path)?;
+        config.migrate_if_needed();
+        config.map_paths(utils::expand_tilde);
+
+        let paths = [
+            ("Game path", &config.game_path),
+            ("Workshop path", &config.workshop_path),
+        ]
+        .into_iter()
+        .chain(
+            config
+                .custom_mods_paths
+                .iter()
+                .map(|path| ("Custom mods path", path)),
+        );
+        let missing = paths
+            .filter(|(_, path)| !Path::new(path).exists())
+            .map(|(label, path)| format!("{} not found: {}", label, path))
+            .collect();
+
+        Ok((config, missing))
+    }
+
+    fn map_paths(&mut self, f: impl Fn(&str) -> String) {
+        self.game_path = f(&self.game_path);
+        self.workshop_path = f(&self.workshop_path);
+        self.custom_mods_paths = self.custom_mods_paths.iter().map(|path| f(path)).collect();
+    }
+
+    /// Reads the config. A config that isn't valid JSON is reported as corrupt, so the user
+    /// can choose between the backup and starting over instead of losing it silently.
+    fn read_from(config_path: &Path) -> AppResult<Self> {
+        let mut config: Config = match file_handler::read_json(config_path) {
+            Ok(config) => config,
+            Err(AppError::SerdeJsonError(e)) => {
+                return Err(AppError::CorruptConfig(
+                    config_path.display().to_string(),
+                    e.to_string(),
+                ))
+            }
+            Err(e) => return Err(e),
+        };
         config.migrate_if_needed();
         Ok(config)
     }
@@ -263,7 +1113,7 @@ mod tests {
 
     /// Helper to create a Config for testing without touching the filesystem.
     fn test_config() -> Config {
-        Config::new(String::new(), String::new(), None).unwrap()
+        Config::new(String::new(), String::new(), Vec::new()).unwrap()
     }
 
     // ── Preset CRUD ──
@@ -299,6 +1149,18 @@ mod tests {
         assert_eq!(config.get_enabled_mods(), vec!["1".to_string(), "2".to_string()]);
     }
 
+    #[test]
+    fn replace_mod_identifier_keeps_enabled_mods_in_sync() {
+        let mut config = test_config();
+        config.update_mods(vec!["@cba".into(), "@ace".into()]);
+        config.toggle_favorite("@cba");
+
+        config.replace_mod_identifier("@cba", "@CBA");
+        assert_eq!(config.get_enabled_mods(), vec!["@CBA", "@ace"]);
+        assert_eq!(enabled_ids(&config.enabled_mods), vec!["@CBA", "@ace"]);
+        assert!(config.is_favorite("@CBA"));
+    }
+
     #[test]
     fn set_active_preset_ignores_nonexistent() {
         let mut config = test_config();
@@ -383,6 +1245,314 @@ mod tests {
         assert_eq!(names, vec!["Alpha", "Default", "Zebra"]);
     }
 
+    #[test]
+    fn sort_order_is_kept_per_preset() {
+        let mut config = test_config();
+        config.save_preset("Other".to_string(), vec![]);
+        config.set_sort_order(SortOrder::Updated);
+        assert_eq!(config.get_sort_order(), SortOrder::Updated);
+
+        config.set_active_preset("Other");
+        assert_eq!(config.get_sort_order(), SortOrder::Name);
+
+        config.rename_preset("Default", "Main".to_string());
+        config.set_active_preset("Main");
+        assert_eq!(config.get_sort_order(), SortOrder::Updated);
+    }
+
+    #[test]
+    fn sort_order_falls_back_to_sort_enabled_first() {
+        let mut config = test_config();
+        config.sort_enabled_first = true;
+        assert_eq!(config.get_sort_order(), SortOrder::EnabledFirst);
+
+        config.set_sort_order(SortOrder::Name);
+        assert_eq!(config.get_sort_order(), SortOrder::Name);
+    }
+
+    #[test]
+    fn mod_filter_is_kept_per_preset() {
+        let mut config = test_config();
+        config.save_preset("Other".to_string(), vec![]);
+        config.set_mod_filter(ModFilter::Enabled);
+        assert_eq!(config.get_mod_filter(), ModFilter::Enabled);
+
+        config.set_active_preset("Other");
+        assert_eq!(config.get_mod_filter(), ModFilter::All);
+
+        config.rename_preset("Default", "Main".to_string());
+        config.set_active_preset("Main");
+        assert_eq!(config.get_mod_filter(), ModFilter::Enabled);
+
+        config.set_mod_filter(ModFilter::All);
+        assert!(config.mod_filters.is_empty());
+    }
+
+    #[test]
+    fn tag_and_favorites_filters_are_kept_per_preset() {
+        let mut config = test_config();
+        config.save_preset("Other".to_string(), vec![]);
+        config.toggle_tag("ACE", "@ace");
+        config.set_tag_filter(Some("ACE".to_string()));
+        config.set_favorites_only(true);
+        assert_eq!(config.get_tag_filter(), Some("ACE"));
+        assert!(config.get_favorites_only());
+
+        config.set_active_preset("Other");
+        assert_eq!(config.get_tag_filter(), None);
+        assert!(!config.get_favorites_only());
+
+        config.rename_preset("Default", "Main".to_string());
+        config.set_active_preset("Main");
+        assert_eq!(config.get_tag_filter(), Some("ACE"));
+        assert!(config.get_favorites_only());
+
+        config.set_favorites_only(false);
+        assert!(config.favorites_only.is_empty());
+
+        // The filter goes with the tag
+        config.toggle_tag("ACE", "@ace");
+        assert_eq!(config.get_tag_filter(), None);
+        assert!(config.tag_filters.is_empty());
+    }
+
+    #[test]
+    fn launch_args_are_kept_per_preset() {
+        let mut config = test_config();
+        config.set_default_args("-noSplash".to_string());
+        config.save_preset("Other".to_string(), vec![]);
+        config.set_active_preset("Other");
+        config.set_preset_args(Some("-par=other.par".to_string()));
+        assert_eq!(config.get_launch_args(), "-par=other.par");
+
+        config.set_active_preset("Default");
+        assert_eq!(config.get_launch_args(), "-noSplash");
+
+        config.set_active_preset("Other");
+
+        config.rename_preset("Other", "Renamed".to_string());
+        assert_eq!(config.get_launch_args(), "-par=other.par");
+
+        config.set_preset_args(None);
+        assert_eq!(config.get_launch_args(), "-noSplash");
+    }
+
+    // ── Servers ──
+
+    #[test]
+    fn parse_server_address() {
+        let server = Server::parse("192.168.1.10:2302", Some("secret".into())).unwrap();
+        assert_eq!(server.host, "192.168.1.10");
+        assert_eq!(server.port, 2302);
+        assert_eq!(server.password.as_deref(), Some("secret"));
+
+        let server = Server::parse(" arma.example.com:2402 ", Some(String::new())).unwrap();
+        assert_eq!(server.address(), "arma.example.com:2402");
+        assert_eq!(server.password, None);
+    }
+
+    #[test]
+    fn parse_server_address_rejects_invalid() {
+        for address in [
+            "",
+            "localhost",
+            ":2302",
+            "host:",
+            "host:0",
+            "host:99999",
+            "my host:2302",
+        ] {
+            assert!(Server::parse(address, None).is_err(), "{}", address);
+        }
+    }
+
+    #[test]
+    fn set_server_keeps_recent_servers() {
+        let mut config = test_config();
+        for port in 2300..2310 {
+            config.set_server(Some(
+                Server::parse(&format!("host:{}", port), None).unwrap(),
+            ));
+        }
+        config.set_server(Some(Server::parse("host:2305", None).unwrap()));
+
+        let recent: Vec<u16> = config.get_recent_servers().iter().map(|s| s.port).collect();
+        assert_eq!(recent, vec![2305, 2309, 2308, 2307, 2306]);
+
+        config.set_server(None);
+        assert!(config.get_server().is_none());
+        assert_eq!(config.get_recent_servers().len(), MAX_RECENT_SERVERS);
+    }
+
+    #[test]
+    fn empty_tags_are_removed() {
+        let mut config = test_config();
+
+        assert!(config.toggle_tag("ACE", "1"));
+        assert!(config.toggle_tag("ACE", "2"));
+        assert!(config.toggle_tag("Aircraft", "2"));
+        assert_eq!(config.get_tags(), vec!["ACE", "Aircraft"]);
+        assert!(config.has_tag("Aircraft", "2"));
+
+        assert!(!config.toggle_tag("Aircraft", "2"));
+        assert_eq!(config.get_tags(), vec!["ACE"]);
+        assert_eq!(config.get_tagged_mods("ACE"), ["1", "2"]);
+        assert!(config.get_tagged_mods("Aircraft").is_empty());
+    }
+
+    // ── Config location ──
+
+    #[test]
+    fn linux_config_path_prefers_xdg_config_home() {
+        let root = std::env::temp_dir().join("a3mm_xdg_config_test");
+        let _ = std::fs::remove_dir_all(&root);
+        let home = root.join("home");
+        let xdg = root.join("xdg");
+        let xdg_config = xdg.join("arma3-mod-manager-console").join("config.json");
+
+        // Nothing exists yet, so the new config goes in XDG_CONFIG_HOME
+        assert_eq!(linux_config_path(&home, Some(xdg.clone())), xdg_config);
+        assert_eq!(
+            linux_config_path(&home, None),
+            home.join(".config/arma3-mod-manager-console/config.json")
+        );
+        // Relative paths are ignored
+        assert_eq!(
+            linux_config_path(&home, Some(PathBuf::from("relative"))),
+            home.join(".config/arma3-mod-manager-console/config.json")
+        );
+
+        // Legacy configs are still read
+        let legacy = home.join("arma3-mod-manager-console-config.json");
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(&legacy, "{}").unwrap();
+        assert_eq!(linux_config_path(&home, Some(xdg.clone())), legacy);
+
+        // Until there is a config in XDG_CONFIG_HOME
+        std::fs::create_dir_all(xdg_config.parent().unwrap()).unwrap();
+        std::fs::write(&xdg_config, "{}").unwrap();
+        assert_eq!(linux_config_path(&home, Some(xdg)), xdg_config);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn save_keeps_a_backup_to_restore_when_the_config_is_corrupt() {
+        let dir = std::env::temp_dir().join("a3mm_config_backup_test");
+        let _ = fs::remove_dir_all(&dir);
+        let config_path = dir.join("config.json");
+
+        let mut config = test_config();
+        config.save_preset("First".to_string(), vec![]);
+        config.save_to(&config_path).unwrap();
+        // Nothing to back up on the first save
+        assert!(!dir.join("config.json.bak").exists());
+
+        config.save_preset("Second".to_string(), vec![]);
+        config.save_to(&config_path).unwrap();
+        assert!(dir.join("config.json.bak").exists());
+        assert!(!dir.join("config.json.tmp").exists());
+
+        fs::write(&config_path, "{ \"game_path\": ").unwrap();
+        assert!(matches!(
+            Config::read_from(&config_path),
+            Err(AppError::CorruptConfig(..))
+        ));
+
+        // A corrupt config is not backed up over the good backup
+        config.save_to(&config_path).unwrap();
+        let backup = Config::read_from(&dir.join("config.json.bak")).unwrap();
+        assert!(!backup.get_preset_names().contains(&"Second".to_string()));
+
+        fs::write(&config_path, "{ \"game_path\": ").unwrap();
+        let backup = Config::read_backup_at(&config_path).unwrap();
+        assert!(backup.get_preset_names().contains(&"First".to_string()));
+
+        Config::restore_backup_at(&config_path).unwrap();
+        assert!(!dir.join("config.json.tmp").exists());
+        let restored = Config::read_from(&config_path).unwrap();
+        assert!(restored.get_preset_names().contains(&"First".to_string()));
+        assert!(!restored.get_preset_names().contains(&"Second".to_string()));
+
+        fs::write(&config_path, "{ \"game_path\": ").unwrap();
+        let corrupt_path = Config::set_aside_at(&config_path).unwrap();
+        assert_eq!(corrupt_path, dir.join("config.json.corrupt"));
+        assert!(!config_path.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn page_size_is_at_least_one() {
+        let config = test_config();
+        assert_eq!(config.get_page_size(), None);
+        assert_eq!(config.get_initial_page_size(), DEFAULT_PAGE_SIZE);
+
+        let mut value = serde_json::to_value(&config).unwrap();
+        value["page_size"] = 0.into();
+        let config: Config = serde_json::from_value(value).unwrap();
+        assert_eq!(config.get_page_size(), Some(1));
+    }
+
+    #[test]
+    fn missing_workshop_folder_is_valid() {
+        let dir = std::env::temp_dir().join("a3mm_config_workshop_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let config = Config::new(
+            dir.to_string_lossy().to_string(),
+            dir.join("workshop").to_string_lossy().to_string(),
+            Vec::new(),
+        )
+        .unwrap();
+        let missing_game = Config::new(
+            dir.join("Arma 3").to_string_lossy().to_string(),
+            dir.to_string_lossy().to_string(),
+            Vec::new(),
+        )
+        .unwrap();
+
+        assert!(config.is_valid());
+        assert!(!config.has_workshop_path());
+        assert!(!missing_game.is_valid());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn import_keeps_the_config_and_reports_missing_paths() {
+        let dir = std::env::temp_dir().join("a3mm_config_import_test");
+        let _ = fs::remove_dir_all(&dir);
+        let game_path = dir.join("Arma 3");
+        fs::create_dir_all(&game_path).unwrap();
+
+        let mut config = Config::new(
+            game_path.to_string_lossy().to_string(),
+            dir.join("workshop").to_string_lossy().to_string(),
+            Vec::new(),
+        )
+        .unwrap();
+        config.set_executable_name("arma3_x64".to_string());
+        config.export_to(&dir.join("export.json"), false).unwrap();
+
+        let (imported, missing) = Config::import_from(&dir.join("export.json")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(imported.get_game_path(), game_path);
+        assert_eq!(imported.get_executable_name(), "arma3_x64");
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].starts_with("Workshop path not found"));
+    }
+
+    #[test]
+    fn read_missing_config_is_not_found() {
+        let path = std::env::temp_dir().join("a3mm_config_missing_test.json");
+        match Config::read_from(&path) {
+            Err(AppError::IoError(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("expected NotFound, got {:?}", other.map(|_| ())),
+        }
+    }
+
     // ── Serde roundtrip ──
 
     #[test]
@@ -404,6 +1574,41 @@ mod tests {
         assert_eq!(config.get_preset_mod_count("Default"), 2);
     }
 
+    #[test]
+    fn deserialize_old_custom_mods_path() {
+        let config = |custom_mods_path: &str| -> Config {
+            let json = format!(
+                r#"{{
+                    "game_path": "/game",
+                    "workshop_path": "/workshop",
+                    {},
+                    "enabled_mods": [],
+                    "default_args": ""
+                }}"#,
+                custom_mods_path
+            );
+            serde_json::from_str(&json).unwrap()
+        };
+
+        let paths = |config: &Config| -> Vec<PathBuf> {
+            config
+                .get_custom_mods_paths()
+                .iter()
+                .map(|path| path.to_path_buf())
+                .collect()
+        };
+
+        assert!(paths(&config(r#""custom_mods_path": null"#)).is_empty());
+        assert_eq!(
+            paths(&config(r#""custom_mods_path": "/mods""#)),
+            [PathBuf::from("/mods")]
+        );
+        assert_eq!(
+            paths(&config(r#""custom_mods_paths": ["/dev", "/shared"]"#)),
+            [PathBuf::from("/dev"), PathBuf::from("/shared")]
+        );
+    }
+
     #[test]
     fn deserialize_config_with_presets() {
         let json = r#"{
@@ -447,6 +1652,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn disabled_entries_are_kept_but_not_enabled() {
+        let json = r#"{
+            "game_path": "/game",
+            "workshop_path": "/workshop",
+            "custom_mods_path": null,
+            "enabled_mods": [],
+            "default_args": "",
+            "presets": {
+                "Default": ["1", { "id": "2", "enabled": false }, { "id": 3 }, { "note": "stray" }]
+            }
+        }"#;
+
+        let mut config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.get_enabled_mods(), vec!["1", "3"]);
+        assert_eq!(config.get_preset_mod_count("Default"), 2);
+
+        config.update_mods(vec!["3".into()]);
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            value["presets"]["Default"],
+            serde_json::json!(["3", { "id": "2", "enabled": false }])
+        );
+
+        // Enabling the mod again replaces its disabled entry
+        config.update_mods(vec!["3".into(), "2".into()]);
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["presets"]["Default"], serde_json::json!(["3", "2"]));
+    }
+
     #[test]
     fn serialize_then_deserialize_roundtrip() {
         let mut config = test_config();
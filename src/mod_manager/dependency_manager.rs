@@ -1,25 +1,309 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
 use regex::Regex;
-use ureq;
+use serde::{Deserialize, Serialize};
 
 use crate::errors::{AppError, AppResult};
 
+use super::{config::Config, file_handler};
+
+/// Maximum number of concurrent requests to the Steam Workshop
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// How many levels of dependencies-of-dependencies are followed
+const MAX_DEPENDENCY_DEPTH: usize = 5;
+
+/// Maximum number of workshop pages fetched when resolving dependencies recursively
+const MAX_DEPENDENCY_REQUESTS: usize = 50;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// For the whole request, including reading the page
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Waited before the first retry, doubled for every retry after it
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Waited when rate limited without a `Retry-After` header
+const RATE_LIMIT_WAIT: Duration = Duration::from_secs(5);
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependency {
     pub id: String,
     pub name: String,
 }
 
-pub fn fetch_dependencies(workshop_id: &str) -> AppResult<Vec<Dependency>> {
+/// A dependency in the dependency tree of a mod
+#[derive(Debug)]
+pub struct TreeNode {
+    pub dependency: Dependency,
+    /// 1 for direct dependencies
+    pub depth: usize,
+    /// Requires a mod above it in the tree, so its dependencies are not shown again
+    pub cycle: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    dependencies: Vec<Dependency>,
+    /// Seconds since the Unix epoch
+    fetched_at: u64,
+}
+
+/// Background dependency checks save their caches one at a time
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Dependencies fetched earlier, stored next to the config
+/// so repeated checks don't have to hit the Steam Workshop
+pub struct DependencyCache {
+    path: Option<PathBuf>,
+    ttl: Duration,
+    /// How often fetching items that aren't cached is retried
+    retries: u32,
+    /// Only cached dependencies are used, the Steam Workshop isn't contacted
+    offline: bool,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl DependencyCache {
+    /// Reads the cache from disk, starting empty when there is none
+    pub fn load(ttl: Duration, retries: u32, offline: bool) -> Self {
+        let path = get_cache_path().ok();
+        let entries = path
+            .as_ref()
+            .and_then(|path| file_handler::read_json(path).ok())
+            .unwrap_or_default();
+
+        DependencyCache {
+            path,
+            ttl,
+            retries,
+            offline,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Writes the cache to disk, keeping the entries saved by other checks since it was loaded.
+    /// The newer entry wins when both have one for an item.
+    pub fn save(&self) -> AppResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let _saving = SAVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let saved: HashMap<String, CacheEntry> = file_handler::read_json(path).unwrap_or_default();
+        for (workshop_id, entry) in saved {
+            let is_newer = entries
+                .get(&workshop_id)
+                .is_none_or(|current| current.fetched_at < entry.fetched_at);
+            if is_newer {
+                entries.insert(workshop_id, entry);
+            }
+        }
+        file_handler::write_json(path, &*entries)
+    }
+
+    /// The cached dependencies, if they have not expired yet
+    fn get(&self, workshop_id: &str, allow_expired: bool) -> Option<Vec<Dependency>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(workshop_id)
+            .filter(|entry| {
+                allow_expired || now().saturating_sub(entry.fetched_at) < self.ttl.as_secs()
+            })
+            .map(|entry| entry.dependencies.clone())
+    }
+
+    fn insert(&self, workshop_id: &str, dependencies: &[Dependency]) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(
+            workshop_id.to_string(),
+            CacheEntry {
+                dependencies: dependencies.to_vec(),
+                fetched_at: now(),
+            },
+        );
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn get_cache_path() -> AppResult<PathBuf> {
+    let config_path = Config::get_save_path()?;
+
+    // On Windows the config is stored directly in the home folder
+    let file_name = match std::env::consts::OS {
+        "windows" => "arma3-mod-manager-console-dependency-cache.json",
+        _ => "dependency_cache.json",
+    };
+    Ok(config_path.with_file_name(file_name))
+}
+
+/// Fetches the dependencies of a workshop item, unless they are cached.
+/// Expired entries are still used when the Steam Workshop can't be reached or when offline.
+pub fn fetch_dependencies(
+    workshop_id: &str,
+    cache: &DependencyCache,
+) -> AppResult<Vec<Dependency>> {
+    if let Some(dependencies) = cache.get(workshop_id, false) {
+        return Ok(dependencies);
+    }
+    if cache.offline {
+        return cache.get(workshop_id, true).ok_or(AppError::Offline);
+    }
+
+    match fetch_workshop_dependencies(workshop_id, cache.retries) {
+        Ok(dependencies) => {
+            cache.insert(workshop_id, &dependencies);
+            Ok(dependencies)
+        }
+        Err(e) => cache.get(workshop_id, true).ok_or(e),
+    }
+}
+
+/// Why a request to the Steam Workshop failed, and whether trying again may help
+#[derive(Debug)]
+enum FetchError {
+    /// Worth retrying after a backoff, e.g. a timeout or a server error
+    Transient(AppError),
+    /// Retried after the wait from the `Retry-After` header
+    RateLimited(Option<Duration>),
+    Fatal(AppError),
+}
+
+impl From<FetchError> for AppError {
+    fn from(error: FetchError) -> Self {
+        match error {
+            FetchError::Transient(e) | FetchError::Fatal(e) => e,
+            FetchError::RateLimited(_) => AppError::RateLimited,
+        }
+    }
+}
+
+impl FetchError {
+    fn from_ureq(error: ureq::Error, workshop_id: &str) -> Self {
+        match error {
+            ureq::Error::Timeout(_) => {
+                FetchError::Transient(AppError::NetworkTimeout(workshop_id.to_string()))
+            }
+            ureq::Error::Io(_) | ureq::Error::ConnectionFailed => {
+                FetchError::Transient(AppError::NetworkError(error.to_string()))
+            }
+            _ => FetchError::Fatal(AppError::NetworkError(error.to_string())),
+        }
+    }
+
+    /// How long to wait before the retry after `attempt` failed attempts,
+    /// `None` when it should not be retried
+    fn retry_delay(&self, attempt: u32) -> Option<Duration> {
+        match self {
+            FetchError::Transient(_) => Some(RETRY_BACKOFF * 2u32.saturating_pow(attempt)),
+            FetchError::RateLimited(wait) => {
+                Some(wait.unwrap_or(RATE_LIMIT_WAIT).min(MAX_RATE_LIMIT_WAIT))
+            }
+            FetchError::Fatal(_) => None,
+        }
+    }
+}
+
+/// Fetches the workshop page of the item, retrying transient failures up to `retries` times
+fn fetch_workshop_page(workshop_id: &str, retries: u32) -> AppResult<String> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_connect(Some(CONNECT_TIMEOUT))
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        // Checked in `request_workshop_page`, to read `Retry-After` when rate limited
+        .http_status_as_error(false)
+        .build()
+        .into();
+
+    let mut attempt = 0;
+    loop {
+        let error = match request_workshop_page(&agent, workshop_id) {
+            Ok(body) => return Ok(body),
+            Err(error) => error,
+        };
+
+        match error.retry_delay(attempt) {
+            Some(delay) if attempt < retries => thread::sleep(delay),
+            _ => return Err(error.into()),
+        }
+        attempt += 1;
+    }
+}
+
+fn request_workshop_page(agent: &ureq::Agent, workshop_id: &str) -> Result<String, FetchError> {
     let url = format!(
         "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
         workshop_id
     );
 
-    let body = ureq::get(&url)
+    let mut response = agent
+        .get(&url)
         .call()
-        .map_err(|e| AppError::NetworkError(e.to_string()))?
-        .into_body()
+        .map_err(|e| FetchError::from_ureq(e, workshop_id))?;
+
+    match response.status().as_u16() {
+        200..=299 => {}
+        429 => {
+            let wait = response
+                .headers()
+                .get("retry-after")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return Err(FetchError::RateLimited(wait));
+        }
+        404 | 410 => {
+            return Err(FetchError::Fatal(AppError::WorkshopItemNotFound(
+                workshop_id.to_string(),
+            )))
+        }
+        status @ 500..=599 => {
+            return Err(FetchError::Transient(AppError::NetworkError(format!(
+                "HTTP status {}",
+                status
+            ))))
+        }
+        status => {
+            return Err(FetchError::Fatal(AppError::NetworkError(format!(
+                "HTTP status {}",
+                status
+            ))))
+        }
+    }
+
+    let body = response
+        .body_mut()
         .read_to_string()
-        .map_err(|e| AppError::NetworkError(e.to_string()))?;
+        .map_err(|e| FetchError::from_ureq(e, workshop_id))?;
+
+    // Private and removed items are served as an error page
+    if body.contains("<title>Steam Community :: Error</title>") {
+        return Err(FetchError::Fatal(AppError::WorkshopItemNotFound(
+            workshop_id.to_string(),
+        )));
+    }
+
+    Ok(body)
+}
+
+fn fetch_workshop_dependencies(workshop_id: &str, retries: u32) -> AppResult<Vec<Dependency>> {
+    let body = fetch_workshop_page(workshop_id, retries)?;
 
     // Extract the "RequiredItems" section to avoid false positives
     // The section starts with id="RequiredItems" and ends... well, it closes the div.
@@ -53,3 +337,581 @@ pub fn fetch_dependencies(workshop_id: &str) -> AppResult<Vec<Dependency>> {
 
     Ok(dependencies)
 }
+
+/// Fetches the dependencies of all given workshop items concurrently.
+/// `on_progress` is called with the number of completed and total fetches.
+/// The results are returned in the same order as `workshop_ids`.
+pub fn fetch_dependencies_batch<F>(
+    workshop_ids: &[String],
+    cache: &DependencyCache,
+    mut on_progress: F,
+) -> Vec<AppResult<Vec<Dependency>>>
+where
+    F: FnMut(usize, usize),
+{
+    let total = workshop_ids.len();
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let mut results = Vec::with_capacity(total);
+
+    thread::scope(|scope| {
+        for _ in 0..MAX_CONCURRENT_REQUESTS.min(total) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some(workshop_id) = workshop_ids.get(index) else {
+                    break;
+                };
+                if sender
+                    .send((index, fetch_dependencies(workshop_id, cache)))
+                    .is_err()
+                {
+                    break;
+                }
+            });
+        }
+        // Only the workers hold senders now, so the receiver ends once they are done
+        drop(sender);
+
+        on_progress(0, total);
+        for (index, result) in receiver {
+            results.push((index, result));
+            on_progress(results.len(), total);
+        }
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Resolves the dependencies of the given workshop items, including dependencies of dependencies.
+/// Each level of the graph is fetched concurrently. Items that can't be fetched are listed in
+/// `failed` and the walk goes on without them.
+/// `on_progress` is called like in `fetch_dependencies_batch`, the total grows as more levels
+/// are found.
+pub fn resolve_dependency_graph<F>(
+    root_ids: &[String],
+    cache: &DependencyCache,
+    mut on_progress: F,
+) -> DependencyGraph
+where
+    F: FnMut(usize, usize),
+{
+    let mut fetched = 0;
+    resolve_levels(root_ids, |ids| {
+        let results = fetch_dependencies_batch(ids, cache, |done, total| {
+            on_progress(fetched + done, fetched + total)
+        });
+        fetched += ids.len();
+        results
+    })
+}
+
+/// Like `resolve_dependency_graph`, but fails when one of the root items can't be fetched,
+/// since there is nothing to show for it then
+pub fn resolve_all_dependencies<F>(
+    root_ids: &[String],
+    cache: &DependencyCache,
+    on_progress: F,
+) -> AppResult<DependencyGraph>
+where
+    F: FnMut(usize, usize),
+{
+    let mut graph = resolve_dependency_graph(root_ids, cache, on_progress);
+    let failed_root = graph
+        .failed
+        .iter()
+        .position(|(id, _)| root_ids.contains(id));
+    match failed_root {
+        Some(index) => Err(graph.failed.swap_remove(index).1),
+        None => Ok(graph),
+    }
+}
+
+/// Dependencies found by walking the dependency graph
+#[derive(Debug)]
+pub struct DependencyGraph {
+    roots: Vec<String>,
+    /// Every dependency once, in breadth-first order. The root items are not included.
+    pub dependencies: Vec<Dependency>,
+    /// Direct dependencies of each fetched workshop item
+    edges: HashMap<String, Vec<Dependency>>,
+    /// Items whose dependencies couldn't be fetched, with the reason
+    pub failed: Vec<(String, AppError)>,
+    /// The walk stopped at `MAX_DEPENDENCY_DEPTH` or `MAX_DEPENDENCY_REQUESTS`
+    /// with dependencies left to follow
+    pub truncated: bool,
+}
+
+impl DependencyGraph {
+    /// Whether dependencies may be missing from the graph
+    pub fn is_incomplete(&self) -> bool {
+        self.truncated || !self.failed.is_empty()
+    }
+
+    /// Every item required by a fetched item, with the number of fetched items requiring it,
+    /// in the order they were found. Unlike `dependencies` it includes root items that other
+    /// root items require.
+    pub fn required(&self) -> Vec<(Dependency, usize)> {
+        let mut required: Vec<(Dependency, usize)> = Vec::new();
+        let fetched = self
+            .roots
+            .iter()
+            .chain(self.dependencies.iter().map(|d| &d.id));
+
+        for dependency in fetched.filter_map(|id| self.edges.get(id)).flatten() {
+            match required.iter_mut().find(|(d, _)| d.id == dependency.id) {
+                Some((_, count)) => *count += 1,
+                None => required.push((dependency.clone(), 1)),
+            }
+        }
+        required
+    }
+
+    /// The dependencies of the root item as a tree, listed depth-first.
+    /// Dependencies shared by several mods appear under each of them.
+    pub fn tree(&self, root_id: &str) -> Vec<TreeNode> {
+        let mut nodes = Vec::new();
+        let mut path = vec![root_id.to_string()];
+        flatten_tree(&self.edges, &mut path, &mut nodes);
+        nodes
+    }
+}
+
+/// Adds the dependencies of the last item in `path`, each followed by its own dependencies
+fn flatten_tree(
+    edges: &HashMap<String, Vec<Dependency>>,
+    path: &mut Vec<String>,
+    nodes: &mut Vec<TreeNode>,
+) {
+    let Some(dependencies) = path.last().and_then(|id| edges.get(id)) else {
+        return;
+    };
+
+    for dependency in dependencies {
+        let cycle = path.contains(&dependency.id);
+        nodes.push(TreeNode {
+            dependency: dependency.clone(),
+            depth: path.len(),
+            cycle,
+        });
+
+        if !cycle {
+            path.push(dependency.id.clone());
+            flatten_tree(edges, path, nodes);
+            path.pop();
+        }
+    }
+}
+
+/// Breadth-first walk over the dependency graph, one level per `fetch_level` call
+fn resolve_levels<F>(root_ids: &[String], mut fetch_level: F) -> DependencyGraph
+where
+    F: FnMut(&[String]) -> Vec<AppResult<Vec<Dependency>>>,
+{
+    // Guards against cycles, since mods can require each other
+    let mut visited: HashSet<String> = root_ids.iter().cloned().collect();
+    let mut dependencies = Vec::new();
+    let mut edges = HashMap::new();
+    let mut failed = Vec::new();
+
+    let mut level = root_ids.to_vec();
+    let mut requests = 0;
+    let mut truncated = false;
+
+    for _ in 0..MAX_DEPENDENCY_DEPTH {
+        let remaining = MAX_DEPENDENCY_REQUESTS - requests;
+        if level.len() > remaining {
+            level.truncate(remaining);
+            truncated = true;
+        }
+        if level.is_empty() {
+            break;
+        }
+        requests += level.len();
+
+        let mut next_level = Vec::new();
+        for (id, result) in level.iter().zip(fetch_level(&level)) {
+            let fetched = match result {
+                Ok(fetched) => fetched,
+                // A removed dependency is still listed, it just has nothing more to follow.
+                // Offline, the dependencies that aren't cached are left unfollowed the same way.
+                Err(AppError::WorkshopItemNotFound(_) | AppError::Offline)
+                    if !root_ids.contains(id) =>
+                {
+                    Vec::new()
+                }
+                Err(e) => {
+                    failed.push((id.clone(), e));
+                    continue;
+                }
+            };
+            for dependency in &fetched {
+                if visited.insert(dependency.id.clone()) {
+                    next_level.push(dependency.id.clone());
+                    dependencies.push(dependency.clone());
+                }
+            }
+            edges.insert(id.clone(), fetched);
+        }
+
+        level = next_level;
+    }
+
+    DependencyGraph {
+        roots: root_ids.to_vec(),
+        dependencies,
+        edges,
+        failed,
+        // Deeper levels are left out as well
+        truncated: truncated || !level.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn fake_graph(edges: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        edges
+            .iter()
+            .map(|(id, deps)| (id.to_string(), deps.iter().map(|d| d.to_string()).collect()))
+            .collect()
+    }
+
+    fn resolve(graph: &HashMap<String, Vec<String>>, roots: &[&str]) -> (Vec<String>, usize) {
+        let (result, requests) = walk(graph, roots);
+        (
+            result.dependencies.into_iter().map(|d| d.id).collect(),
+            requests,
+        )
+    }
+
+    fn walk(graph: &HashMap<String, Vec<String>>, roots: &[&str]) -> (DependencyGraph, usize) {
+        let roots: Vec<String> = roots.iter().map(|r| r.to_string()).collect();
+        let mut requests = 0;
+
+        let result = resolve_levels(&roots, |ids| {
+            requests += ids.len();
+            ids.iter()
+                .map(|id| {
+                    Ok(graph
+                        .get(id)
+                        .into_iter()
+                        .flatten()
+                        .map(|dep| Dependency {
+                            id: dep.clone(),
+                            name: format!("Mod {}", dep),
+                        })
+                        .collect())
+                })
+                .collect()
+        });
+
+        (result, requests)
+    }
+
+    fn tree(graph: &HashMap<String, Vec<String>>, root: &str) -> Vec<(String, usize, bool)> {
+        let (result, _) = walk(graph, &[root]);
+        let mut nodes = Vec::new();
+        flatten_tree(&result.edges, &mut vec![root.to_string()], &mut nodes);
+
+        nodes
+            .into_iter()
+            .map(|node| (node.dependency.id, node.depth, node.cycle))
+            .collect()
+    }
+
+    #[test]
+    fn tree_lists_shared_dependencies_under_each_mod() {
+        let graph = fake_graph(&[("1", &["2", "3"]), ("2", &["4"]), ("3", &["4"])]);
+
+        assert_eq!(
+            tree(&graph, "1"),
+            vec![
+                ("2".to_string(), 1, false),
+                ("4".to_string(), 2, false),
+                ("3".to_string(), 1, false),
+                ("4".to_string(), 2, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn tree_marks_cycles() {
+        let graph = fake_graph(&[("1", &["2"]), ("2", &["1", "3"]), ("3", &["2"])]);
+
+        assert_eq!(
+            tree(&graph, "1"),
+            vec![
+                ("2".to_string(), 1, false),
+                ("1".to_string(), 2, true),
+                ("3".to_string(), 2, false),
+                ("2".to_string(), 3, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_nested_dependencies_once() {
+        let graph = fake_graph(&[("1", &["2", "3"]), ("2", &["4"]), ("3", &["4"])]);
+
+        let (dependencies, requests) = resolve(&graph, &["1"]);
+        assert_eq!(dependencies, vec!["2", "3", "4"]);
+        assert_eq!(requests, 4);
+    }
+
+    #[test]
+    fn stops_at_cycles() {
+        let graph = fake_graph(&[("1", &["2"]), ("2", &["1", "3"]), ("3", &["2"])]);
+
+        let (dependencies, _) = resolve(&graph, &["1"]);
+        assert_eq!(dependencies, vec!["2", "3"]);
+    }
+
+    #[test]
+    fn limits_depth() {
+        let chain: Vec<(String, Vec<String>)> = (0..20)
+            .map(|i| (i.to_string(), vec![(i + 1).to_string()]))
+            .collect();
+        let graph: HashMap<String, Vec<String>> = chain.into_iter().collect();
+
+        let (dependencies, requests) = resolve(&graph, &["0"]);
+        assert_eq!(dependencies.len(), MAX_DEPENDENCY_DEPTH);
+        assert_eq!(requests, MAX_DEPENDENCY_DEPTH);
+    }
+
+    #[test]
+    fn cut_short_graphs_are_marked_truncated() {
+        let roots = vec!["0".to_string()];
+        let wide = |ids: &[String]| -> Vec<AppResult<Vec<Dependency>>> {
+            ids.iter()
+                .map(|id| {
+                    Ok((0..20)
+                        .map(|i| Dependency {
+                            id: format!("{}.{}", id, i),
+                            name: String::new(),
+                        })
+                        .collect())
+                })
+                .collect()
+        };
+        assert!(resolve_levels(&roots, wide).truncated);
+
+        let graph = fake_graph(&[("1", &["2", "3"]), ("2", &["4"]), ("3", &["4"])]);
+        let (complete, _) = walk(&graph, &["1"]);
+        assert!(!complete.is_incomplete());
+    }
+
+    #[test]
+    fn required_counts_every_item_requiring_it() {
+        let graph = fake_graph(&[("1", &["2", "3"]), ("2", &["3"]), ("4", &["1"])]);
+        let (result, _) = walk(&graph, &["1", "4"]);
+
+        let required: Vec<(String, usize)> = result
+            .required()
+            .into_iter()
+            .map(|(dependency, count)| (dependency.id, count))
+            .collect();
+        assert_eq!(
+            required,
+            vec![
+                ("2".to_string(), 1),
+                ("3".to_string(), 2),
+                ("1".to_string(), 1)
+            ]
+        );
+    }
+
+    fn memory_cache(ttl: Duration) -> DependencyCache {
+        DependencyCache {
+            path: None,
+            ttl,
+            retries: 0,
+            offline: false,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[test]
+    fn cache_returns_fresh_entries() {
+        let cache = memory_cache(Duration::from_secs(60));
+        let dependency = Dependency {
+            id: "450814997".to_string(),
+            name: "CBA_A3".to_string(),
+        };
+        cache.insert("463939057", &[dependency]);
+
+        let cached = cache.get("463939057", false).unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].id, "450814997");
+        assert!(cache.get("123", false).is_none());
+
+        // Served without a network request
+        assert_eq!(fetch_dependencies("463939057", &cache).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn concurrent_saves_keep_each_others_entries() {
+        let path = std::env::temp_dir().join("a3mm_dependency_cache_save_test.json");
+        let _ = std::fs::remove_file(&path);
+
+        let handles: Vec<_> = ["1", "2", "3", "4"]
+            .into_iter()
+            .map(|workshop_id| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    let cache = DependencyCache {
+                        path: Some(path),
+                        ..memory_cache(Duration::from_secs(60))
+                    };
+                    cache.insert(workshop_id, &[]);
+                    cache.save().unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let saved: HashMap<String, CacheEntry> = file_handler::read_json(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let mut ids: Vec<&String> = saved.keys().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn cache_entries_expire() {
+        let cache = memory_cache(Duration::ZERO);
+        cache.insert("463939057", &[]);
+
+        assert!(cache.get("463939057", false).is_none());
+        assert!(cache.get("463939057", true).is_some());
+    }
+
+    #[test]
+    fn offline_only_uses_the_cache() {
+        let mut cache = memory_cache(Duration::ZERO);
+        cache.offline = true;
+        cache.insert("463939057", &[]);
+
+        // Expired, but still better than nothing
+        assert!(fetch_dependencies("463939057", &cache).unwrap().is_empty());
+        assert!(matches!(
+            fetch_dependencies("450814997", &cache),
+            Err(AppError::Offline)
+        ));
+    }
+
+    #[test]
+    fn fetch_errors_are_recorded_and_the_walk_goes_on() {
+        let roots = vec!["1".to_string()];
+        let graph = resolve_levels(&roots, |ids| {
+            ids.iter()
+                .map(|id| match id.as_str() {
+                    "1" => Ok(vec![
+                        Dependency {
+                            id: "2".to_string(),
+                            name: "Timed out".to_string(),
+                        },
+                        Dependency {
+                            id: "3".to_string(),
+                            name: "Fine".to_string(),
+                        },
+                    ]),
+                    "2" => Err(AppError::NetworkTimeout(id.to_string())),
+                    "3" => Ok(vec![Dependency {
+                        id: "4".to_string(),
+                        name: "Nested".to_string(),
+                    }]),
+                    _ => Ok(Vec::new()),
+                })
+                .collect()
+        });
+
+        let ids: Vec<&str> = graph.dependencies.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3", "4"]);
+        assert_eq!(graph.failed.len(), 1);
+        assert_eq!(graph.failed[0].0, "2");
+        assert!(graph.is_incomplete() && !graph.truncated);
+    }
+
+    #[test]
+    fn removed_dependencies_are_not_followed() {
+        let roots = vec!["1".to_string()];
+        let graph = resolve_levels(&roots, |ids| {
+            ids.iter()
+                .map(|id| match id.as_str() {
+                    "1" => Ok(vec![Dependency {
+                        id: "2".to_string(),
+                        name: "Removed".to_string(),
+                    }]),
+                    id => Err(AppError::WorkshopItemNotFound(id.to_string())),
+                })
+                .collect()
+        });
+        assert_eq!(graph.dependencies.len(), 1);
+        assert!(graph.failed.is_empty());
+
+        // Unless it is the item that was looked up
+        let graph = resolve_levels(&roots, |ids| {
+            ids.iter()
+                .map(|id| Err(AppError::WorkshopItemNotFound(id.clone())))
+                .collect()
+        });
+        assert!(matches!(
+            graph.failed.as_slice(),
+            [(_, AppError::WorkshopItemNotFound(_))]
+        ));
+    }
+
+    #[test]
+    fn failed_root_items_are_returned_as_errors() {
+        let mut cache = memory_cache(Duration::ZERO);
+        cache.offline = true;
+        cache.insert(
+            "1",
+            &[Dependency {
+                id: "2".to_string(),
+                name: "Not cached".to_string(),
+            }],
+        );
+
+        let graph = resolve_all_dependencies(&["1".to_string()], &cache, |_, _| {}).unwrap();
+        assert_eq!(graph.dependencies.len(), 1);
+        assert!(!graph.is_incomplete());
+
+        assert!(matches!(
+            resolve_all_dependencies(&["3".to_string()], &cache, |_, _| {}),
+            Err(AppError::Offline)
+        ));
+    }
+
+    #[test]
+    fn retry_delay_backs_off() {
+        let timeout = FetchError::Transient(AppError::NetworkTimeout("1".to_string()));
+        assert_eq!(timeout.retry_delay(0), Some(RETRY_BACKOFF));
+        assert_eq!(timeout.retry_delay(2), Some(RETRY_BACKOFF * 4));
+
+        let rate_limited = FetchError::RateLimited(Some(Duration::from_secs(3600)));
+        assert_eq!(rate_limited.retry_delay(0), Some(MAX_RATE_LIMIT_WAIT));
+        assert_eq!(
+            FetchError::RateLimited(None).retry_delay(0),
+            Some(RATE_LIMIT_WAIT)
+        );
+
+        let not_found = FetchError::Fatal(AppError::WorkshopItemNotFound("1".to_string()));
+        assert_eq!(not_found.retry_delay(0), None);
+        assert!(matches!(
+            AppError::from(FetchError::RateLimited(None)),
+            AppError::RateLimited
+        ));
+    }
+}
@@ -1,14 +1,45 @@
 use std::{
-    fs,
-    io::{BufWriter, Write},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
     path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
+use regex::Regex;
 use serde::{de::DeserializeOwned, Serialize};
+use zip::ZipArchive;
 
-use crate::errors::AppResult;
+use crate::errors::{AppError, AppResult};
 
+use super::{Mod, ARMA3_CDLC_APP_IDS};
+
+/// Numbers the writes of this process, so writes running at the same time never share a
+/// temporary file
+static WRITE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes to a temporary file first and renames it into place,
+/// so the file is never left half-written
 pub fn write_json<T>(path: &Path, data: T) -> AppResult<()>
+where
+    T: Serialize,
+{
+    let temp_path = with_suffix(
+        path,
+        &format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            WRITE_COUNT.fetch_add(1, Ordering::Relaxed)
+        ),
+    );
+
+    let result = write_json_to(&temp_path, data).and_then(|_| Ok(fs::rename(&temp_path, path)?));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_json_to<T>(path: &Path, data: T) -> AppResult<()>
 where
     T: Serialize,
 {
@@ -16,10 +47,18 @@ where
     let mut writer = BufWriter::new(&file);
     serde_json::to_writer_pretty(&mut writer, &data)?;
     writer.flush()?;
+    file.sync_all()?;
 
     Ok(())
 }
 
+/// The path with `suffix` appended to the file name, e.g. `config.json.bak`
+pub fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
 pub fn read_json<T>(path: &Path) -> AppResult<T>
 where
     T: DeserializeOwned,
@@ -31,26 +70,973 @@ where
     Ok(data)
 }
 
-pub fn remove_dir_symlinks(path: &Path) -> AppResult<()> {
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries {
-            let path = entry?.path();
+/// Marks a mod directory as copied into the game directory by the manager
+const COPY_MARKER: &str = ".arma3-mod-manager-copy";
+
+/// Mods previously linked or copied into the given directory from one of `mod_dirs`.
+/// Symlinks pointing anywhere else were not made by the manager and are left out.
+pub fn find_linked_mods(path: &Path, mod_dirs: &[&Path]) -> Vec<PathBuf> {
+    let targets = link_targets(mod_dirs);
+
+    let Ok(entries) = fs::read_dir(path) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
             if path.is_symlink() {
-                fs::remove_file(path)?;
+                links_into(path, &targets)
+            } else {
+                path.join(COPY_MARKER).exists()
             }
+        })
+        .collect()
+}
+
+/// Where links made by the manager point to.
+/// Links point to the real directory, which may be elsewhere when a mod folder
+/// or the mod directory itself is a symlink.
+fn link_targets(mod_dirs: &[&Path]) -> Vec<PathBuf> {
+    let mut targets: Vec<PathBuf> = mod_dirs.iter().map(|dir| dir.to_path_buf()).collect();
+    for dir in mod_dirs {
+        targets.extend(fs::canonicalize(dir));
+        if let Ok(entries) = fs::read_dir(dir) {
+            targets.extend(entries.filter_map(|entry| fs::canonicalize(entry.ok()?.path()).ok()));
+        }
+    }
+    targets
+}
+
+/// Whether the symlink points into one of `targets`.
+/// Dangling links into the mod directories count as well, e.g. after unsubscribing.
+fn links_into(link: &Path, targets: &[PathBuf]) -> bool {
+    fs::read_link(link).is_ok_and(|target| targets.iter().any(|dir| target.starts_with(dir)))
+}
+
+/// Remove mods previously linked or copied into the given directory from one of `mod_dirs`.
+/// Other directories and symlinks are left alone. Returns the number of removed mods.
+pub fn remove_linked_mods(path: &Path, mod_dirs: &[&Path]) -> AppResult<usize> {
+    let linked_mods = find_linked_mods(path, mod_dirs);
+    for path in &linked_mods {
+        if path.is_symlink() {
+            remove_symlink(path)?;
+        } else {
+            fs::remove_dir_all(path)?;
         }
     }
 
+    Ok(linked_mods.len())
+}
+
+/// Link the given mod directories into `path`.
+/// Only links into `mod_dirs` are replaced, other links are left alone like real directories.
+pub fn create_sym_links(path: &Path, entries: Vec<PathBuf>, mod_dirs: &[&Path]) -> AppResult<()> {
+    let targets = link_targets(mod_dirs);
+    for entry in entries {
+        let to_path = path.join(entry.file_name().unwrap());
+
+        // A leftover link (possibly dangling) is replaced, a real directory is left alone
+        if to_path.is_symlink() && links_into(&to_path, &targets) {
+            remove_symlink(&to_path)?;
+        } else if to_path.is_symlink() || to_path.exists() {
+            continue;
+        }
+
+        // Mod folders may be symlinks themselves (e.g. to another drive),
+        // so link directly to the real directory
+        let target = fs::canonicalize(&entry).unwrap_or(entry);
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(target, to_path)?;
+
+        // Requires Developer Mode or an elevated prompt
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(target, to_path)?;
+    }
     Ok(())
 }
 
-pub fn create_sym_links(path: &Path, entries: Vec<PathBuf>) -> AppResult<()> {
+/// Copy the given mod directories into `path`, as an alternative to symlinks.
+/// Files are hard linked when possible, so this is cheap on the same filesystem.
+/// Only links into `mod_dirs` are replaced, other links are left alone like real directories.
+pub fn copy_mod_dirs(path: &Path, entries: Vec<PathBuf>, mod_dirs: &[&Path]) -> AppResult<()> {
+    let targets = link_targets(mod_dirs);
     for entry in entries {
         let to_path = path.join(entry.file_name().unwrap());
-        if to_path.exists() {
+
+        // A leftover link or copy is replaced, any other directory is left alone
+        if to_path.is_symlink() {
+            if !links_into(&to_path, &targets) {
+                continue;
+            }
+            remove_symlink(&to_path)?;
+        } else if to_path.join(COPY_MARKER).exists() {
+            fs::remove_dir_all(&to_path)?;
+        } else if to_path.exists() {
             continue;
         }
-        std::os::unix::fs::symlink(entry, to_path)?;
+
+        // Marked before copying, so a copy that fails halfway is still replaced and removed
+        fs::create_dir_all(&to_path)?;
+        fs::write(to_path.join(COPY_MARKER), "")?;
+        copy_dir(&entry, &to_path)?;
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> AppResult<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let from_path = entry?.path();
+        let to_path = to.join(from_path.file_name().unwrap());
+
+        if from_path.is_dir() {
+            copy_dir(&from_path, &to_path)?;
+        } else if fs::hard_link(&from_path, &to_path).is_err() {
+            fs::copy(&from_path, &to_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract a mod from a zip archive into the custom mods folder. The archive may hold the mod
+/// folder, e.g. `@MyMod/addons/...`, or only its content, in which case the folder is named
+/// after the archive. A `meta.cpp` with the name is written when the mod has none.
+/// Returns the identifier of the mod, which is its folder name.
+pub fn install_custom_mod_from_zip(zip_path: &Path, custom_mods_path: &Path) -> AppResult<String> {
+    let mut archive = ZipArchive::new(File::open(zip_path)?)?;
+
+    // The mod folder is the one holding the addons
+    let names: Vec<PathBuf> = (0..archive.len())
+        .filter_map(|i| archive.by_index(i).ok()?.enclosed_name())
+        .collect();
+    let root = names
+        .iter()
+        .find_map(|name| {
+            let is_addon = name.extension().is_some_and(|extension| {
+                extension.eq_ignore_ascii_case("pbo") || extension.eq_ignore_ascii_case("ebo")
+            });
+            let addons = name.parent()?;
+            (is_addon && addons.file_name()?.eq_ignore_ascii_case("addons"))
+                .then(|| addons.parent().unwrap_or(Path::new("")).to_path_buf())
+        })
+        .ok_or_else(|| AppError::NotAMod(zip_path.display().to_string()))?;
+
+    let folder_name = match root.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => {
+            let stem = zip_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if stem.starts_with('@') {
+                stem
+            } else {
+                format!("@{}", stem)
+            }
+        }
+    };
+
+    let mod_path = custom_mods_path.join(&folder_name);
+    if mod_path.exists() {
+        return Err(AppError::ModAlreadyInstalled(
+            mod_path.display().to_string(),
+        ));
+    }
+
+    // Don't leave a half extracted mod behind
+    if let Err(e) = extract_zip_dir(&mut archive, &root, &mod_path) {
+        let _ = fs::remove_dir_all(&mod_path);
+        return Err(e);
+    }
+
+    if !mod_path.join("meta.cpp").exists() && !mod_path.join("mod.cpp").exists() {
+        let name = folder_name.trim_start_matches('@').replace('"', "'");
+        fs::write(mod_path.join("meta.cpp"), format!("name = \"{}\";\n", name))?;
+    }
+
+    Ok(folder_name)
+}
+
+/// Extract the entries of the archive below `root` into `to`
+fn extract_zip_dir(archive: &mut ZipArchive<File>, root: &Path, to: &Path) -> AppResult<()> {
+    fs::create_dir_all(to)?;
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        // Entries with paths leading out of the folder are skipped
+        let Some(relative) = file
+            .enclosed_name()
+            .and_then(|name| name.strip_prefix(root).ok().map(Path::to_path_buf))
+        else {
+            continue;
+        };
+
+        let to_path = to.join(relative);
+        if file.is_dir() {
+            fs::create_dir_all(&to_path)?;
+            continue;
+        }
+        if let Some(parent) = to_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut file, &mut File::create(&to_path)?)?;
+    }
+
+    Ok(())
+}
+
+/// Directory symlinks are removed as files on Unix, but as directories on Windows
+fn remove_symlink(path: &Path) -> std::io::Result<()> {
+    if cfg!(windows) {
+        fs::remove_dir(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Read the mod identifiers from a preset file.
+/// The format is detected from the file extension and content: a `.preset2` file, an HTML
+/// preset, a `-mod=` argument or a list of mods.
+pub fn parse_preset(path: &Path) -> AppResult<Vec<String>> {
+    let content = fs::read_to_string(path)?;
+
+    let is_preset2 = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("preset2"))
+        || content.contains("<addons-presets");
+
+    if is_preset2 {
+        return parse_preset2(&content);
+    }
+
+    let is_html = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"))
+        || content.contains("arma:Type")
+        || content.to_ascii_lowercase().contains("<html");
+
+    if is_html {
+        return parse_preset_html(&content);
+    }
+
+    // Plain text written by other tools, either launch arguments or a list of mods
+    parse_mod_argument(&content)
+        .or_else(|| parse_mod_list(&content))
+        .ok_or_else(|| AppError::UnsupportedPresetFormat(path.to_string_lossy().to_string()))
+}
+
+/// Read the mods from `-mod=` and `-serverMod=` arguments, e.g. `-mod=@CBA_A3;!Workshop\@ACE`.
+/// The mods are identified by their folder name. `None` when no mods are in such an argument.
+pub fn parse_mod_argument(content: &str) -> Option<Vec<String>> {
+    let start_re = Regex::new(r"(?im)(?:^|\s)-(?:server)?mod=").ok()?;
+    // The value ends at the next argument on the line, unless it is quoted
+    let end_re = Regex::new(r"\s+-").ok()?;
+
+    let mut identifiers: Vec<String> = Vec::new();
+    for start in start_re.find_iter(content) {
+        let line = content[start.end()..].lines().next().unwrap_or_default();
+        let value = match line.strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next().unwrap_or_default(),
+            None => match end_re.find(line) {
+                Some(end) => &line[..end.start()],
+                None => line.trim_end(),
+            },
+        };
+        for identifier in value
+            .split(';')
+            .filter_map(|entry| local_mod_identifier(entry.trim()))
+        {
+            if !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+    }
+
+    (!identifiers.is_empty()).then_some(identifiers)
+}
+
+/// Read a list of workshop ids or `@` folder names, one per line or separated by `;`.
+/// Lines starting with `#` are skipped. `None` when anything else is in the list.
+pub fn parse_mod_list(content: &str) -> Option<Vec<String>> {
+    let mut identifiers: Vec<String> = Vec::new();
+    for entry in content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(|line| line.split(';'))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let is_workshop_id = entry.chars().all(|c| c.is_ascii_digit());
+        if !is_workshop_id && !entry.starts_with('@') {
+            return None;
+        }
+        if !identifiers.iter().any(|identifier| identifier == entry) {
+            identifiers.push(entry.to_string());
+        }
+    }
+
+    (!identifiers.is_empty()).then_some(identifiers)
+}
+
+/// Parse the content of an Arma 3 Launcher `.preset2` file.
+/// Workshop mods are listed as `steam:<id>`, local mods as `local:<path>`
+/// and Creator DLCs by their Steam app id.
+pub fn parse_preset2(content: &str) -> AppResult<Vec<String>> {
+    let id_re = Regex::new(r"<id>\s*([^<]+?)\s*</id>").map_err(|_| AppError::RegexError)?;
+
+    let mut identifiers = Vec::new();
+
+    for (section, is_dlc) in [("published-ids", false), ("dlcs-appids", true)] {
+        let section_re = Regex::new(&format!(r"(?s)<{0}>(.*?)</{0}>", section))
+            .map_err(|_| AppError::RegexError)?;
+
+        let Some(section_content) = section_re.captures(content).and_then(|caps| caps.get(1))
+        else {
+            continue;
+        };
+
+        for cap in id_re.captures_iter(section_content.as_str()) {
+            // Local paths are escaped on export
+            let id = &unescape_xml(&cap[1]);
+
+            let identifier = if is_dlc {
+                ARMA3_CDLC_APP_IDS
+                    .entries()
+                    .find(|(_, app_id)| **app_id == id)
+                    .map(|(key, _)| key.to_string())
+            } else if let Some(workshop_id) = id.strip_prefix("steam:") {
+                Some(workshop_id.to_string())
+            } else if let Some(local_path) = id.strip_prefix("local:") {
+                local_mod_identifier(local_path)
+            } else {
+                None
+            };
+
+            identifiers.extend(identifier);
+        }
+    }
+
+    Ok(identifiers)
+}
+
+/// Parse the content of an Arma 3 Launcher HTML preset export.
+/// Workshop mods link to their workshop page, Creator DLCs to their store page
+/// and local mods keep their path in `data-meta`.
+pub fn parse_preset_html(content: &str) -> AppResult<Vec<String>> {
+    let entry_re = Regex::new(
+        r#"href="https?://steamcommunity\.com/sharedfiles/filedetails/\?id=(\d+)"|href="https?://store\.steampowered\.com/app/(\d+)/?"|data-meta="local:([^"]+)""#,
+    )
+    .map_err(|_| AppError::RegexError)?;
+
+    let mut identifiers: Vec<String> = Vec::new();
+
+    for cap in entry_re.captures_iter(content) {
+        let identifier = if let Some(workshop_id) = cap.get(1) {
+            Some(workshop_id.as_str().to_string())
+        } else if let Some(app_id) = cap.get(2) {
+            ARMA3_CDLC_APP_IDS
+                .entries()
+                .find(|(_, id)| **id == app_id.as_str())
+                .map(|(key, _)| key.to_string())
+        } else {
+            cap.get(3)
+                .and_then(|local_path| local_mod_identifier(&unescape_xml(local_path.as_str())))
+        };
+
+        if let Some(identifier) = identifier {
+            if !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+    }
+
+    Ok(identifiers)
+}
+
+/// Local mods are identified by their folder name
+fn local_mod_identifier(local_path: &str) -> Option<String> {
+    local_path
+        .trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+}
+
+/// Write the given mods as a preset file.
+/// The format is chosen by the file extension, defaulting to `.preset2`.
+pub fn write_preset(
+    path: &Path,
+    name: &str,
+    mods: &[&Mod],
+    custom_mods_paths: &[&Path],
+) -> AppResult<()> {
+    let is_html = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+
+    if is_html {
+        write_preset_html(path, name, mods, custom_mods_paths)
+    } else {
+        write_preset2(path, mods, custom_mods_paths)
+    }
+}
+
+/// Write the given mods as an Arma 3 Launcher `.preset2` file
+pub fn write_preset2(path: &Path, mods: &[&Mod], custom_mods_paths: &[&Path]) -> AppResult<()> {
+    let mut published_ids = String::new();
+    let mut dlc_app_ids = String::new();
+
+    for m in mods {
+        if m.is_cdlc {
+            if let Some(app_id) = ARMA3_CDLC_APP_IDS.get(m.identifier.as_str()) {
+                dlc_app_ids += &format!("    <id>{}</id>\n", app_id);
+            }
+        } else if m.is_custom {
+            if let Some(local_path) = m.find_path(custom_mods_paths) {
+                let local_path = local_path.to_string_lossy().to_string();
+                published_ids += &format!("    <id>local:{}</id>\n", escape_xml(&local_path));
+            }
+        } else {
+            published_ids += &format!("    <id>steam:{}</id>\n", m.workshop_id());
+        }
+    }
+
+    let mut content = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    content += "<addons-presets>\n";
+    content += &format!("  <published-ids>\n{}  </published-ids>\n", published_ids);
+    content += &format!("  <dlcs-appids>\n{}  </dlcs-appids>\n", dlc_app_ids);
+    content += "</addons-presets>\n";
+
+    fs::write(path, content)?;
+
+    Ok(())
+}
+
+/// Write the given mods as an Arma 3 Launcher HTML preset
+pub fn write_preset_html(
+    path: &Path,
+    name: &str,
+    mods: &[&Mod],
+    custom_mods_paths: &[&Path],
+) -> AppResult<()> {
+    let mut mod_rows = String::new();
+    let mut dlc_rows = String::new();
+
+    for m in mods {
+        let display_name = escape_xml(&m.name);
+
+        if m.is_cdlc {
+            if let Some(app_id) = ARMA3_CDLC_APP_IDS.get(m.identifier.as_str()) {
+                let url = format!("https://store.steampowered.com/app/{}", app_id);
+                dlc_rows += "        <tr data-type=\"DlcContainer\">\n";
+                dlc_rows += &format!(
+                    "          <td data-type=\"DisplayName\">{}</td>\n",
+                    display_name
+                );
+                dlc_rows += &format!(
+                    "          <td>\n            <a href=\"{0}\" data-type=\"Link\">{0}</a>\n          </td>\n",
+                    url
+                );
+                dlc_rows += "        </tr>\n";
+            }
+        } else if m.is_custom {
+            // Local mods have no workshop page, the launcher looks them up by path
+            if let Some(local_path) = m.find_path(custom_mods_paths) {
+                let local_path = local_path.to_string_lossy().to_string();
+                mod_rows += "        <tr data-type=\"ModContainer\">\n";
+                mod_rows += &format!(
+                    "          <td data-type=\"DisplayName\">{}</td>\n",
+                    display_name
+                );
+                mod_rows += "          <td>\n            <span class=\"from-local\">Local</span>\n          </td>\n";
+                mod_rows += &format!(
+                    "          <td>\n            <span data-type=\"Link\" data-meta=\"local:{}\" />\n          </td>\n",
+                    escape_xml(&local_path)
+                );
+                mod_rows += "        </tr>\n";
+            }
+        } else {
+            let url = format!(
+                "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
+                m.workshop_id()
+            );
+            mod_rows += "        <tr data-type=\"ModContainer\">\n";
+            mod_rows += &format!(
+                "          <td data-type=\"DisplayName\">{}</td>\n",
+                display_name
+            );
+            mod_rows += "          <td>\n            <span class=\"from-steam\">Steam</span>\n          </td>\n";
+            mod_rows += &format!(
+                "          <td>\n            <a href=\"{0}\" data-type=\"Link\">{0}</a>\n          </td>\n",
+                url
+            );
+            mod_rows += "        </tr>\n";
+        }
     }
+
+    let name = escape_xml(name);
+
+    let mut content = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    content += "<html>\n";
+    content += "  <!--Created by Arma 3 Mod Manager Console-->\n";
+    content += "  <head>\n";
+    content += "    <meta name=\"arma:Type\" content=\"preset\" />\n";
+    content += &format!(
+        "    <meta name=\"arma:PresetName\" content=\"{}\" />\n",
+        name
+    );
+    content += "    <meta name=\"generator\" content=\"Arma 3 Mod Manager Console\" />\n";
+    content += "    <title>Arma 3</title>\n";
+    content += "  </head>\n";
+    content += "  <body>\n";
+    content += &format!("    <h1>Arma 3  - Preset <strong>{}</strong></h1>\n", name);
+    content += "    <p class=\"before-list\">\n";
+    content += "      <em>To import this preset, drag this file onto the Launcher window. Or click the MODS tab, then PRESET in the top right, then IMPORT at the bottom, and finally select this file.</em>\n";
+    content += "    </p>\n";
+    content += &format!(
+        "    <div class=\"mod-list\">\n      <table>\n{}      </table>\n    </div>\n",
+        mod_rows
+    );
+    content += &format!(
+        "    <div class=\"dlc-list\">\n      <table>\n{}      </table>\n    </div>\n",
+        dlc_rows
+    );
+    content += "  </body>\n";
+    content += "</html>\n";
+
+    fs::write(path, content)?;
+
     Ok(())
 }
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&gt;", ">")
+        .replace("&lt;", "<")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRESET2: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<addons-presets>
+  <last-update>2024-05-01T12:00:00.0000000+02:00</last-update>
+  <published-ids>
+    <id>steam:450814997</id>
+    <id>steam:463939057</id>
+    <id>local:/home/user/mods/@my_mod/</id>
+  </published-ids>
+  <dlcs-appids>
+    <id>1042220</id>
+    <id>999999</id>
+  </dlcs-appids>
+</addons-presets>"#;
+
+    #[test]
+    fn parse_preset2_reads_all_sections() {
+        let ids = parse_preset2(PRESET2).unwrap();
+        assert_eq!(ids, vec!["450814997", "463939057", "@my_mod", "GM"]);
+    }
+
+    #[test]
+    fn parse_preset2_without_dlcs() {
+        let content = "<addons-presets><published-ids><id>steam:1</id></published-ids><dlcs-appids /></addons-presets>";
+        assert_eq!(parse_preset2(content).unwrap(), vec!["1"]);
+    }
+
+    #[test]
+    fn parse_preset2_unescapes_local_paths() {
+        let content = "<addons-presets><published-ids><id>local:/mods/@R&amp;D&apos;s &lt;mod&gt;/</id></published-ids></addons-presets>";
+        assert_eq!(parse_preset2(content).unwrap(), vec!["@R&D's <mod>"]);
+    }
+
+    const PRESET_HTML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<html>
+  <head>
+    <meta name="arma:Type" content="preset" />
+    <meta name="arma:PresetName" content="Test" />
+  </head>
+  <body>
+    <div class="mod-list">
+      <table>
+        <tr data-type="ModContainer">
+          <td data-type="DisplayName">CBA_A3</td>
+          <td>
+            <a href="https://steamcommunity.com/sharedfiles/filedetails/?id=450814997" data-type="Link">https://steamcommunity.com/sharedfiles/filedetails/?id=450814997</a>
+          </td>
+        </tr>
+        <tr data-type="ModContainer">
+          <td data-type="DisplayName">My Mod</td>
+          <td>
+            <span data-type="Link" data-meta="local:C:\Arma 3\@my_mod" />
+          </td>
+        </tr>
+      </table>
+    </div>
+    <div class="dlc-list">
+      <table>
+        <tr data-type="DlcContainer">
+          <td data-type="DisplayName">Western Sahara</td>
+          <td>
+            <a href="https://store.steampowered.com/app/1681170" data-type="Link">https://store.steampowered.com/app/1681170</a>
+          </td>
+        </tr>
+      </table>
+    </div>
+  </body>
+</html>"#;
+
+    #[test]
+    fn parse_preset_html_reads_mods_and_dlcs() {
+        let ids = parse_preset_html(PRESET_HTML).unwrap();
+        assert_eq!(ids, vec!["450814997", "@my_mod", "WS"]);
+    }
+
+    #[test]
+    fn parse_preset_detects_html() {
+        let path = std::env::temp_dir().join("a3mm_detect_html_test.txt");
+        fs::write(&path, PRESET_HTML).unwrap();
+        let ids = parse_preset(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn write_then_parse_preset2_roundtrip() {
+        let path = std::env::temp_dir().join("a3mm_roundtrip_test.preset2");
+
+        let workshop = Mod::new("450814997".into(), "CBA".into(), false, false);
+        let custom = Mod::new("@local".into(), "Local".into(), false, true);
+        let cdlc = Mod::new("WS".into(), "Western Sahara".into(), true, false);
+
+        write_preset2(&path, &[&workshop, &custom, &cdlc], &[Path::new("/custom")]).unwrap();
+        let ids = parse_preset(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(ids, vec!["450814997", "@local", "WS"]);
+    }
+
+    #[test]
+    fn write_preset2_uses_the_published_id() {
+        let path = std::env::temp_dir().join("a3mm_published_id_test.preset2");
+
+        let mut workshop = Mod::new("folder".into(), "CBA".into(), false, false);
+        workshop.published_id = Some("450814997".into());
+
+        write_preset2(&path, &[&workshop], &[]).unwrap();
+        let ids = parse_preset(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(ids, vec!["450814997"]);
+    }
+
+    #[test]
+    fn write_then_parse_preset_html_roundtrip() {
+        let path = std::env::temp_dir().join("a3mm_roundtrip_test.html");
+
+        let workshop = Mod::new("450814997".into(), "CBA & Co".into(), false, false);
+        let custom = Mod::new("@local".into(), "Local".into(), false, true);
+        let cdlc = Mod::new("WS".into(), "Western Sahara".into(), true, false);
+
+        write_preset(
+            &path,
+            "Test",
+            &[&workshop, &custom, &cdlc],
+            &[Path::new("/custom")],
+        )
+        .unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let ids = parse_preset(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert!(content.contains("<meta name=\"arma:PresetName\" content=\"Test\" />"));
+        assert!(content.contains("CBA &amp; Co"));
+        assert_eq!(ids, vec!["450814997", "@local", "WS"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn symlinked_mod_folder_is_scanned_and_linked() {
+        use crate::mod_manager::utils;
+
+        let root = std::env::temp_dir().join("a3mm_symlinked_mod_test");
+        let _ = fs::remove_dir_all(&root);
+
+        let real_mod = root.join("other_drive").join("123456");
+        let workshop = root.join("workshop");
+        let game = root.join("game");
+        fs::create_dir_all(&real_mod).unwrap();
+        fs::create_dir_all(&workshop).unwrap();
+        fs::create_dir_all(&game).unwrap();
+        fs::write(real_mod.join("meta.cpp"), "name = \"Linked Mod\";").unwrap();
+
+        std::os::unix::fs::symlink(&real_mod, workshop.join("123456")).unwrap();
+
+        let mods: Vec<Mod> = utils::yield_path_dirs(&workshop)
+            .unwrap()
+            .filter_map(|path| utils::process_mod_dir(path, false))
+            .collect();
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].identifier, "123456");
+        assert_eq!(mods[0].name, "Linked Mod");
+
+        create_sym_links(&game, vec![mods[0].get_path(&workshop)], &[&workshop]).unwrap();
+        assert!(game.join("123456").join("meta.cpp").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn create_sym_links_replaces_dangling_link() {
+        let root = std::env::temp_dir().join("a3mm_dangling_link_test");
+        let _ = fs::remove_dir_all(&root);
+
+        let mod_dir = root.join("workshop").join("42");
+        let game = root.join("game");
+        fs::create_dir_all(&mod_dir).unwrap();
+        fs::create_dir_all(&game).unwrap();
+
+        // Linked before unsubscribing from the mod
+        std::os::unix::fs::symlink(root.join("workshop").join("gone"), game.join("42")).unwrap();
+
+        create_sym_links(&game, vec![mod_dir.clone()], &[&root.join("workshop")]).unwrap();
+        assert_eq!(
+            fs::read_link(game.join("42")).unwrap(),
+            fs::canonicalize(&mod_dir).unwrap()
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn relinking_keeps_links_made_by_the_user() {
+        let root = std::env::temp_dir().join("a3mm_foreign_link_test");
+        let _ = fs::remove_dir_all(&root);
+
+        let workshop = root.join("workshop");
+        let mod_dir = workshop.join("42");
+        let game = root.join("game");
+        fs::create_dir_all(&mod_dir).unwrap();
+        fs::create_dir_all(root.join("elsewhere")).unwrap();
+        fs::create_dir_all(&game).unwrap();
+
+        std::os::unix::fs::symlink(root.join("elsewhere"), game.join("42")).unwrap();
+
+        create_sym_links(&game, vec![mod_dir.clone()], &[&workshop]).unwrap();
+        assert_eq!(
+            fs::read_link(game.join("42")).unwrap(),
+            root.join("elsewhere")
+        );
+        copy_mod_dirs(&game, vec![mod_dir.clone()], &[&workshop]).unwrap();
+        assert_eq!(
+            fs::read_link(game.join("42")).unwrap(),
+            root.join("elsewhere")
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn copied_mod_dirs_are_removed_again() {
+        let root = std::env::temp_dir().join("a3mm_copy_mode_test");
+        let _ = fs::remove_dir_all(&root);
+
+        let mod_dir = root.join("workshop").join("42");
+        let game = root.join("game");
+        fs::create_dir_all(mod_dir.join("addons")).unwrap();
+        fs::create_dir_all(game.join("Addons")).unwrap();
+        fs::write(mod_dir.join("addons").join("mod.pbo"), "pbo").unwrap();
+
+        copy_mod_dirs(&game, vec![mod_dir.clone()], &[&root.join("workshop")]).unwrap();
+        assert!(!game.join("42").is_symlink());
+        assert_eq!(
+            fs::read_to_string(game.join("42").join("addons").join("mod.pbo")).unwrap(),
+            "pbo"
+        );
+
+        // Copying again replaces the previous copy, also one that failed halfway
+        fs::remove_dir_all(game.join("42").join("addons")).unwrap();
+        copy_mod_dirs(&game, vec![mod_dir.clone()], &[&root.join("workshop")]).unwrap();
+        assert!(game.join("42").join("addons").join("mod.pbo").exists());
+
+        remove_linked_mods(&game, &[&root.join("workshop")]).unwrap();
+        assert!(!game.join("42").exists());
+        assert!(game.join("Addons").exists());
+        assert!(mod_dir.join("addons").join("mod.pbo").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn only_links_into_the_mod_directories_are_removed() {
+        let root = std::env::temp_dir().join("a3mm_stale_links_test");
+        let _ = fs::remove_dir_all(&root);
+
+        let workshop = root.join("workshop");
+        let game = root.join("game");
+        fs::create_dir_all(workshop.join("42")).unwrap();
+        fs::create_dir_all(workshop.join("43")).unwrap();
+        fs::create_dir_all(root.join("elsewhere")).unwrap();
+        fs::create_dir_all(&game).unwrap();
+
+        create_sym_links(
+            &game,
+            vec![workshop.join("42"), workshop.join("43")],
+            &[&workshop],
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(root.join("elsewhere"), game.join("other")).unwrap();
+        // Unsubscribed since the last launch
+        fs::remove_dir_all(workshop.join("43")).unwrap();
+
+        let mut linked = find_linked_mods(&game, &[&workshop]);
+        linked.sort();
+        assert_eq!(linked, vec![game.join("42"), game.join("43")]);
+
+        assert_eq!(remove_linked_mods(&game, &[&workshop]).unwrap(), 2);
+        assert!(!game.join("42").is_symlink());
+        assert!(!game.join("43").is_symlink());
+        assert!(game.join("other").is_symlink());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn parse_mod_argument_reads_folder_names() {
+        let content = r#"-noSplash -mod=@CBA_A3;!Workshop\@ACE;C:\Mods\@My Mod -skipIntro
+-serverMod="@Server;@CBA_A3""#;
+
+        assert_eq!(
+            parse_mod_argument(content).unwrap(),
+            vec!["@CBA_A3", "@ACE", "@My Mod", "@Server"]
+        );
+        assert_eq!(parse_mod_argument("-noSplash -world=empty"), None);
+        assert_eq!(parse_mod_argument("-noSplash -mod= -skipIntro"), None);
+    }
+
+    #[test]
+    fn parse_mod_argument_reads_every_argument_on_a_line() {
+        assert_eq!(
+            parse_mod_argument("-mod=@a;@b -serverMod=@c").unwrap(),
+            vec!["@a", "@b", "@c"]
+        );
+        assert_eq!(
+            parse_mod_argument("-mod=@x -mod=@y -noSplash").unwrap(),
+            vec!["@x", "@y"]
+        );
+        assert_eq!(
+            parse_mod_argument(r#"-mod="@My Mod;@b" -serverMod=@c"#).unwrap(),
+            vec!["@My Mod", "@b", "@c"]
+        );
+    }
+
+    #[test]
+    fn parse_mod_list_reads_ids_and_folders() {
+        let content = "# Operation night\n450814997\n463939057;@Custom\n\n450814997\n";
+
+        assert_eq!(
+            parse_mod_list(content).unwrap(),
+            vec!["450814997", "463939057", "@Custom"]
+        );
+        assert_eq!(parse_mod_list("450814997\nnot a mod\n"), None);
+        assert_eq!(parse_mod_list("\n# empty\n"), None);
+    }
+
+    #[test]
+    fn parse_preset_detects_text_formats() {
+        let path = std::env::temp_dir().join("a3mm_detect_text_test.txt");
+
+        fs::write(&path, "-mod=1;2").unwrap();
+        let argument = parse_preset(&path).unwrap();
+        fs::write(&path, "1\n2\n").unwrap();
+        let list = parse_preset(&path).unwrap();
+        fs::write(&path, "Hello").unwrap();
+        let unknown = parse_preset(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(argument, vec!["1", "2"]);
+        assert_eq!(list, vec!["1", "2"]);
+        assert!(matches!(unknown, Err(AppError::UnsupportedPresetFormat(_))));
+    }
+
+    fn write_zip(path: &Path, files: &[&str]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for name in files {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"content").unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn install_custom_mod_from_zip_finds_the_mod_folder() {
+        let root = std::env::temp_dir().join("a3mm_install_zip_test");
+        let _ = fs::remove_dir_all(&root);
+        let mods = root.join("mods");
+        fs::create_dir_all(&mods).unwrap();
+
+        let folder_zip = root.join("download.zip");
+        write_zip(
+            &folder_zip,
+            &["@Folder/addons/folder.pbo", "@Folder/mod.cpp", "readme.txt"],
+        );
+        let content_zip = root.join("Content Only.zip");
+        write_zip(&content_zip, &["addons/content.pbo", "../escape.txt"]);
+        let empty_zip = root.join("empty.zip");
+        write_zip(&empty_zip, &["readme.txt"]);
+
+        let folder = install_custom_mod_from_zip(&folder_zip, &mods).unwrap();
+        let content = install_custom_mod_from_zip(&content_zip, &mods).unwrap();
+        let again = install_custom_mod_from_zip(&folder_zip, &mods);
+        let empty = install_custom_mod_from_zip(&empty_zip, &mods);
+
+        assert_eq!(folder, "@Folder");
+        assert!(mods.join("@Folder/addons/folder.pbo").is_file());
+        assert!(!mods.join("@Folder/meta.cpp").exists());
+        assert!(!mods.join("@Folder/readme.txt").exists());
+
+        assert_eq!(content, "@Content Only");
+        assert!(mods.join("@Content Only/addons/content.pbo").is_file());
+        assert_eq!(
+            fs::read_to_string(mods.join("@Content Only/meta.cpp")).unwrap(),
+            "name = \"Content Only\";\n"
+        );
+        assert!(!root.join("escape.txt").exists());
+
+        assert!(matches!(again, Err(AppError::ModAlreadyInstalled(_))));
+        assert!(matches!(empty, Err(AppError::NotAMod(_))));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn parse_preset_rejects_unknown_format() {
+        let path = std::env::temp_dir().join("a3mm_unknown_format_test.json");
+        fs::write(&path, "{}").unwrap();
+        let result = parse_preset(&path);
+        let _ = fs::remove_file(&path);
+
+        assert!(matches!(result, Err(AppError::UnsupportedPresetFormat(_))));
+    }
+}
@@ -0,0 +1,819 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::errors::{AppError, AppResult};
+
+use super::{
+    config::{Config, LaunchMethod, LinkMode},
+    file_handler, utils, Mod,
+};
+
+/// Steam app id of Arma 3
+const ARMA3_APP_ID: &str = "107410";
+
+/// Start of the names of the files the game output is logged to
+const LAUNCH_LOG_PREFIX: &str = "arma3-mod-manager-launch-";
+
+/// The BattlEye launcher in the game directory, which starts the game with anticheat
+const BATTLEYE_EXECUTABLE: &str = "arma3battleye.exe";
+
+/// How many launch logs are kept, older ones are removed
+const MAX_LAUNCH_LOGS: usize = 10;
+
+/// Get the executable path based on the current platform
+#[cfg(target_os = "macos")]
+pub fn get_executable_path(game_path: &Path, executable_name: &str) -> PathBuf {
+    let game_app_path = game_path.join(format!("{}.app", executable_name));
+    game_app_path.join("Contents/MacOS").join(executable_name)
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_executable_path(game_path: &Path, executable_name: &str) -> PathBuf {
+    game_path.join(executable_name)
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_executable_path(game_path: &Path, executable_name: &str) -> PathBuf {
+    // The executable name may be given with or without the extension
+    let path = game_path.join(executable_name);
+    match path.extension() {
+        Some(extension) if extension.eq_ignore_ascii_case("exe") => path,
+        _ => path.with_extension("exe"),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub fn get_executable_path(game_path: &Path, executable_name: &str) -> PathBuf {
+    // Fallback for other platforms - assume direct executable
+    game_path.join(executable_name)
+}
+
+/// Where the BattlEye launcher is, it only comes with the Windows version of the game
+pub fn get_battleye_path(game_path: &Path) -> PathBuf {
+    game_path.join(BATTLEYE_EXECUTABLE)
+}
+
+/// The command to start the game, without any arguments
+pub fn game_command(config: &Config) -> AppResult<Command> {
+    let game_path = config.get_game_path();
+
+    let executable_name = config.get_executable_name();
+    let executable_path = get_executable_path(game_path, executable_name);
+    let executable_path_str = executable_path.to_string_lossy().to_string();
+
+    if !executable_path.exists() {
+        return Err(AppError::InvalidPath(executable_path_str.to_owned()));
+    }
+
+    let mut command = if config.get_use_battleye() {
+        let battleye_path = get_battleye_path(game_path);
+        if !battleye_path.exists() {
+            return Err(AppError::InvalidPath(
+                battleye_path.to_string_lossy().to_string(),
+            ));
+        }
+
+        // The BattlEye launcher starts the game executable itself
+        let mut command = Command::new(battleye_path);
+        command
+            .args(["2", "1", "1", "-exe"])
+            .arg(executable_path.file_name().unwrap_or_default());
+        command
+    } else {
+        Command::new(&executable_path_str)
+    };
+    command.current_dir(game_path);
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(overlay_path) = utils::get_steam_overlay_path() {
+            command.env("DYLD_INSERT_LIBRARIES", overlay_path);
+            command.env("DYLD_FORCE_FLAT_NAMESPACE", "1");
+        }
+        command.env("SteamAppId", ARMA3_APP_ID);
+    }
+
+    Ok(command)
+}
+
+/// What launching the game with some mods does, worked out without changing anything
+pub struct LaunchPlan {
+    pub command: Command,
+    /// Mod directories and where they are linked or copied to in the game directory
+    pub links: Vec<(PathBuf, PathBuf)>,
+}
+
+/// Build the command to launch the game with the given mods,
+/// and work out where the mods have to be linked
+pub fn plan_launch(config: &Config, mods: &[&Mod]) -> AppResult<LaunchPlan> {
+    let game_path = config.get_game_path();
+
+    let mut command = game_command(config)?;
+    let args = build_launch_args(config, mods);
+
+    let links = if config.get_link_mode() == LinkMode::Paths {
+        Vec::new()
+    } else {
+        linked_mod_paths(config, mods)
+            .into_iter()
+            .filter_map(|path| {
+                let to_path = game_path.join(path.file_name()?);
+                Some((path, to_path))
+            })
+            .collect()
+    };
+
+    // Steam would start the game without BattlEye
+    if config.get_launch_method() == LaunchMethod::Steam && !config.get_use_battleye() {
+        // Falls back to running the game directly when Steam isn't found
+        if let Some(mut steam_command) = steam_command() {
+            steam_command.args(args);
+            return Ok(LaunchPlan {
+                command: steam_command,
+                links,
+            });
+        }
+    }
+
+    command.args(args);
+
+    Ok(LaunchPlan { command, links })
+}
+
+/// Link the given mods into the game directory and build the command to launch the game with them
+pub fn prepare_launch(config: &Config, mods: &[&Mod]) -> AppResult<Command> {
+    let game_path = config.get_game_path();
+    let plan = plan_launch(config, mods)?;
+
+    // The game directory is left alone when the mods are passed by their path
+    if config.get_link_mode() == LinkMode::Paths {
+        return Ok(plan.command);
+    }
+
+    // Remove previously linked mods from the game directory
+    remove_linked_mods(config)?;
+
+    let mod_paths = plan.links.into_iter().map(|(path, _)| path).collect();
+    let mod_dirs = mod_source_dirs(config);
+    match config.get_link_mode() {
+        LinkMode::Symlink => file_handler::create_sym_links(game_path, mod_paths, &mod_dirs)?,
+        LinkMode::Copy => file_handler::copy_mod_dirs(game_path, mod_paths, &mod_dirs)?,
+        LinkMode::Paths => {}
+    }
+
+    Ok(plan.command)
+}
+
+/// Mods linked or copied into the game directory that launching with `mods` wouldn't use,
+/// e.g. left behind by a crash or from another selection of mods
+pub fn find_stale_linked_mods(config: &Config, mods: &[&Mod]) -> Vec<PathBuf> {
+    let game_path = config.get_game_path();
+    let used: Vec<PathBuf> = linked_mod_paths(config, mods)
+        .into_iter()
+        .filter_map(|path| Some(game_path.join(path.file_name()?)))
+        .collect();
+
+    file_handler::find_linked_mods(game_path, &mod_source_dirs(config))
+        .into_iter()
+        // Dangling links point to mods that are no longer installed
+        .filter(|path| !path.exists() || !used.contains(path))
+        .collect()
+}
+
+/// Removes the mods linked or copied into the game directory by an earlier launch.
+/// Returns the number of removed mods.
+pub fn remove_linked_mods(config: &Config) -> AppResult<usize> {
+    file_handler::remove_linked_mods(config.get_game_path(), &mod_source_dirs(config))
+}
+
+/// Where the mods linked into the game directory come from.
+/// Unset and relative paths are left out, every link would start with an empty path.
+fn mod_source_dirs(config: &Config) -> Vec<&Path> {
+    std::iter::once(config.get_workshop_path())
+        .chain(config.get_custom_mods_paths())
+        .filter(|dir| dir.is_absolute())
+        .collect()
+}
+
+/// Whether `prepare_launch` starts the game through Steam instead of running it directly
+pub fn launches_through_steam(config: &Config) -> bool {
+    config.get_launch_method() == LaunchMethod::Steam
+        && !config.get_use_battleye()
+        && utils::find_steam_executable().is_some()
+}
+
+/// Redirects the output of the command to a new log file next to the config,
+/// removing the oldest logs. Returns the path of the log file.
+/// On an error the command is left as it was, so the game can still be started without a log.
+pub fn log_output(command: &mut Command) -> AppResult<PathBuf> {
+    let config_path = Config::get_save_path()?;
+    let log_dir = config_path.parent().ok_or(AppError::InvalidHomePath)?;
+
+    // Old logs that can't be removed don't keep the new one from being written
+    let _ = remove_old_logs(log_dir, MAX_LAUNCH_LOGS - 1);
+
+    // Milliseconds keep launches within the same second from sharing a log
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let log_path = log_dir.join(format!(
+        "{}{}-{:03}.log",
+        LAUNCH_LOG_PREFIX,
+        utils::format_file_timestamp(now.as_secs() as i64),
+        now.subsec_millis()
+    ));
+
+    let log_file = File::create(&log_path)?;
+    command.stdout(log_file.try_clone()?).stderr(log_file);
+
+    Ok(log_path)
+}
+
+/// Removes the oldest launch logs in the directory until at most `keep` are left
+fn remove_old_logs(dir: &Path, keep: usize) -> AppResult<()> {
+    let mut logs: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LAUNCH_LOG_PREFIX) && name.ends_with(".log"))
+        })
+        .collect();
+
+    // The timestamp in the name sorts the logs from oldest to newest
+    logs.sort();
+
+    let excess = logs.len().saturating_sub(keep);
+    for path in &logs[..excess] {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+/// The command to start the game through Steam, without any arguments
+fn steam_command() -> Option<Command> {
+    let steam_path = utils::find_steam_executable()?;
+
+    let mut command = Command::new(steam_path);
+    command.args(["-applaunch", ARMA3_APP_ID]);
+    Some(command)
+}
+
+/// The directories of the given mods that have to be linked into the game directory
+fn linked_mod_paths(config: &Config, mods: &[&Mod]) -> Vec<PathBuf> {
+    mods.iter()
+        .filter_map(|m| mod_source_path(config, m))
+        .collect()
+}
+
+/// The directory of a workshop or custom mod. `None` for CDLCs, since they already are
+/// in the game folder, and for missing mods.
+fn mod_source_path(config: &Config, m: &Mod) -> Option<PathBuf> {
+    if m.is_cdlc || m.is_missing {
+        None
+    } else if m.is_custom {
+        m.find_path(&config.get_custom_mods_paths())
+    } else {
+        Some(m.get_path(config.get_workshop_path()))
+    }
+}
+
+/// The arguments to launch the game with the given mods.
+/// Doesn't touch the game directory, so the launch command can be shown and tested.
+pub fn build_launch_args(config: &Config, mods: &[&Mod]) -> Vec<String> {
+    let mut args = Vec::new();
+
+    // The editor world replaces a `-world=` from the launch arguments further down
+    let replace_world = config.get_editor_world().is_some();
+    args.extend(
+        split_launch_args(config.get_launch_args())
+            .into_iter()
+            .filter(|arg| !(replace_world && arg.to_lowercase().starts_with("-world="))),
+    );
+
+    if let Some(profile) = config.get_profile() {
+        args.push(format!("-name={}", profile));
+    }
+
+    let mut client_mods = Vec::new();
+    let mut server_mods = Vec::new();
+    for m in mods.iter().filter(|m| !m.is_missing) {
+        let name = mod_argument(config, m);
+        // Would corrupt the list, reported by `invalid_mod_arguments` instead
+        if !is_valid_mod_argument(&name) {
+            continue;
+        }
+        if m.is_server_mod {
+            server_mods.push(name);
+        } else {
+            client_mods.push(name);
+        }
+    }
+
+    for (argument, names) in [("-mod", client_mods), ("-serverMod", server_mods)] {
+        if !names.is_empty() {
+            args.push(format!("{}={}", argument, names.join(";")));
+        }
+    }
+
+    if let Some(world) = config.get_editor_world() {
+        args.push(format!("-world={}", world));
+    }
+    if let Some(command) = config.get_init_command() {
+        args.push(format!("-init={}", command));
+    }
+
+    if let Some(server) = config.get_server() {
+        args.push(format!("-connect={}", server.host));
+        args.push(format!("-port={}", server.port));
+        if let Some(password) = &server.password {
+            args.push(format!("-password={}", password));
+        }
+    }
+
+    args
+}
+
+/// How the mod is listed in `-mod=`, by its full path in paths mode and otherwise by its
+/// folder name. CDLCs are always passed by their folder name in the game directory.
+fn mod_argument(config: &Config, m: &Mod) -> String {
+    let full_paths = config.get_link_mode() == LinkMode::Paths;
+    match mod_source_path(config, m).filter(|_| full_paths) {
+        Some(path) => path.to_string_lossy().to_string(),
+        None => m.identifier.clone(),
+    }
+}
+
+/// The game splits `-mod=` on `;` without any escaping, and a `"` ends the quoted argument
+/// on Windows. Spaces are fine, since the whole argument is quoted when needed.
+fn is_valid_mod_argument(name: &str) -> bool {
+    !name.contains([';', '"'])
+}
+
+/// How many bytes are kept free for the game's cache and logs when checking the disk space
+const DISK_SPACE_HEADROOM: u64 = 2 * 1024 * 1024 * 1024;
+
+/// A warning when copying the mods into the game directory likely doesn't fit on the drive.
+/// Only copy mode takes up space, files on the same drive as the game are hard linked and
+/// copies left by an earlier launch are replaced.
+pub fn disk_space_warning(config: &Config, mods: &[&Mod]) -> Option<String> {
+    if config.get_link_mode() != LinkMode::Copy {
+        return None;
+    }
+
+    let game_path = config.get_game_path();
+    let needed: u64 = mods
+        .iter()
+        .filter(|m| {
+            mod_source_path(config, m).is_some_and(|path| {
+                let copied = path.file_name().map(|name| game_path.join(name));
+                !utils::same_filesystem(&path, game_path)
+                    && !copied.is_some_and(|copied| copied.is_dir() && !copied.is_symlink())
+            })
+        })
+        .map(|m| m.disk_size)
+        .sum();
+    if needed == 0 {
+        return None;
+    }
+
+    let available = utils::available_space(game_path)?;
+    (needed.saturating_add(DISK_SPACE_HEADROOM) > available).then(|| {
+        format!(
+            "Copying the mods needs about {}, but only {} is free on the game drive",
+            utils::format_size(needed),
+            utils::format_size(available)
+        )
+    })
+}
+
+/// Mods that can't be passed to the game, since their folder name or path would corrupt
+/// `-mod=`. They are left out of the launch arguments.
+pub fn invalid_mod_arguments<'a>(config: &Config, mods: &[&'a Mod]) -> Vec<&'a Mod> {
+    mods.iter()
+        .filter(|m| !m.is_missing && !is_valid_mod_argument(&mod_argument(config, m)))
+        .copied()
+        .collect()
+}
+
+/// Likely mistakes in hand-written launch arguments. Only warnings, since the game accepts
+/// more arguments than can be checked here.
+pub fn launch_arg_warnings(launch_args: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if launch_args.matches('"').count() % 2 == 1 {
+        warnings.push("Unbalanced quotes".to_string());
+    }
+
+    for arg in split_launch_args(launch_args) {
+        let lowercase = arg.to_lowercase();
+        if !arg.starts_with('-') {
+            warnings.push(format!("{} doesn't start with -", arg));
+        } else if lowercase.starts_with("-mod=") || lowercase.starts_with("-servermod=") {
+            warnings.push(format!(
+                "{} conflicts with the mods enabled in the manager",
+                arg
+            ));
+        }
+    }
+
+    warnings
+}
+
+/// Splits the launch arguments on spaces outside of quotes, so `-profiles="C:\My Profiles"`
+/// is passed to the game as one argument. The quotes themselves are left out.
+fn split_launch_args(launch_args: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut in_quotes = false;
+    for c in launch_args.chars() {
+        if c.is_whitespace() && !in_quotes {
+            if !arg.is_empty() {
+                args.push(std::mem::take(&mut arg));
+            }
+        } else if c == '"' {
+            in_quotes = !in_quotes;
+        } else {
+            arg.push(c);
+        }
+    }
+    if !arg.is_empty() {
+        args.push(arg);
+    }
+    args
+}
+
+/// The full command line, for showing to the user
+pub fn command_line(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            if arg.contains(' ') {
+                format!("\"{}\"", arg)
+            } else {
+                arg.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_manager::config::Server;
+
+    #[test]
+    fn remove_old_logs_keeps_the_newest() {
+        let dir = std::env::temp_dir().join("a3mm_launch_logs_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        for name in [
+            "arma3-mod-manager-launch-20240101-120000-000.log",
+            "arma3-mod-manager-launch-20240301-080000-000.log",
+            "arma3-mod-manager-launch-20240201-230000-500.log",
+            "config.json",
+        ] {
+            File::create(dir.join(name)).unwrap();
+        }
+
+        remove_old_logs(&dir, 2).unwrap();
+
+        let mut remaining: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec![
+                "arma3-mod-manager-launch-20240201-230000-500.log",
+                "arma3-mod-manager-launch-20240301-080000-000.log",
+                "config.json",
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn links_are_kept_without_a_workshop_path() {
+        let root = std::env::temp_dir().join("a3mm_empty_workshop_test");
+        let _ = fs::remove_dir_all(&root);
+        let game = root.join("game");
+        fs::create_dir_all(root.join("elsewhere")).unwrap();
+        fs::create_dir_all(&game).unwrap();
+        std::os::unix::fs::symlink(root.join("elsewhere"), game.join("other")).unwrap();
+
+        let config = Config::new(
+            game.to_string_lossy().to_string(),
+            String::new(),
+            Vec::new(),
+        )
+        .unwrap();
+        assert!(find_stale_linked_mods(&config, &[]).is_empty());
+        assert_eq!(remove_linked_mods(&config).unwrap(), 0);
+        assert!(game.join("other").is_symlink());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn build_launch_args_split_server_mods() {
+        let mut config = Config::new(String::new(), String::new(), Vec::new()).unwrap();
+        config.set_default_args("-noSplash".to_string());
+
+        let client = Mod::new("1".into(), "Client".into(), false, false);
+        let mut server = Mod::new("2".into(), "Server".into(), false, false);
+        server.is_server_mod = true;
+        let missing = Mod::missing("3".into());
+
+        assert_eq!(
+            build_launch_args(&config, &[&client, &server, &missing]),
+            vec!["-noSplash", "-mod=1", "-serverMod=2"]
+        );
+        assert_eq!(
+            build_launch_args(&config, &[&server]),
+            vec!["-noSplash", "-serverMod=2"]
+        );
+    }
+
+    #[test]
+    fn build_launch_args_without_mods() {
+        let mut config = Config::new(String::new(), String::new(), Vec::new()).unwrap();
+        config.set_default_args("-noSplash -skipIntro".to_string());
+
+        assert_eq!(
+            build_launch_args(&config, &[]),
+            vec!["-noSplash", "-skipIntro"]
+        );
+
+        config.set_default_args(String::new());
+        assert!(build_launch_args(&config, &[]).is_empty());
+        assert!(build_launch_args(&config, &[&Mod::missing("1".into())]).is_empty());
+    }
+
+    #[test]
+    fn build_launch_args_join_all_kinds_of_mods() {
+        let mut config = Config::new(String::new(), String::new(), Vec::new()).unwrap();
+        config.set_default_args(String::new());
+
+        let workshop_mod = Mod::new("1".into(), "Workshop".into(), false, false);
+        let custom_mod = Mod::new("@Custom".into(), "Custom".into(), false, true);
+        let cdlc = Mod::new("ws".into(), "Western Sahara".into(), true, false);
+
+        assert_eq!(
+            build_launch_args(&config, &[&workshop_mod, &custom_mod, &cdlc]),
+            vec!["-mod=1;@Custom;ws"]
+        );
+        assert_eq!(build_launch_args(&config, &[&cdlc]), vec!["-mod=ws"]);
+    }
+
+    #[test]
+    fn build_launch_args_skip_mods_that_break_the_list() {
+        let mut config = Config::new(String::new(), String::new(), Vec::new()).unwrap();
+        config.set_default_args(String::new());
+
+        let valid = Mod::new("1".into(), "Valid".into(), false, false);
+        let semicolon = Mod::new("@A;B".into(), "Semicolon".into(), false, true);
+        let quote = Mod::new("@A\"B".into(), "Quote".into(), false, true);
+        let mods = [&valid, &semicolon, &quote];
+
+        assert_eq!(build_launch_args(&config, &mods), vec!["-mod=1"]);
+        let invalid: Vec<&str> = invalid_mod_arguments(&config, &mods)
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(invalid, vec!["Semicolon", "Quote"]);
+    }
+
+    #[test]
+    fn build_launch_args_keep_spaces_in_one_argument() {
+        let mut config = Config::new(String::new(), String::new(), Vec::new()).unwrap();
+        config.set_default_args(String::new());
+
+        let spaces = Mod::new("@My Mod".into(), "My Mod".into(), false, true);
+        let other = Mod::new("1".into(), "Other".into(), false, false);
+
+        let args = build_launch_args(&config, &[&spaces, &other]);
+        assert_eq!(args, vec!["-mod=@My Mod;1"]);
+        let names: Vec<&str> = args[0].trim_start_matches("-mod=").split(';').collect();
+        assert_eq!(names, vec!["@My Mod", "1"]);
+        assert!(invalid_mod_arguments(&config, &[&spaces, &other]).is_empty());
+    }
+
+    #[test]
+    fn build_launch_args_start_in_the_editor_world() {
+        let mut config = Config::new(String::new(), String::new(), Vec::new()).unwrap();
+        config.set_default_args("-noSplash -world=empty -skipIntro".to_string());
+        config.set_editor_world(Some("Stratis".to_string()));
+        config.set_init_command(Some("playMission[\"\",\"Test.Stratis\"]".to_string()));
+
+        assert_eq!(
+            build_launch_args(&config, &[]),
+            vec![
+                "-noSplash",
+                "-skipIntro",
+                "-world=Stratis",
+                "-init=playMission[\"\",\"Test.Stratis\"]"
+            ]
+        );
+    }
+
+    #[test]
+    fn build_launch_args_connect_to_server() {
+        let mut config = Config::new(String::new(), String::new(), Vec::new()).unwrap();
+        config.set_default_args(String::new());
+        config.set_server(Some(
+            Server::parse("10.0.0.1:2302", Some("pw".to_string())).unwrap(),
+        ));
+
+        let client = Mod::new("1".into(), "Client".into(), false, false);
+        assert_eq!(
+            build_launch_args(&config, &[&client]),
+            vec!["-mod=1", "-connect=10.0.0.1", "-port=2302", "-password=pw"]
+        );
+    }
+
+    #[test]
+    fn build_launch_args_use_the_profile() {
+        let mut config = Config::new(String::new(), String::new(), Vec::new()).unwrap();
+        config.set_default_args("-noSplash".to_string());
+        let client = Mod::new("1".into(), "Client".into(), false, false);
+
+        assert_eq!(
+            build_launch_args(&config, &[&client]),
+            vec!["-noSplash", "-mod=1"]
+        );
+
+        config.set_profile(Some("Modding".to_string()));
+        assert_eq!(
+            build_launch_args(&config, &[&client]),
+            vec!["-noSplash", "-name=Modding", "-mod=1"]
+        );
+    }
+
+    #[test]
+    fn plan_launch_leaves_the_game_directory_alone() {
+        let dir = std::env::temp_dir().join("a3mm_plan_launch_test");
+        let _ = fs::remove_dir_all(&dir);
+        let game_path = dir.join("Arma 3");
+        let workshop_path = dir.join("workshop");
+        fs::create_dir_all(workshop_path.join("1")).unwrap();
+
+        let config = Config::new(
+            game_path.to_string_lossy().to_string(),
+            workshop_path.to_string_lossy().to_string(),
+            Vec::new(),
+        )
+        .unwrap();
+        let executable_path = get_executable_path(&game_path, config.get_executable_name());
+        fs::create_dir_all(executable_path.parent().unwrap()).unwrap();
+        File::create(&executable_path).unwrap();
+
+        let workshop_mod = Mod::new("1".into(), "Workshop".into(), false, false);
+        let cdlc = Mod::new("ws".into(), "Western Sahara".into(), true, false);
+        let plan = plan_launch(&config, &[&workshop_mod, &cdlc]).unwrap();
+
+        assert_eq!(
+            plan.links,
+            vec![(workshop_path.join("1"), game_path.join("1"))]
+        );
+        assert!(plan.command.get_args().any(|arg| arg == "-mod=1;ws"));
+        assert!(!game_path.join("1").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn paths_mode_passes_full_paths_without_links() {
+        let dir = std::env::temp_dir().join("a3mm_paths_mode_test");
+        let _ = fs::remove_dir_all(&dir);
+        let game_path = dir.join("Arma 3");
+        let workshop_path = dir.join("workshop");
+        fs::create_dir_all(workshop_path.join("1")).unwrap();
+
+        let config = Config::new(
+            game_path.to_string_lossy().to_string(),
+            workshop_path.to_string_lossy().to_string(),
+            Vec::new(),
+        )
+        .unwrap();
+        let mut value = serde_json::to_value(&config).unwrap();
+        value["link_mode"] = "paths".into();
+        let config: Config = serde_json::from_value(value).unwrap();
+
+        let executable_path = get_executable_path(&game_path, config.get_executable_name());
+        fs::create_dir_all(executable_path.parent().unwrap()).unwrap();
+        File::create(&executable_path).unwrap();
+
+        let workshop_mod = Mod::new("1".into(), "Workshop".into(), false, false);
+        let cdlc = Mod::new("ws".into(), "Western Sahara".into(), true, false);
+        let command = prepare_launch(&config, &[&workshop_mod, &cdlc]).unwrap();
+
+        let mod_argument = format!("-mod={};ws", workshop_path.join("1").display());
+        assert!(command.get_args().any(|arg| arg == mod_argument.as_str()));
+        assert!(!game_path.join("1").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn command_line_quotes_args_with_spaces() {
+        let mut command = Command::new("/games/Arma 3/arma3");
+        command.args(["-noSplash", "-name=Some Player", "-mod=1;2"]);
+
+        assert_eq!(
+            command_line(&command),
+            "\"/games/Arma 3/arma3\" -noSplash \"-name=Some Player\" -mod=1;2"
+        );
+    }
+
+    #[test]
+    fn launch_arg_warnings_point_out_likely_mistakes() {
+        assert!(launch_arg_warnings("-noSplash -profiles=\"C:\\My Profiles\"").is_empty());
+        assert!(launch_arg_warnings("").is_empty());
+
+        assert_eq!(
+            launch_arg_warnings("-noSplash skipIntro -mod=@ace -serverMod=@srv"),
+            [
+                "skipIntro doesn't start with -",
+                "-mod=@ace conflicts with the mods enabled in the manager",
+                "-serverMod=@srv conflicts with the mods enabled in the manager",
+            ]
+        );
+        assert_eq!(launch_arg_warnings("-name=\"Player"), ["Unbalanced quotes"]);
+    }
+
+    #[test]
+    fn split_launch_args_keep_quoted_spaces() {
+        assert_eq!(
+            split_launch_args("  -noSplash   -profiles=\"C:\\My Profiles\" -skipIntro "),
+            vec!["-noSplash", "-profiles=C:\\My Profiles", "-skipIntro"]
+        );
+        assert!(split_launch_args("").is_empty());
+    }
+
+    #[test]
+    fn disk_space_is_only_needed_for_copies_to_another_drive() {
+        let dir = std::env::temp_dir().join("a3mm_disk_space_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("game")).unwrap();
+        fs::create_dir_all(dir.join("workshop").join("1")).unwrap();
+
+        let config = Config::new(
+            dir.join("game").to_string_lossy().to_string(),
+            dir.join("workshop").to_string_lossy().to_string(),
+            Vec::new(),
+        )
+        .unwrap();
+        let mut huge = Mod::new("1".into(), "Huge".into(), false, false);
+        huge.disk_size = u64::MAX / 2;
+
+        assert_eq!(disk_space_warning(&config, &[&huge]), None);
+
+        let mut value = serde_json::to_value(&config).unwrap();
+        value["link_mode"] = "copy".into();
+        let config: Config = serde_json::from_value(value).unwrap();
+        // Hard linked, since both are in the same directory
+        assert_eq!(disk_space_warning(&config, &[&huge]), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn game_command_starts_the_game_through_battleye() {
+        let dir = std::env::temp_dir().join("a3mm_battleye_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut config =
+            Config::new(dir.to_string_lossy().to_string(), String::new(), Vec::new()).unwrap();
+        config.set_executable_name("arma3_x64.exe".to_string());
+        File::create(get_executable_path(&dir, "arma3_x64.exe")).unwrap();
+
+        config.set_use_battleye(true);
+        assert!(matches!(
+            game_command(&config),
+            Err(AppError::InvalidPath(_))
+        ));
+
+        File::create(get_battleye_path(&dir)).unwrap();
+        let command = game_command(&config).unwrap();
+        assert_eq!(command.get_program(), get_battleye_path(&dir));
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["2", "1", "1", "-exe", "arma3_x64.exe"]);
+        assert!(!launches_through_steam(&config));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,16 +1,31 @@
-use std::path::{Path, PathBuf};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    process::Child,
+};
 
 use phf::phf_map;
+use regex::Regex;
+use serde::Serialize;
 
-use self::{config::Config, paginator::Paginator, terminal::Terminal};
+use self::{
+    config::{Config, ListMode, SortOrder},
+    paginator::Paginator,
+    terminal::{ConfigRecovery, Terminal},
+};
 
 use crate::errors::{AppError, AppResult};
 
-mod config;
+mod bisect;
+pub mod config;
+#[cfg(feature = "network")]
 pub mod dependency_manager;
 mod file_handler;
+pub mod launcher;
 mod paginator;
 mod terminal;
+mod text_input;
 mod utils;
 
 /// Arma 3 Creator DLCs
@@ -25,13 +40,40 @@ static ARMA3_CDLCS: phf::Map<&'static str, &'static str> = phf_map! {
     "EF" => "Expeditionary Forces",
 };
 
-#[derive(Debug, Clone)]
+/// Steam app ids of the Creator DLCs, as used by the Arma 3 Launcher presets
+static ARMA3_CDLC_APP_IDS: phf::Map<&'static str, &'static str> = phf_map! {
+    "GM" => "1042220",
+    "VN" => "1227700",
+    "CSLA" => "1294440",
+    "WS" => "1681170",
+    "SPE" => "1175380",
+    "RF" => "2647760",
+    "EF" => "2647830",
+};
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Mod {
     pub identifier: String,
     pub name: String,
     pub enabled: bool,
     pub is_cdlc: bool,
     pub is_custom: bool,
+    /// Enabled in the config, but not found on disk
+    pub is_missing: bool,
+    /// Loaded with `-serverMod=` instead of `-mod=`
+    pub is_server_mod: bool,
+    /// The workshop id from `meta.cpp`, which may differ from the folder name
+    pub published_id: Option<String>,
+    /// Last update from `meta.cpp`, in seconds since the Unix epoch
+    pub timestamp: Option<i64>,
+    /// Size of the mod directory in bytes, measured when the mods are loaded
+    pub disk_size: u64,
+    pub is_favorite: bool,
+    /// A CDLC that doesn't look owned, launching with it may fail
+    pub is_unowned: bool,
+    /// The end of the identifier, shown after names shared by several mods
+    #[serde(skip)]
+    pub name_suffix: Option<String>,
 }
 
 impl Mod {
@@ -42,56 +84,183 @@ impl Mod {
             enabled: false,
             is_cdlc,
             is_custom,
+            is_missing: false,
+            is_server_mod: false,
+            published_id: None,
+            timestamp: None,
+            disk_size: 0,
+            is_favorite: false,
+            is_unowned: false,
+            name_suffix: None,
+        }
+    }
+
+    fn missing(identifier: String) -> Mod {
+        Mod {
+            name: identifier.clone(),
+            identifier,
+            enabled: true,
+            is_cdlc: false,
+            is_custom: false,
+            is_missing: true,
+            is_server_mod: false,
+            published_id: None,
+            timestamp: None,
+            disk_size: 0,
+            is_favorite: false,
+            is_unowned: false,
+            name_suffix: None,
+        }
+    }
+
+    /// The name as listed, with the end of the identifier when other mods share the name
+    pub fn display_name(&self) -> String {
+        match &self.name_suffix {
+            Some(suffix) => format!("{} ({})", self.name, suffix),
+            None => self.name.clone(),
+        }
+    }
+
+    /// The id to look the mod up by on the Steam Workshop
+    pub fn workshop_id(&self) -> &str {
+        self.published_id.as_deref().unwrap_or(&self.identifier)
+    }
+
+    /// The Steam Workshop page of the mod, `None` for CDLCs and custom mods not from the workshop
+    pub fn workshop_url(&self) -> Option<String> {
+        if self.is_cdlc || (self.is_custom && self.published_id.is_none()) {
+            return None;
         }
+        Some(format!(
+            "https://steamcommunity.com/sharedfiles/filedetails/?id={}",
+            self.workshop_id()
+        ))
     }
 
     pub fn get_path(&self, path: &Path) -> PathBuf {
         path.join(&self.identifier)
     }
+
+    /// The mod in the first of the folders that contains it, or in the first folder when
+    /// none of them do. `None` without any folders.
+    pub fn find_path(&self, paths: &[&Path]) -> Option<PathBuf> {
+        paths
+            .iter()
+            .map(|path| self.get_path(path))
+            .find(|path| path.is_dir())
+            .or_else(|| paths.first().map(|path| self.get_path(path)))
+    }
+}
+
+/// Groups of mods that can be enabled or disabled at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModCategory {
+    Workshop,
+    Custom,
+    Cdlc,
+    ServerMod,
+}
+
+impl ModCategory {
+    /// Missing mods are in no category, since they can't be enabled again
+    pub fn contains(self, m: &Mod) -> bool {
+        if m.is_missing {
+            return false;
+        }
+        match self {
+            ModCategory::Workshop => !m.is_cdlc && !m.is_custom,
+            ModCategory::Custom => m.is_custom,
+            ModCategory::Cdlc => m.is_cdlc,
+            ModCategory::ServerMod => m.is_server_mod,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ModCategory::Workshop => "workshop mods",
+            ModCategory::Custom => "custom mods",
+            ModCategory::Cdlc => "CDLCs",
+            ModCategory::ServerMod => "server mods",
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct ModManager {
     pub config: Config,
     pub loaded_mods: Paginator<Mod>,
+    /// Identifiers in the presets and the installed folder names they were corrected to
+    case_corrections: Vec<(String, String)>,
 }
 
 impl ModManager {
-    pub fn new(page_size: usize) -> AppResult<Self> {
+    pub fn new() -> AppResult<Self> {
+        ModManager::load(Config::read())
+    }
+
+    /// Like `new`, with the backup of the config, for command line actions that can't ask
+    /// how to recover from a config that can't be parsed
+    pub fn from_backup() -> AppResult<Self> {
+        ModManager::load(Config::read_backup())
+    }
+
+    fn load(config: AppResult<Config>) -> AppResult<Self> {
         // Try to read config. If it fails (NotFound), create a default empty one.
-        let config = match Config::read() {
+        let config = match config {
             Ok(c) => c,
             Err(AppError::IoError(e)) if e.kind() == std::io::ErrorKind::NotFound => {
                 // Create a default config (empty paths) to start with.
                 // We don't save it yet; the Wizard will handle that.
-                Config::new(String::new(), String::new(), None)?
+                Config::new(String::new(), String::new(), Vec::new())?
             }
             Err(e) => return Err(e),
         };
 
         // Attempt to load mods if config is somewhat valid, otherwise just start with empty list
         let loaded_mods_vec = if config.is_valid() {
-            match ModManager::get_installed_mods(&config) {
-                Ok(mut mods) => {
-                     for l_mod in &mut mods {
-                        if config.get_enabled_mods().contains(&l_mod.identifier) {
-                            l_mod.enabled = true;
-                        }
-                    }
-                    mods
-                },
-                Err(_) => Vec::new(), // If path reading fails, just return empty list
-            }
+            // If path reading fails, just return empty list
+            ModManager::get_installed_mods(&config).unwrap_or_default()
         } else {
             Vec::new()
         };
 
-        Ok(ModManager {
+        let mut manager = ModManager {
+            loaded_mods: Paginator::new(loaded_mods_vec, config.get_initial_page_size()),
             config,
-            loaded_mods: Paginator::new(loaded_mods_vec, page_size),
-        })
+            case_corrections: Vec::new(),
+        };
+        manager.loaded_mods.scrolling = manager.config.get_list_mode() == ListMode::Scroll;
+
+        if manager.config.is_valid() {
+            manager.correct_identifiers();
+            manager.apply_active_preset();
+        }
+
+        manager.apply_sort_order();
+
+        Ok(manager)
     }
 
+    /// Like `new`, but when the config can't be read asks whether to restore its backup or
+    /// start over. Returns `None` when the user quits to fix the config by hand.
+    pub fn new_or_recover() -> AppResult<Option<Self>> {
+        let (path, message) = match ModManager::new() {
+            Err(AppError::CorruptConfig(path, message)) => (path, message),
+            result => return result.map(Some),
+        };
+
+        match terminal::corrupt_config_screen(&path, &message, Config::has_backup()?)? {
+            ConfigRecovery::RestoreBackup => Config::restore_backup()?,
+            ConfigRecovery::Reset => {
+                Config::set_aside()?;
+            }
+            ConfigRecovery::Quit => return Ok(None),
+        }
+
+        ModManager::new().map(Some)
+    }
+
+    /// Runs the terminal UI until it is quit
     pub fn start(&mut self) -> AppResult<()> {
         let mut term = Terminal::new(self);
 
@@ -100,19 +269,418 @@ impl ModManager {
         Ok(())
     }
 
+    /// The installed mods, followed by the enabled mods that are missing
+    pub fn mods(&self) -> &[Mod] {
+        self.loaded_mods.all_items()
+    }
+
+    /// Enables or disables the mod. Missing mods can only be disabled.
+    /// Returns false when there is no such mod.
+    pub fn set_enabled(&mut self, identifier: &str, enabled: bool) -> bool {
+        let Some(m) = self
+            .loaded_mods
+            .all_items_mut()
+            .iter_mut()
+            .find(|m| m.identifier == identifier && !(enabled && m.is_missing))
+        else {
+            return false;
+        };
+
+        m.enabled = enabled;
+        true
+    }
+
+    /// Saves the enabled mods to the active preset
+    pub fn save(&mut self) -> AppResult<()> {
+        self.config.update_mods(self.enabled_identifiers());
+        self.config.save()
+    }
+
+    /// Links the enabled mods into the game directory and starts the game,
+    /// logging its output next to the config when the log can be written
+    pub fn launch(&self) -> AppResult<Child> {
+        let mut command = launcher::prepare_launch(&self.config, &self.enabled_mods())?;
+        // Without a log the output goes wherever ours does
+        let _ = launcher::log_output(&mut command);
+        Ok(command.spawn()?)
+    }
+
     pub fn refresh_mods(&mut self) -> AppResult<()> {
         let installed_mods = ModManager::get_installed_mods(&self.config)?;
+        self.set_installed_mods(installed_mods);
+
+        Ok(())
+    }
+
+    /// Replaces the mod list with freshly scanned mods and applies the active preset.
+    /// Mods that were already listed keep their enabled state, so unsaved toggles survive.
+    pub fn set_installed_mods(&mut self, installed_mods: Vec<Mod>) {
+        let previous: HashMap<String, bool> = self
+            .loaded_mods
+            .all_items()
+            .iter()
+            .filter(|m| !m.is_missing)
+            .map(|m| (m.identifier.clone(), m.enabled))
+            .collect();
+
+        let scrolling = self.loaded_mods.scrolling;
         self.loaded_mods = Paginator::new(installed_mods, self.loaded_mods.page_size);
+        self.loaded_mods.scrolling = scrolling;
         self.apply_active_preset();
 
+        for m in self.loaded_mods.all_items_mut() {
+            if let Some(enabled) = previous.get(&m.identifier) {
+                m.enabled = *enabled;
+            }
+        }
+
+        self.apply_sort_order();
+    }
+
+    /// Sorts the mod list by the sort order of the active preset, with favorites on top.
+    /// Missing mods stay at the bottom.
+    pub fn apply_sort_order(&mut self) {
+        let mods = self.loaded_mods.all_items_mut();
+        mods.sort_by(|a, b| a.name.cmp(&b.name));
+
+        match self.config.get_sort_order() {
+            SortOrder::Name => mods.sort_by_key(|m| m.is_missing),
+            SortOrder::EnabledFirst => self.sort_enabled_first(),
+            SortOrder::Updated => mods.sort_by_key(|m| (m.is_missing, Reverse(m.timestamp))),
+        }
+
+        self.loaded_mods
+            .all_items_mut()
+            .sort_by_key(|m| (m.is_missing, !m.is_favorite));
+    }
+
+    /// Marks or unmarks the mod at the index as a favorite
+    pub fn toggle_favorite(&mut self, index: usize) {
+        let m = &mut self.loaded_mods.all_items_mut()[index];
+        m.is_favorite = self.config.toggle_favorite(&m.identifier);
+    }
+
+    /// Moves enabled mods to the top, keeping the alphabetical order within each group.
+    /// Missing mods stay at the bottom.
+    pub fn sort_enabled_first(&mut self) {
+        self.loaded_mods
+            .all_items_mut()
+            .sort_by_key(|m| (m.is_missing, !m.enabled));
+    }
+
+    /// Re-detects the Steam paths and saves them if valid.
+    /// The rest of the config (enabled mods, presets, args) is preserved.
+    pub fn repair_paths(&mut self) -> AppResult<()> {
+        let (workshop_path, game_path) = utils::setup_steam_paths()?;
+
+        self.config.set_workshop_path(workshop_path);
+        self.config.set_game_path(game_path);
+
+        if !self.config.is_valid() {
+            return Err(AppError::InvalidPath(
+                self.config.get_game_path().to_string_lossy().to_string(),
+            ));
+        }
+
+        self.config.save()?;
+        self.refresh_mods()?;
+
+        Ok(())
+    }
+
+    /// Changes the game path if it exists, and reloads the mods.
+    pub fn change_game_path(&mut self, path: String) -> AppResult<()> {
+        if !Path::new(&path).is_dir() {
+            return Err(AppError::InvalidPath(path));
+        }
+
+        self.config.set_game_path(path);
+        self.config.save()?;
+        self.refresh_mods()
+    }
+
+    /// Changes the workshop path if it exists and contains mods, and reloads the mods.
+    pub fn change_workshop_path(&mut self, path: String) -> AppResult<()> {
+        if !Path::new(&path).is_dir() {
+            return Err(AppError::InvalidPath(path));
+        }
+
+        let has_mods = utils::yield_path_dirs(Path::new(&path))?
+            .any(|dir| utils::process_mod_dir(dir, false).is_some());
+        if !has_mods {
+            return Err(AppError::NoModsFound(path));
+        }
+
+        self.config.set_workshop_path(path);
+        self.config.save()?;
+        self.refresh_mods()
+    }
+
+    /// Adds a custom mods folder if it exists, and reloads the mods
+    pub fn add_custom_mods_path(&mut self, path: String) -> AppResult<()> {
+        if !Path::new(&path).is_dir() {
+            return Err(AppError::InvalidPath(path));
+        }
+
+        if self.config.add_custom_mods_path(path) {
+            self.config.save()?;
+            self.refresh_mods()?;
+        }
         Ok(())
     }
 
+    /// Installs the mod in the zip archive into the custom mods folder at the index, and
+    /// reloads the mods. Returns the identifier of the installed mod.
+    pub fn install_custom_mod_from_zip(
+        &mut self,
+        zip_path: &Path,
+        index: usize,
+    ) -> AppResult<String> {
+        let custom_mods_path = self
+            .config
+            .get_custom_mods_paths()
+            .get(index)
+            .map(|path| path.to_path_buf())
+            .ok_or_else(|| AppError::InvalidPath(format!("custom mods folder {}", index + 1)))?;
+
+        let identifier = file_handler::install_custom_mod_from_zip(zip_path, &custom_mods_path)?;
+        self.refresh_mods()?;
+        Ok(identifier)
+    }
+
+    /// Removes the custom mods folder at the index, and reloads the mods
+    pub fn remove_custom_mods_path(&mut self, index: usize) -> AppResult<()> {
+        self.config.remove_custom_mods_path(index);
+        self.config.save()?;
+        self.refresh_mods()
+    }
+
     /// Sets `mod.enabled` for all loaded mods based on the active preset.
+    /// Enabled mods that are no longer installed are listed as missing at the bottom.
     pub fn apply_active_preset(&mut self) {
+        // Missing entries belong to the previous selection
+        self.loaded_mods.retain(|m| !m.is_missing);
+
         let enabled = self.config.get_enabled_mods();
+
         for m in self.loaded_mods.all_items_mut() {
             m.enabled = enabled.contains(&m.identifier);
+            m.is_favorite = self.config.is_favorite(&m.identifier);
+        }
+
+        let missing_mods: Vec<Mod> = enabled
+            .into_iter()
+            .filter(|id| {
+                !self
+                    .loaded_mods
+                    .all_items()
+                    .iter()
+                    .any(|m| m.identifier == *id)
+            })
+            .map(Mod::missing)
+            .collect();
+        self.loaded_mods.extend(missing_mods);
+    }
+
+    /// Replaces identifiers in the presets that only differ in case from the folder name of an
+    /// installed mod. Linux matches the links and `-mod=` case-sensitively, so the mod
+    /// wouldn't load otherwise. Published ids of mods in a folder named otherwise, as written
+    /// by the preset exports, are replaced with the folder name as well.
+    /// Runs once after loading, the replacements are kept for `take_case_corrections`.
+    fn correct_identifiers(&mut self) {
+        let mut identifiers: Vec<String> = self
+            .config
+            .get_preset_names()
+            .iter()
+            .flat_map(|name| self.config.get_preset_mods(name))
+            .collect();
+        identifiers.sort();
+        identifiers.dedup();
+
+        for identifier in identifiers {
+            let Some(actual) = self
+                .installed_identifier(&identifier)
+                .or_else(|| {
+                    self.loaded_mods
+                        .all_items()
+                        .iter()
+                        .find(|m| m.identifier.eq_ignore_ascii_case(&identifier))
+                        .map(|m| m.identifier.as_str())
+                })
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            if actual == identifier {
+                continue;
+            }
+
+            self.config.replace_mod_identifier(&identifier, &actual);
+            self.case_corrections.push((identifier, actual));
+        }
+    }
+
+    /// The identifier of the installed mod with the given identifier or published id
+    pub fn installed_identifier(&self, id: &str) -> Option<&str> {
+        let installed = self.loaded_mods.all_items();
+        installed
+            .iter()
+            .filter(|m| !m.is_missing)
+            .find(|m| m.identifier == id)
+            .or_else(|| {
+                installed
+                    .iter()
+                    .find(|m| !m.is_missing && m.workshop_id() == id)
+            })
+            .map(|m| m.identifier.as_str())
+    }
+
+    /// The identifiers in the presets corrected to the folder name of the installed mod
+    /// since the last call, with the folder name they were corrected to
+    pub fn take_case_corrections(&mut self) -> Vec<(String, String)> {
+        std::mem::take(&mut self.case_corrections)
+    }
+
+    /// Enabled mods in load order.
+    /// The order is kept in the active preset; newly enabled mods are loaded last.
+    pub fn enabled_mods(&self) -> Vec<&Mod> {
+        let load_order = self.config.get_enabled_mods();
+        let mut mods = self.loaded_mods.filter(|m| m.enabled);
+        mods.sort_by_key(|m| {
+            load_order
+                .iter()
+                .position(|id| *id == m.identifier)
+                .unwrap_or(usize::MAX)
+        });
+        mods
+    }
+
+    pub fn enabled_identifiers(&self) -> Vec<String> {
+        self.enabled_mods()
+            .iter()
+            .map(|m| m.identifier.clone())
+            .collect()
+    }
+
+    /// Identifiers of the mods enabled or disabled since the selection of the active preset
+    /// was last saved
+    pub fn toggled_mods(&self) -> HashSet<&str> {
+        let saved = self.config.get_enabled_mods();
+        self.loaded_mods
+            .all_items()
+            .iter()
+            .filter(|m| m.enabled != saved.contains(&m.identifier))
+            .map(|m| m.identifier.as_str())
+            .collect()
+    }
+
+    /// Moves an enabled mod one step earlier or later in the load order.
+    /// Only the order of the mods saved in the active preset changes, so other unsaved
+    /// changes to the selection stay unsaved.
+    /// Returns false if the mod is not enabled and saved, or already at the edge.
+    pub fn move_in_load_order(&mut self, identifier: &str, earlier: bool) -> bool {
+        let enabled = self.enabled_identifiers();
+        let mut saved = self.config.get_enabled_mods();
+        // Saved mods that were disabled since don't show in the load order
+        let visible: Vec<usize> = saved
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| enabled.contains(id))
+            .map(|(i, _)| i)
+            .collect();
+        let Some(index) = visible.iter().position(|i| saved[*i] == identifier) else {
+            return false;
+        };
+
+        let other = if earlier {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1).filter(|i| *i < visible.len())
+        };
+        let Some(other) = other else {
+            return false;
+        };
+
+        saved.swap(visible[index], visible[other]);
+        self.config.update_mods(saved);
+        true
+    }
+
+    /// Enabled mods of the active preset that are no longer installed
+    pub fn missing_mods(&self) -> Vec<&Mod> {
+        self.loaded_mods.filter(|m| m.is_missing)
+    }
+
+    /// Removes missing mods from the list and from the active preset.
+    /// Other unsaved changes to the selection stay unsaved.
+    /// Returns the number of purged mods.
+    pub fn purge_missing_mods(&mut self) -> usize {
+        let missing: Vec<String> = self
+            .missing_mods()
+            .iter()
+            .map(|m| m.identifier.clone())
+            .collect();
+
+        self.loaded_mods.retain(|m| !m.is_missing);
+        let mut saved = self.config.get_enabled_mods();
+        saved.retain(|id| !missing.contains(id));
+        self.config.update_mods(saved);
+
+        missing.len()
+    }
+
+    /// Enables or disables the mods whose name or identifier matches the pattern,
+    /// and saves the selection to the active preset.
+    /// Returns the names of the mods that changed.
+    pub fn set_enabled_matching(&mut self, pattern: &Regex, enabled: bool) -> Vec<String> {
+        let mut changed = Vec::new();
+
+        for m in self.loaded_mods.all_items_mut() {
+            // Missing mods can only be disabled
+            if m.enabled == enabled || (enabled && m.is_missing) {
+                continue;
+            }
+            if pattern.is_match(&m.name) || pattern.is_match(&m.identifier) {
+                m.enabled = enabled;
+                changed.push(m.name.clone());
+            }
+        }
+
+        self.config.update_mods(self.enabled_identifiers());
+
+        changed
+    }
+
+    /// Enables all mods in the category, or disables them when all are enabled already.
+    /// Returns whether they are enabled now, `None` when there are no mods in the category.
+    pub fn toggle_category(&mut self, category: ModCategory) -> Option<bool> {
+        let mut mods: Vec<&mut Mod> = self
+            .loaded_mods
+            .all_items_mut()
+            .iter_mut()
+            .filter(|m| category.contains(m))
+            .collect();
+        if mods.is_empty() {
+            return None;
+        }
+
+        let value = !mods.iter().all(|m| m.enabled);
+        for m in &mut mods {
+            m.enabled = value;
+        }
+        Some(value)
+    }
+
+    /// The directory of the mod, `None` when it is missing
+    pub fn mod_dir(&self, m: &Mod) -> Option<PathBuf> {
+        if m.is_missing {
+            None
+        } else if m.is_cdlc {
+            Some(m.get_path(self.config.get_game_path()))
+        } else if m.is_custom {
+            m.find_path(&self.config.get_custom_mods_paths())
+        } else {
+            Some(m.get_path(self.config.get_workshop_path()))
         }
     }
 
@@ -120,53 +688,533 @@ impl ModManager {
     pub fn switch_preset(&mut self, name: &str) {
         self.config.set_active_preset(name);
         self.apply_active_preset();
+        self.apply_sort_order();
     }
 
     fn get_installed_mods(config: &Config) -> AppResult<Vec<Mod>> {
+        ModManager::scan_installed_mods(config, |_, _| {})
+    }
+
+    /// Like `get_installed_mods`, calling `on_progress` with the number of scanned and total
+    /// mod directories, since measuring the size of many large mods takes a while
+    fn scan_installed_mods<F>(config: &Config, mut on_progress: F) -> AppResult<Vec<Mod>>
+    where
+        F: FnMut(usize, usize),
+    {
+        let list_dirs = |path: &Path| -> Vec<PathBuf> {
+            utils::yield_path_dirs(path)
+                .map(|dirs| dirs.collect())
+                .unwrap_or_default()
+        };
+
+        // Listed up front, so the total is known before the slow part
+        let workshop_dirs = list_dirs(config.get_workshop_path());
+        // A mod in more than one custom folder is taken from the first, like `Mod::find_path`
+        let mut custom_dirs: Vec<PathBuf> = Vec::new();
+        for path in config.get_custom_mods_paths() {
+            for dir in list_dirs(path) {
+                if !custom_dirs.iter().any(|d| d.file_name() == dir.file_name()) {
+                    custom_dirs.push(dir);
+                }
+            }
+        }
+        let cdlc_dirs: Vec<(PathBuf, &str, &str)> = list_dirs(config.get_game_path())
+            .into_iter()
+            .filter_map(|path_buf| {
+                let dir_name = path_buf.file_name()?.to_str()?;
+                let (key, value) = ARMA3_CDLCS.get_entry(dir_name)?;
+                Some((path_buf, *key, *value))
+            })
+            .collect();
+
+        let total = workshop_dirs.len() + custom_dirs.len() + cdlc_dirs.len();
+        on_progress(0, total);
+        let mut scanned = 0;
+        let mut report = || {
+            scanned += 1;
+            on_progress(scanned, total);
+        };
+
         let mut mods: Vec<Mod> = Vec::new();
 
         // Process workshop mods
-        if let Ok(paths) = utils::yield_path_dirs(config.get_workshop_path()) {
-            mods.extend(
-                paths
-                    .into_iter()
-                    .filter_map(|path_buf| utils::process_mod_dir(path_buf, false)),
-            );
+        for path_buf in workshop_dirs {
+            mods.extend(utils::process_mod_dir(path_buf, false));
+            report();
         }
 
         // Process custom mods folder
-        if let Some(custom_mods_path) = config.get_custom_mods_path() {
-            if let Ok(paths) = utils::yield_path_dirs(custom_mods_path) {
-                mods.extend(
-                    paths
-                        .into_iter()
-                        .filter_map(|path_buf| utils::process_mod_dir(path_buf, true)),
-                );
-            }
+        for path_buf in custom_dirs {
+            mods.extend(utils::process_mod_dir(path_buf, true));
+            report();
         }
 
         // Process CDLCS
-        if let Ok(paths) = utils::yield_path_dirs(config.get_game_path()) {
-            mods.extend(paths.into_iter().filter_map(|path_buf| {
-                let dir_name = path_buf.file_name()?.to_str()?.to_string();
-                ARMA3_CDLCS
-                    .get_entry(&dir_name)
-                    .map(|(key, value)| Mod::new(key.to_string(), value.to_string(), true, false))
-            }));
+        let installed_dlcs = utils::installed_dlc_app_ids(config.get_game_path());
+        for (path_buf, key, value) in cdlc_dirs {
+            let mut m = Mod::new(key.to_string(), value.to_string(), true, false);
+            m.disk_size = utils::dir_size(&path_buf);
+            let not_installed = installed_dlcs.as_ref().is_some_and(|app_ids| {
+                ARMA3_CDLC_APP_IDS
+                    .get(key)
+                    .is_some_and(|app_id| !app_ids.iter().any(|id| id == app_id))
+            });
+            m.is_unowned = not_installed || !utils::has_cdlc_content(&path_buf);
+            mods.push(m);
+            report();
         }
 
+        disambiguate_names(&mut mods);
         mods.sort_by(|a, b| a.name.cmp(&b.name));
 
         Ok(mods)
     }
 }
 
+/// Keeps the last digits of the identifier to show after names shared by several mods,
+/// so they can be told apart. The names themselves are left as they are.
+fn disambiguate_names(mods: &mut [Mod]) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for m in mods.iter() {
+        *counts.entry(m.name.clone()).or_default() += 1;
+    }
+
+    for m in mods.iter_mut().filter(|m| counts[&m.name] > 1) {
+        let start = m
+            .identifier
+            .char_indices()
+            .rev()
+            .nth(3)
+            .map_or(0, |(i, _)| i);
+        m.name_suffix = Some(m.identifier[start..].to_string());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::env;
     use std::fs;
 
+    fn manager(mods: Vec<Mod>) -> ModManager {
+        ModManager {
+            config: Config::new(String::new(), String::new(), Vec::new()).unwrap(),
+            loaded_mods: Paginator::new(mods, 10),
+            case_corrections: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn sort_enabled_first_keeps_name_order() {
+        let mut mods: Vec<Mod> = ["Alpha", "Bravo", "Charlie", "Delta"]
+            .iter()
+            .map(|name| Mod::new(name.to_lowercase(), name.to_string(), false, false))
+            .collect();
+        mods[1].enabled = true;
+        mods[3].enabled = true;
+
+        let mut manager = manager(mods);
+        manager.sort_enabled_first();
+
+        let names: Vec<&str> = manager
+            .loaded_mods
+            .all_items()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Bravo", "Delta", "Alpha", "Charlie"]);
+    }
+
+    #[test]
+    fn toggle_category_only_flips_that_category() {
+        let mut cdlc = Mod::new("GM".into(), "Global Mobilization".into(), true, false);
+        cdlc.enabled = true;
+        let mods = vec![
+            cdlc,
+            Mod::new("VN".into(), "S.O.G. Prairie Fire".into(), true, false),
+            Mod::new("1".into(), "Workshop".into(), false, false),
+            Mod::missing("2".into()),
+        ];
+        let mut manager = manager(mods);
+
+        // Enabled unless all are enabled already
+        assert_eq!(manager.toggle_category(ModCategory::Cdlc), Some(true));
+        assert_eq!(manager.enabled_identifiers(), vec!["GM", "VN", "2"]);
+        assert_eq!(manager.toggle_category(ModCategory::Cdlc), Some(false));
+        assert_eq!(manager.enabled_identifiers(), vec!["2"]);
+
+        assert_eq!(manager.toggle_category(ModCategory::Workshop), Some(true));
+        assert_eq!(manager.enabled_identifiers(), vec!["1", "2"]);
+        assert_eq!(manager.toggle_category(ModCategory::Custom), None);
+    }
+
+    #[test]
+    fn set_enabled_finds_the_mod() {
+        let mods = vec![
+            Mod::new("1".into(), "Workshop".into(), false, false),
+            Mod::missing("2".into()),
+        ];
+        let mut manager = manager(mods);
+
+        assert!(manager.set_enabled("1", true));
+        assert!(!manager.set_enabled("2", true));
+        assert!(manager.set_enabled("2", false));
+        assert!(!manager.set_enabled("3", true));
+        assert_eq!(manager.enabled_identifiers(), vec!["1"]);
+    }
+
+    #[test]
+    fn toggled_mods_until_saved() {
+        let mods: Vec<Mod> = ["a", "b", "c"]
+            .iter()
+            .map(|id| Mod::new(id.to_string(), id.to_uppercase(), false, false))
+            .collect();
+        let mut manager = manager(mods);
+        manager.config.update_mods(vec!["a".into(), "b".into()]);
+        manager.apply_active_preset();
+
+        manager.set_enabled("b", false);
+        manager.set_enabled("c", true);
+        assert_eq!(manager.toggled_mods(), HashSet::from(["b", "c"]));
+
+        manager.set_enabled("b", true);
+        assert_eq!(manager.toggled_mods(), HashSet::from(["c"]));
+
+        manager.config.update_mods(manager.enabled_identifiers());
+        assert!(manager.toggled_mods().is_empty());
+    }
+
+    #[test]
+    fn preset_identifiers_take_the_case_of_the_folder() {
+        let mods = vec![
+            Mod::new("@CBA_A3".into(), "CBA".into(), false, true),
+            Mod::new("@ace".into(), "ACE".into(), false, true),
+        ];
+        let mut manager = manager(mods);
+        manager
+            .config
+            .update_mods(vec!["@cba_a3".into(), "@ace".into(), "@Other".into()]);
+        manager.config.toggle_favorite("@cba_a3");
+        manager.correct_identifiers();
+        manager.apply_active_preset();
+
+        assert_eq!(
+            manager.take_case_corrections(),
+            vec![("@cba_a3".to_string(), "@CBA_A3".to_string())]
+        );
+        assert!(manager.take_case_corrections().is_empty());
+        assert_eq!(
+            manager.enabled_identifiers(),
+            vec!["@CBA_A3", "@ace", "@Other"]
+        );
+        assert!(manager.config.is_favorite("@CBA_A3"));
+        assert!(manager.toggled_mods().is_empty());
+    }
+
+    #[test]
+    fn published_ids_in_presets_are_replaced_with_the_folder_name() {
+        let mut folder_named = Mod::new("@ace".into(), "ACE".into(), false, false);
+        folder_named.published_id = Some("463939057".into());
+        let mods = vec![
+            folder_named,
+            Mod::new("450814997".into(), "CBA".into(), false, false),
+        ];
+        let mut manager = manager(mods);
+        manager
+            .config
+            .update_mods(vec!["463939057".into(), "450814997".into()]);
+
+        assert_eq!(manager.installed_identifier("463939057"), Some("@ace"));
+        assert_eq!(manager.installed_identifier("450814997"), Some("450814997"));
+        assert_eq!(manager.installed_identifier("1"), None);
+
+        manager.correct_identifiers();
+        manager.apply_active_preset();
+        assert_eq!(manager.enabled_identifiers(), vec!["@ace", "450814997"]);
+        assert!(manager.missing_mods().is_empty());
+        assert_eq!(
+            manager.take_case_corrections(),
+            vec![("463939057".to_string(), "@ace".to_string())]
+        );
+
+        // Switching presets doesn't correct them again
+        manager.config.update_mods(vec!["463939057".into()]);
+        manager.apply_active_preset();
+        assert_eq!(manager.config.get_enabled_mods(), vec!["463939057"]);
+        assert!(manager.take_case_corrections().is_empty());
+    }
+
+    #[test]
+    fn set_installed_mods_keeps_unsaved_toggles() {
+        let mods: Vec<Mod> = ["a", "b"]
+            .iter()
+            .map(|id| Mod::new(id.to_string(), id.to_uppercase(), false, false))
+            .collect();
+        let mut manager = manager(mods);
+        manager.config.update_mods(vec!["a".into(), "c".into()]);
+        manager.apply_active_preset();
+        // Toggled, but not saved to the preset
+        manager.loaded_mods.all_items_mut()[0].enabled = false;
+        manager.loaded_mods.all_items_mut()[1].enabled = true;
+
+        let installed: Vec<Mod> = ["a", "b", "c"]
+            .iter()
+            .map(|id| Mod::new(id.to_string(), id.to_uppercase(), false, false))
+            .collect();
+        manager.set_installed_mods(installed);
+
+        assert_eq!(manager.enabled_identifiers(), vec!["c", "b"]);
+        assert!(manager.missing_mods().is_empty());
+    }
+
+    #[test]
+    fn custom_mods_are_scanned_from_every_folder() {
+        let dir = env::temp_dir().join("a3mm_custom_folders_test");
+        let _ = fs::remove_dir_all(&dir);
+        let (dev, shared) = (dir.join("dev"), dir.join("shared"));
+        for path in [
+            dev.join("@dev"),
+            dev.join("@both"),
+            shared.join("@both"),
+            shared.join("@shared"),
+        ] {
+            fs::create_dir_all(&path).unwrap();
+        }
+
+        let config = Config::new(
+            dir.join("Arma 3").to_string_lossy().to_string(),
+            dir.join("workshop").to_string_lossy().to_string(),
+            vec![
+                dev.to_string_lossy().to_string(),
+                shared.to_string_lossy().to_string(),
+            ],
+        )
+        .unwrap();
+        let mut mods = ModManager::get_installed_mods(&config).unwrap();
+        mods.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+        let identifiers: Vec<&str> = mods.iter().map(|m| m.identifier.as_str()).collect();
+        assert_eq!(identifiers, ["@both", "@dev", "@shared"]);
+
+        let paths = config.get_custom_mods_paths();
+        assert_eq!(mods[0].find_path(&paths), Some(dev.join("@both")));
+        assert_eq!(mods[2].find_path(&paths), Some(shared.join("@shared")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn workshop_url_only_for_workshop_mods() {
+        let workshop_mod = Mod::new("450814997".into(), "CBA_A3".into(), false, false);
+        assert_eq!(
+            workshop_mod.workshop_url().as_deref(),
+            Some("https://steamcommunity.com/sharedfiles/filedetails/?id=450814997")
+        );
+
+        let cdlc = Mod::new("ws".into(), "Western Sahara".into(), true, false);
+        assert_eq!(cdlc.workshop_url(), None);
+
+        let mut custom = Mod::new("@cba".into(), "CBA_A3".into(), false, true);
+        assert_eq!(custom.workshop_url(), None);
+        custom.published_id = Some("450814997".into());
+        assert_eq!(custom.workshop_url(), workshop_mod.workshop_url());
+    }
+
+    #[test]
+    fn duplicate_names_get_the_end_of_the_identifier() {
+        let mut mods = vec![
+            Mod::new("450814997".into(), "CBA_A3".into(), false, false),
+            Mod::new("2867537125".into(), "CBA_A3".into(), false, false),
+            Mod::new("463939057".into(), "ace".into(), false, false),
+            Mod::new("abc".into(), "Dup".into(), false, true),
+            Mod::new("xyz".into(), "Dup".into(), false, true),
+        ];
+        disambiguate_names(&mut mods);
+
+        assert!(mods.iter().all(|m| !m.name.contains('(')));
+        let names: Vec<String> = mods.iter().map(Mod::display_name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "CBA_A3 (4997)",
+                "CBA_A3 (7125)",
+                "ace",
+                "Dup (abc)",
+                "Dup (xyz)"
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_by_last_updated() {
+        let mut mods: Vec<Mod> = ["Alpha", "Bravo", "Charlie"]
+            .iter()
+            .map(|name| Mod::new(name.to_lowercase(), name.to_string(), false, false))
+            .collect();
+        mods[0].timestamp = Some(100);
+        mods[2].timestamp = Some(200);
+        mods.push(Mod::missing("gone".into()));
+
+        let mut manager = manager(mods);
+        manager.config.set_sort_order(SortOrder::Updated);
+        manager.apply_sort_order();
+
+        let ids: Vec<&str> = manager
+            .loaded_mods
+            .all_items()
+            .iter()
+            .map(|m| m.identifier.as_str())
+            .collect();
+        assert_eq!(ids, vec!["charlie", "alpha", "bravo", "gone"]);
+
+        manager.config.set_sort_order(SortOrder::Name);
+        manager.apply_sort_order();
+        assert_eq!(manager.loaded_mods.all_items()[0].identifier, "alpha");
+    }
+
+    #[test]
+    fn set_enabled_matching_keeps_load_order() {
+        let mods = vec![
+            Mod::new("ace".into(), "ACE3".into(), false, false),
+            Mod::new("cba".into(), "CBA_A3".into(), false, false),
+            Mod::new("rhs".into(), "RHS: AFRF".into(), false, false),
+        ];
+        let mut manager = manager(mods);
+        manager
+            .config
+            .update_mods(vec!["ace".into(), "gone".into()]);
+        manager.apply_active_preset();
+
+        let changed = manager.set_enabled_matching(&Regex::new("(?i)^cba|rhs").unwrap(), true);
+        assert_eq!(changed, vec!["CBA_A3", "RHS: AFRF"]);
+
+        let changed = manager.set_enabled_matching(&Regex::new("^(gone|rhs)$").unwrap(), false);
+        assert_eq!(changed, vec!["RHS: AFRF", "gone"]);
+
+        assert_eq!(
+            manager.config.get_enabled_mods(),
+            vec!["ace".to_string(), "cba".to_string()]
+        );
+    }
+
+    #[test]
+    fn favorites_are_sorted_first() {
+        let mods = ["Alpha", "Bravo", "Charlie"]
+            .iter()
+            .map(|name| Mod::new(name.to_lowercase(), name.to_string(), false, false))
+            .collect();
+        let mut manager = manager(mods);
+
+        manager.toggle_favorite(2);
+        manager.apply_sort_order();
+
+        let names: Vec<&str> = manager
+            .loaded_mods
+            .all_items()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Charlie", "Alpha", "Bravo"]);
+        assert!(manager.config.is_favorite("charlie"));
+
+        manager.toggle_favorite(0);
+        manager.apply_sort_order();
+        assert_eq!(manager.loaded_mods.all_items()[0].name, "Alpha");
+        assert!(!manager.config.is_favorite("charlie"));
+    }
+
+    #[test]
+    fn missing_enabled_mods_are_listed_and_purged() {
+        let mods = vec![Mod::new(
+            "installed".into(),
+            "Installed".into(),
+            false,
+            false,
+        )];
+        let mut manager = manager(mods);
+        manager
+            .config
+            .update_mods(vec!["installed".into(), "gone".into()]);
+        manager.apply_active_preset();
+
+        let items = manager.loaded_mods.all_items();
+        assert_eq!(items.len(), 2);
+        assert!(items[0].enabled && !items[0].is_missing);
+        assert!(items[1].enabled && items[1].is_missing);
+        assert_eq!(items[1].identifier, "gone");
+
+        // Re-applying doesn't duplicate missing entries
+        manager.apply_active_preset();
+        assert_eq!(manager.loaded_mods.all_items().len(), 2);
+
+        // Disabled since the preset was saved
+        manager.loaded_mods.all_items_mut()[0].enabled = false;
+
+        assert_eq!(manager.purge_missing_mods(), 1);
+        assert_eq!(manager.loaded_mods.all_items().len(), 1);
+        assert!(!manager.loaded_mods.all_items()[0].enabled);
+        assert_eq!(
+            manager.config.get_enabled_mods(),
+            vec!["installed".to_string()]
+        );
+    }
+
+    #[test]
+    fn load_order_is_kept_and_moved() {
+        let mods = ["Alpha", "Bravo", "Charlie"]
+            .iter()
+            .map(|name| Mod::new(name.to_lowercase(), name.to_string(), false, false))
+            .collect();
+        let mut manager = manager(mods);
+        manager
+            .config
+            .update_mods(vec!["charlie".into(), "alpha".into()]);
+        manager.apply_active_preset();
+        assert_eq!(manager.enabled_identifiers(), vec!["charlie", "alpha"]);
+
+        // Newly enabled mods are loaded last
+        manager.loaded_mods.all_items_mut()[1].enabled = true;
+        assert_eq!(
+            manager.enabled_identifiers(),
+            vec!["charlie", "alpha", "bravo"]
+        );
+
+        // Unsaved mods have no place in the saved order yet
+        assert!(!manager.move_in_load_order("bravo", true));
+
+        assert!(!manager.move_in_load_order("charlie", true));
+        assert!(!manager.move_in_load_order("alpha", false));
+        assert!(manager.move_in_load_order("charlie", false));
+        assert_eq!(
+            manager.enabled_identifiers(),
+            vec!["alpha", "charlie", "bravo"]
+        );
+        // Moving doesn't save the newly enabled mod
+        assert_eq!(manager.config.get_enabled_mods(), vec!["alpha", "charlie"]);
+    }
+
+    #[test]
+    fn moving_in_load_order_skips_unsaved_disabled_mods() {
+        let mods = ["Alpha", "Bravo", "Charlie"]
+            .iter()
+            .map(|name| Mod::new(name.to_lowercase(), name.to_string(), false, false))
+            .collect();
+        let mut manager = manager(mods);
+        manager
+            .config
+            .update_mods(vec!["alpha".into(), "bravo".into(), "charlie".into()]);
+        manager.apply_active_preset();
+        manager.loaded_mods.all_items_mut()[1].enabled = false;
+
+        assert!(manager.move_in_load_order("charlie", true));
+        assert_eq!(manager.enabled_identifiers(), vec!["charlie", "alpha"]);
+        // Bravo is disabled but not saved as such
+        assert_eq!(
+            manager.config.get_enabled_mods(),
+            vec!["charlie", "bravo", "alpha"]
+        );
+    }
+
     #[test]
     fn test_mod_manager_full_flow() {
         // Setup paths
@@ -198,13 +1246,13 @@ mod tests {
         let config = Config::new(
             game_path.to_string_lossy().to_string(),
             workshop_path.to_string_lossy().to_string(),
-            None,
+            Vec::new(),
         )
         .expect("Failed to create config");
         config.save().expect("Failed to save config");
 
         // Initialize ModManager
-        let manager = ModManager::new(10).expect("Failed to initialize ModManager");
+        let manager = ModManager::new().expect("Failed to initialize ModManager");
 
         // Verify Config was saved at the OS-specific path
         let config_path = Config::get_save_path().expect("Failed to get config save path");
@@ -230,7 +1278,7 @@ mod tests {
         let mod_paths = vec![mod_path];
 
         // Create symlinks
-        file_handler::create_sym_links(config_game_path, mod_paths)
+        file_handler::create_sym_links(config_game_path, mod_paths, &[config_workshop_path])
             .expect("Failed to create symlinks");
 
         // Verify symlink exists
@@ -243,7 +1291,7 @@ mod tests {
         assert!(symlink_path.is_symlink(), "Created file is not a symlink");
 
         // Test Symlink Removal
-        file_handler::remove_dir_symlinks(config_game_path).expect("Failed to remove symlinks");
+        launcher::remove_linked_mods(&manager.config).expect("Failed to remove symlinks");
 
         // Verify symlink is gone
         assert!(!symlink_path.exists(), "Symlink was not removed");
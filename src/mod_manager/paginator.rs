@@ -1,21 +1,38 @@
 #[derive(Debug)]
 pub struct Paginator<T> {
     items: Vec<T>,
+    /// Indices of the visible items, when filtered
+    view: Option<Vec<usize>>,
     pub page_size: usize,
     pub current_page: usize,
+    /// Show a window that follows the selection instead of fixed pages
+    pub scrolling: bool,
+    /// First visible position when scrolling
+    scroll_offset: usize,
 }
 
 impl<T> Paginator<T> {
     pub fn new(items: Vec<T>, page_size: usize) -> Self {
         Paginator {
             items,
+            view: None,
             page_size,
             current_page: 0,
+            scrolling: false,
+            scroll_offset: 0,
+        }
+    }
+
+    /// Number of items that are visible with the current filter
+    pub fn visible_len(&self) -> usize {
+        match &self.view {
+            Some(view) => view.len(),
+            None => self.items.len(),
         }
     }
 
     pub fn total_pages(&self) -> usize {
-        self.items.len().div_ceil(self.page_size)
+        self.visible_len().div_ceil(self.page_size)
     }
 
     pub fn all_items(&self) -> &[T] {
@@ -26,10 +43,148 @@ impl<T> Paginator<T> {
         &mut self.items
     }
 
-    pub fn current_page_items(&self) -> &[T] {
-        let start = self.current_page * self.page_size;
-        let end = usize::min(start + self.page_size, self.items.len());
-        &self.items[start..end]
+    /// Position of the first item on the current page, or in the window when scrolling
+    pub fn window_start(&self) -> usize {
+        if self.scrolling {
+            self.scroll_offset
+        } else {
+            self.current_page * self.page_size
+        }
+    }
+
+    /// Whether there are items before the current page or window
+    pub fn has_previous(&self) -> bool {
+        self.window_start() > 0
+    }
+
+    /// Whether there are items after the current page or window
+    pub fn has_next(&self) -> bool {
+        self.window_start() + self.page_size < self.visible_len()
+    }
+
+    pub fn current_page_items(&self) -> Vec<&T> {
+        let start = self.window_start();
+        let end = usize::min(start + self.page_size, self.visible_len());
+
+        (start..end)
+            .filter_map(|i| self.visible_index(i))
+            .map(|i| &self.items[i])
+            .collect()
+    }
+
+    /// Maps an offset on the current page to the index in `all_items`
+    pub fn global_index(&self, page_offset: usize) -> Option<usize> {
+        if page_offset >= self.page_size {
+            return None;
+        }
+        self.visible_index(self.window_start() + page_offset)
+    }
+
+    /// Moves the window just enough to show the item at the visible position
+    pub fn scroll_to(&mut self, position: usize) {
+        if position < self.scroll_offset {
+            self.scroll_offset = position;
+        } else if position >= self.scroll_offset + self.page_size {
+            self.scroll_offset = position + 1 - self.page_size;
+        }
+    }
+
+    /// Moves the visible position by `delta` and the window along with it, so the position
+    /// stays on the same row until the window reaches the first or last item.
+    /// Returns the new position.
+    pub fn scroll_by(&mut self, position: usize, delta: isize) -> usize {
+        let len = self.visible_len();
+        let new_position = position
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+        let max_offset = len.saturating_sub(self.page_size);
+
+        self.scroll_offset = if new_position >= position {
+            (self.scroll_offset + (new_position - position)).min(max_offset)
+        } else {
+            self.scroll_offset.saturating_sub(position - new_position)
+        };
+        self.scroll_to(new_position);
+        new_position
+    }
+
+    /// Back to the first page, or the top when scrolling
+    pub fn rewind(&mut self) {
+        self.current_page = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// Maps a position in the visible items to the index in `all_items`
+    pub fn visible_index(&self, position: usize) -> Option<usize> {
+        match &self.view {
+            Some(view) => view.get(position).copied(),
+            None => (position < self.items.len()).then_some(position),
+        }
+    }
+
+    /// Index in `all_items` of the first item matching the predicate
+    pub fn position<F>(&self, predicate: F) -> Option<usize>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.items.iter().position(predicate)
+    }
+
+    /// Shows the page with the item at the index in `all_items`, or scrolls to it.
+    /// Returns its offset on the page, or `None` when it is not visible.
+    pub fn goto_item(&mut self, index: usize) -> Option<usize> {
+        let position = match &self.view {
+            Some(view) => view.iter().position(|&i| i == index)?,
+            None => (index < self.items.len()).then_some(index)?,
+        };
+
+        if self.scrolling {
+            self.scroll_to(position);
+        } else {
+            self.current_page = position / self.page_size;
+        }
+
+        Some(position - self.window_start())
+    }
+
+    /// Only show the items matching the predicate, starting from the first page
+    pub fn set_filter<F>(&mut self, predicate: F)
+    where
+        F: Fn(&T) -> bool,
+    {
+        let view = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| predicate(item))
+            .map(|(i, _)| i)
+            .collect();
+
+        self.view = Some(view);
+        self.rewind();
+    }
+
+    /// Only show the items the function gives a score, the highest score first and
+    /// otherwise in their current order, starting from the first page
+    pub fn set_ranked_filter<F>(&mut self, score: F)
+    where
+        F: Fn(&T) -> Option<i64>,
+    {
+        let mut ranked: Vec<(usize, i64)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| score(item).map(|score| (i, score)))
+            .collect();
+        ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+        self.view = Some(ranked.into_iter().map(|(i, _)| i).collect());
+        self.rewind();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.view = None;
+        self.rewind();
     }
 
     pub fn next_page(&mut self) {
@@ -44,6 +199,66 @@ impl<T> Paginator<T> {
         }
     }
 
+    /// Jumps to the page, clamped to the last page
+    pub fn goto_page(&mut self, page: usize) {
+        self.current_page = page.min(self.total_pages().saturating_sub(1));
+    }
+
+    pub fn goto_first(&mut self) {
+        self.goto_page(0);
+    }
+
+    pub fn goto_last(&mut self) {
+        self.goto_page(usize::MAX);
+    }
+
+    /// Changes the number of items per page, keeping the first shown item in view
+    pub fn set_page_size(&mut self, page_size: usize) {
+        let page_size = page_size.max(1);
+        let start = self.window_start();
+
+        self.page_size = page_size;
+        self.current_page = start / page_size;
+        self.scroll_offset = start.min(self.visible_len().saturating_sub(page_size));
+    }
+
+    /// Keeps only the items matching the predicate, clamping the current page.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let keep: Vec<bool> = self.items.iter().map(&mut predicate).collect();
+
+        // Shift the filtered indices to match the remaining items
+        if let Some(view) = &mut self.view {
+            let mut new_indices = Vec::with_capacity(keep.len());
+            let mut next = 0;
+            for kept in &keep {
+                new_indices.push(next);
+                if *kept {
+                    next += 1;
+                }
+            }
+            view.retain(|i| keep[*i]);
+            view.iter_mut().for_each(|i| *i = new_indices[*i]);
+        }
+
+        let mut keep = keep.into_iter();
+        self.items.retain(|_| keep.next().unwrap_or(false));
+
+        self.current_page = self.current_page.min(self.total_pages().saturating_sub(1));
+        self.scroll_offset = self
+            .scroll_offset
+            .min(self.visible_len().saturating_sub(self.page_size));
+    }
+
+    pub fn extend<I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.items.extend(items);
+    }
+
     pub fn filter<F>(&self, predicate: F) -> Vec<&T>
     where
         F: Fn(&T) -> bool,
@@ -87,14 +302,14 @@ mod tests {
     #[test]
     fn current_page_items_first_page() {
         let p = make_paginator(7, 3);
-        assert_eq!(p.current_page_items(), &[0, 1, 2]);
+        assert_eq!(p.current_page_items(), [&0, &1, &2]);
     }
 
     #[test]
     fn current_page_items_last_partial_page() {
         let mut p = make_paginator(7, 3);
         p.current_page = 2; // third page: only item 6
-        assert_eq!(p.current_page_items(), &[6]);
+        assert_eq!(p.current_page_items(), [&6]);
     }
 
     #[test]
@@ -103,7 +318,7 @@ mod tests {
         assert_eq!(p.current_page, 0);
         p.next_page();
         assert_eq!(p.current_page, 1);
-        assert_eq!(p.current_page_items(), &[3, 4, 5]);
+        assert_eq!(p.current_page_items(), [&3, &4, &5]);
     }
 
     #[test]
@@ -130,6 +345,266 @@ mod tests {
         assert_eq!(p.current_page, 1);
     }
 
+    #[test]
+    fn goto_page_clamps_to_last_page() {
+        let mut p = make_paginator(10, 3);
+        p.goto_page(2);
+        assert_eq!(p.current_page_items(), [&6, &7, &8]);
+
+        p.goto_page(10);
+        assert_eq!(p.current_page, 3);
+
+        p.goto_first();
+        assert_eq!(p.current_page, 0);
+
+        p.goto_last();
+        assert_eq!(p.current_page_items(), [&9]);
+    }
+
+    #[test]
+    fn goto_page_on_empty_paginator() {
+        let mut p = make_paginator(0, 3);
+        p.goto_page(5);
+        assert_eq!(p.current_page, 0);
+
+        p.goto_last();
+        assert_eq!(p.current_page, 0);
+        assert!(p.current_page_items().is_empty());
+    }
+
+    #[test]
+    fn position_finds_first_match() {
+        let mut p = make_paginator(10, 3);
+        p.set_filter(|x| *x > 5);
+        // Indices refer to all items, also when filtered
+        assert_eq!(p.position(|x| *x % 4 == 0), Some(0));
+        assert_eq!(p.position(|x| *x > 20), None);
+    }
+
+    #[test]
+    fn goto_item_returns_page_offset() {
+        let mut p = make_paginator(10, 3);
+        assert_eq!(p.goto_item(0), Some(0));
+        assert_eq!(p.current_page, 0);
+
+        assert_eq!(p.goto_item(2), Some(2));
+        assert_eq!(p.current_page, 0);
+
+        assert_eq!(p.goto_item(3), Some(0));
+        assert_eq!(p.current_page, 1);
+
+        assert_eq!(p.goto_item(9), Some(0));
+        assert_eq!(p.current_page, 3);
+
+        // Out of range keeps the current page
+        assert_eq!(p.goto_item(10), None);
+        assert_eq!(p.current_page, 3);
+    }
+
+    #[test]
+    fn goto_item_through_filter() {
+        let mut p = make_paginator(10, 2);
+        p.set_filter(|x| *x % 3 == 0);
+
+        // 9 is the fourth visible item, the second on the second page
+        assert_eq!(p.goto_item(9), Some(1));
+        assert_eq!(p.current_page, 1);
+        assert_eq!(p.global_index(1), Some(9));
+
+        assert_eq!(p.goto_item(4), None);
+    }
+
+    #[test]
+    fn goto_item_when_scrolling() {
+        let mut p = make_paginator(10, 3);
+        p.scrolling = true;
+
+        assert_eq!(p.goto_item(7), Some(2));
+        assert_eq!(p.current_page_items(), [&5, &6, &7]);
+
+        assert_eq!(p.goto_item(6), Some(1));
+        assert_eq!(p.window_start(), 5);
+
+        assert_eq!(p.goto_item(1), Some(0));
+        assert_eq!(p.current_page_items(), [&1, &2, &3]);
+    }
+
+    #[test]
+    fn retain_clamps_current_page() {
+        let mut p = make_paginator(10, 3);
+        p.current_page = 3;
+        p.retain(|x| *x < 4);
+        assert_eq!(p.all_items(), &[0, 1, 2, 3]);
+        assert_eq!(p.current_page, 1);
+    }
+
+    #[test]
+    fn retain_all_removed() {
+        let mut p = make_paginator(5, 2);
+        p.current_page = 2;
+        p.retain(|_| false);
+        assert_eq!(p.current_page, 0);
+        assert!(p.current_page_items().is_empty());
+    }
+
+    #[test]
+    fn extend_appends_items() {
+        let mut p = make_paginator(2, 5);
+        p.extend(vec![10, 11]);
+        assert_eq!(p.all_items(), &[0, 1, 10, 11]);
+    }
+
+    #[test]
+    fn set_filter_paginates_visible_items() {
+        let mut p = make_paginator(10, 2);
+        p.current_page = 3;
+        p.set_filter(|x| *x % 3 == 0);
+
+        assert_eq!(p.current_page, 0);
+        assert_eq!(p.visible_len(), 4);
+        assert_eq!(p.total_pages(), 2);
+        assert_eq!(p.current_page_items(), [&0, &3]);
+
+        p.next_page();
+        assert_eq!(p.current_page_items(), [&6, &9]);
+    }
+
+    #[test]
+    fn set_ranked_filter_orders_by_score() {
+        let mut p = make_paginator(10, 3);
+        p.next_page();
+        p.set_ranked_filter(|x| (*x % 3 != 0).then_some((*x % 3) as i64));
+
+        assert_eq!(p.current_page, 0);
+        assert_eq!(p.visible_len(), 6);
+        assert_eq!(p.current_page_items(), [&2, &5, &8]);
+
+        p.next_page();
+        assert_eq!(p.current_page_items(), [&1, &4, &7]);
+        assert_eq!(p.global_index(0), Some(1));
+    }
+
+    #[test]
+    fn global_index_maps_through_filter() {
+        let mut p = make_paginator(10, 2);
+        p.set_filter(|x| *x % 3 == 0);
+        p.next_page();
+
+        assert_eq!(p.global_index(0), Some(6));
+        assert_eq!(p.global_index(1), Some(9));
+        assert_eq!(p.global_index(2), None);
+    }
+
+    #[test]
+    fn global_index_unfiltered() {
+        let mut p = make_paginator(5, 2);
+        p.next_page();
+        p.next_page();
+        assert_eq!(p.global_index(0), Some(4));
+        assert_eq!(p.global_index(1), None);
+    }
+
+    #[test]
+    fn clear_filter_restores_all_items() {
+        let mut p = make_paginator(5, 10);
+        p.set_filter(|x| *x > 10);
+        assert!(p.current_page_items().is_empty());
+        assert_eq!(p.total_pages(), 0);
+
+        p.clear_filter();
+        assert_eq!(p.current_page_items().len(), 5);
+    }
+
+    #[test]
+    fn retain_keeps_filter_in_sync() {
+        let mut p = make_paginator(6, 10);
+        p.set_filter(|x| *x % 2 == 0);
+        p.retain(|x| *x != 2);
+
+        assert_eq!(p.all_items(), &[0, 1, 3, 4, 5]);
+        assert_eq!(p.current_page_items(), [&0, &4]);
+    }
+
+    #[test]
+    fn scroll_window_follows_position() {
+        let mut p = make_paginator(10, 3);
+        p.scrolling = true;
+        assert_eq!(p.current_page_items(), [&0, &1, &2]);
+        assert!(!p.has_previous());
+
+        // Moving within the window does not scroll
+        p.scroll_to(2);
+        assert_eq!(p.window_start(), 0);
+
+        p.scroll_to(3);
+        assert_eq!(p.current_page_items(), [&1, &2, &3]);
+        assert_eq!(p.global_index(2), Some(3));
+
+        p.scroll_to(9);
+        assert_eq!(p.current_page_items(), [&7, &8, &9]);
+        assert!(p.has_previous());
+        assert!(!p.has_next());
+
+        p.scroll_to(5);
+        assert_eq!(p.current_page_items(), [&5, &6, &7]);
+    }
+
+    #[test]
+    fn scroll_by_keeps_the_row() {
+        let mut p = make_paginator(10, 3);
+        p.scrolling = true;
+
+        assert_eq!(p.scroll_by(1, 3), 4);
+        assert_eq!(p.current_page_items(), [&3, &4, &5]);
+
+        assert_eq!(p.scroll_by(4, 3), 7);
+        assert_eq!(p.current_page_items(), [&6, &7, &8]);
+
+        // The window stops at the last item, the position does not
+        assert_eq!(p.scroll_by(7, 3), 9);
+        assert_eq!(p.current_page_items(), [&7, &8, &9]);
+
+        assert_eq!(p.scroll_by(9, -3), 6);
+        assert_eq!(p.current_page_items(), [&4, &5, &6]);
+
+        assert_eq!(p.scroll_by(6, -10), 0);
+        assert_eq!(p.current_page_items(), [&0, &1, &2]);
+    }
+
+    #[test]
+    fn retain_clamps_scroll_offset() {
+        let mut p = make_paginator(10, 3);
+        p.scrolling = true;
+        p.scroll_to(9);
+        p.retain(|x| *x < 5);
+        assert_eq!(p.current_page_items(), [&2, &3, &4]);
+    }
+
+    #[test]
+    fn set_page_size_keeps_first_item_in_view() {
+        let mut p = make_paginator(10, 3);
+        p.goto_page(2);
+        assert_eq!(p.current_page_items(), [&6, &7, &8]);
+
+        p.set_page_size(4);
+        assert_eq!(p.current_page_items(), [&4, &5, &6, &7]);
+        assert_eq!(p.total_pages(), 3);
+
+        p.set_page_size(0);
+        assert_eq!(p.page_size, 1);
+        assert_eq!(p.current_page_items(), [&4]);
+    }
+
+    #[test]
+    fn set_page_size_clamps_scroll_offset() {
+        let mut p = make_paginator(10, 3);
+        p.scrolling = true;
+        p.scroll_to(9);
+
+        p.set_page_size(5);
+        assert_eq!(p.current_page_items(), [&5, &6, &7, &8, &9]);
+    }
+
     #[test]
     fn filter_returns_matching() {
         let p = make_paginator(6, 10);
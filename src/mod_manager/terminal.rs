@@ -1,13 +1,24 @@
 use std::{
+    ffi::OsString,
     io::{self, Stdout, Write},
-    path::PathBuf,
-    process::Command,
-    time::Duration,
+    ops::RangeInclusive,
+    panic,
+    path::{Path, PathBuf},
+    process::{Child, Command},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use crossterm::{
     cursor::{self, SetCursorStyle},
-    event::{self, poll, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{
+        self, poll, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent,
+        KeyEventKind, KeyModifiers,
+    },
     execute,
     style::{Color, Print, SetForegroundColor},
     terminal,
@@ -15,67 +26,448 @@ use crossterm::{
 
 use crate::{
     errors::{AppError, AppResult},
-    mod_manager::config::Config,
+    mod_manager::config::{Config, LinkMode, ModFilter, Server},
 };
 
-use super::{dependency_manager, ModManager};
+#[cfg(feature = "network")]
+use super::dependency_manager;
+use super::{
+    bisect::Bisection,
+    file_handler, launcher,
+    text_input::{InputAction, TextInput},
+    utils, Mod, ModCategory, ModManager,
+};
 
-pub struct Terminal<'a> {
-    mod_manager: &'a mut ModManager,
-    selected_index: usize,
+/// Startup arguments used when launching in safe mode
+const SAFE_MODE_ARGS: [&str; 2] = ["-noSplash", "-skipIntro"];
+
+/// Action, keys, and whether it is listed in the panel next to the mods.
+/// All of them are listed in the help screen.
+const KEYBINDINGS: &[(&str, &str, bool)] = &[
+    ("Navigation", "<WASD>, <HJKL> or <ARROW KEYS>", true),
+    (
+        "Next / Previous Page",
+        "<PGDN> / <PGUP>, <CTRL> + D/U",
+        false,
+    ),
+    ("First / Last Page", "g / G", false),
+    ("Go to Page", ":", false),
+    ("Search Mods", "/", true),
+    ("Toggle Selected Mod", "<SPACE>", true),
+    ("Toggle All Mods", "<CTRL> + <SPACE>", true),
+    ("Toggle Workshop / Custom Mods", "1 / 2", false),
+    ("Toggle CDLCs / Server Mods", "3 / 4", false),
+    ("Undo Bulk Change", "U", false),
+    ("Solo Selected Mod", "O", false),
+    ("Favorite Selected Mod", "*", false),
+    ("Tag Selected Mod", "#", false),
+    ("Select Range", "<SHIFT> + V", false),
+    ("Show Enabled / Disabled Mods", "<F2>", false),
+    ("Filter by Tag", "<F3>", false),
+    ("Show Only Favorites", "<F4>", false),
+    ("Move in Load Order", "<SHIFT> + K/J", false),
+    ("Cycle Presets", "<TAB> / <SHIFT+TAB>", true),
+    ("Manage Presets", "T", true),
+    ("Join Server", "N", false),
+    ("Select Profile", "<SHIFT> + N", false),
+    ("Start in Mission / Editor", "M", false),
+    #[cfg(feature = "network")]
+    ("Check Dependencies", "C", true),
+    #[cfg(feature = "network")]
+    ("Check All Dependencies", "<SHIFT> + C", false),
+    #[cfg(feature = "network")]
+    ("Dependency Tree", "<CTRL> + T", false),
+    ("Refresh Mods", "R", true),
+    ("Cycle Sort Order", "<SHIFT> + O", false),
+    ("Set Custom Parameters", "F", true),
+    ("Set Executable Name", "E", false),
+    ("Toggle BattlEye", "<CTRL> + E", false),
+    ("Set Game Path", "<CTRL> + G", false),
+    ("Set Workshop Path", "<CTRL> + W", false),
+    ("Custom Mod Folders", "<CTRL> + F", false),
+    ("Open Mod Folder", "<CTRL> + O", false),
+    ("Open Workshop Page", "<CTRL> + B", false),
+    ("Remove Linked Mods", "<CTRL> + L", false),
+    ("Save Config", "<ENTER>", true),
+    ("Launch Game", "P", true),
+    ("Launch Safe Mode", "<SHIFT> + P", false),
+    ("Bisect Enabled Mods", "B", false),
+    ("Export HTML Preset", "X", false),
+    ("Purge Missing Mods", "<DEL>", true),
+    ("Quit", "Q / <ESC>", true),
+    ("Help", "?", true),
+];
+
+/// Rows above the mod list: the title, config path, counts, header and missing mods
+const LIST_TOP: u16 = 5;
+/// Rows below the mod list: the page arrows, last updated and the banner
+const LIST_BOTTOM: u16 = 3;
+const MIN_COLUMNS: u16 = 40;
+const MIN_ROWS: u16 = LIST_TOP + LIST_BOTTOM + 3;
+/// The list doesn't grow past this, so the panel stays next to it on wide terminals
+const MAX_LIST_WIDTH: u16 = 80;
+const PANEL_PADDING: usize = 25;
+
+/// Positions on the main screen, computed from the terminal size
+struct Layout {
+    page_size: usize,
+    name_width: usize,
+    tag_column: u16,
+    order_column: u16,
+    /// Column of the keybinding panel, `None` when it doesn't fit
+    panel_column: Option<u16>,
+    panel_rows: usize,
 }
 
-impl<'a> Terminal<'a> {
-    pub fn new(mod_manager: &'a mut ModManager) -> Self {
-        Terminal {
-            mod_manager,
-            selected_index: 0,
+impl Layout {
+    /// Returns `None` when the terminal is too small to show the mod list
+    fn new(columns: u16, rows: u16) -> Option<Self> {
+        if columns < MIN_COLUMNS || rows < MIN_ROWS {
+            return None;
         }
-    }
 
-    /// Get the executable path based on the current platform
-    #[cfg(target_os = "macos")]
-    fn get_executable_path(game_path: &std::path::Path, executable_name: &str) -> PathBuf {
-        let game_app_path = game_path.join(format!("{}.app", executable_name));
-        game_app_path.join("Contents/MacOS").join(executable_name)
-    }
+        let panel_width = KEYBINDINGS
+            .iter()
+            .filter(|(_, _, in_panel)| *in_panel)
+            .map(|(_, keybinding, _)| PANEL_PADDING + keybinding.len())
+            .max()
+            .unwrap_or_default() as u16;
+
+        let (list_width, panel_column) = if columns >= MIN_COLUMNS + panel_width {
+            let list_width = (columns - panel_width).min(MAX_LIST_WIDTH);
+            (list_width, Some(list_width))
+        } else {
+            (columns.min(MAX_LIST_WIDTH), None)
+        };
 
-    #[cfg(target_os = "linux")]
-    fn get_executable_path(game_path: &std::path::Path, executable_name: &str) -> PathBuf {
-        game_path.join(executable_name)
+        Some(Layout {
+            page_size: (rows - LIST_TOP - LIST_BOTTOM) as usize,
+            name_width: (list_width - 14) as usize,
+            tag_column: list_width - 9,
+            order_column: list_width - 4,
+            panel_column,
+            // Below the panel header, above the banner
+            panel_rows: rows.saturating_sub(5) as usize,
+        })
     }
+}
 
-    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-    fn get_executable_path(game_path: &std::path::Path, executable_name: &str) -> PathBuf {
-        // Fallback for other platforms - assume direct executable
-        game_path.join(executable_name)
-    }
+/// Set once the terminal has been restored, so it only happens once
+static TERMINAL_RESTORED: AtomicBool = AtomicBool::new(false);
 
-    pub fn run(&mut self) -> AppResult<()> {
-        let mut stdout = io::stdout();
+/// Sets up the terminal for the UI and restores it when dropped,
+/// also when returning early with an error or unwinding from a panic
+struct TerminalGuard {
+    alternate_screen: bool,
+}
+
+impl TerminalGuard {
+    fn new(stdout: &mut Stdout, alternate_screen: bool) -> AppResult<Self> {
+        TERMINAL_RESTORED.store(false, Ordering::SeqCst);
+
+        // Restore before the panic message is printed, so it ends up readable in the main screen
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_terminal(alternate_screen);
+            default_hook(info);
+        }));
 
         execute!(stdout, cursor::SavePosition)?;
-        execute!(stdout, terminal::EnterAlternateScreen)?;
+        if alternate_screen {
+            execute!(stdout, terminal::EnterAlternateScreen)?;
+        }
         execute!(stdout, crossterm::cursor::Hide)?;
+        // Pasted text arrives as one event instead of key presses, where a line break would
+        // confirm the input early. Not every terminal supports it, then pasting works as before
+        let _ = execute!(stdout, EnableBracketedPaste);
 
         terminal::enable_raw_mode()?;
 
-        if !self.mod_manager.config.is_valid() {
-            self.run_setup_wizard(&mut stdout)?;
+        Ok(TerminalGuard { alternate_screen })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal(self.alternate_screen);
+    }
+}
+
+fn restore_terminal(alternate_screen: bool) {
+    if TERMINAL_RESTORED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let mut stdout = io::stdout();
+
+    // Errors are ignored, there is nothing left to do if restoring fails
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(stdout, DisableBracketedPaste);
+
+    if alternate_screen {
+        let _ = execute!(stdout, terminal::LeaveAlternateScreen);
+    } else {
+        let _ = execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All)
+        );
+    }
+    let _ = execute!(
+        stdout,
+        cursor::RestorePosition,
+        cursor::Show,
+        SetCursorStyle::DefaultUserShape
+    );
+}
+
+/// Color of a mod in the list. Missing mods are dimmed even though they count as enabled.
+fn row_color(m: &Mod) -> Color {
+    if m.is_missing {
+        Color::DarkGrey
+    } else if m.enabled {
+        Color::White
+    } else {
+        Color::Grey
+    }
+}
+
+/// Reads the next event, or `None` for a key release or repeat. Windows reports those
+/// besides the press, which would otherwise handle every key twice.
+fn read_event() -> AppResult<Option<Event>> {
+    match event::read()? {
+        Event::Key(KeyEvent { kind, .. }) if kind != KeyEventKind::Press => Ok(None),
+        event => Ok(Some(event)),
+    }
+}
+
+/// What to do about a config that can't be read, chosen on `corrupt_config_screen`
+pub enum ConfigRecovery {
+    RestoreBackup,
+    /// Start over with a new config, like on the first run
+    Reset,
+    Quit,
+}
+
+/// Explains that the config can't be read and asks whether to restore the backup, start over
+/// or quit to fix it by hand. Shown before there is a config to draw the manager with.
+pub fn corrupt_config_screen(
+    path: &str,
+    message: &str,
+    has_backup: bool,
+) -> AppResult<ConfigRecovery> {
+    let mut stdout = io::stdout();
+    let _guard = TerminalGuard::new(&mut stdout, true)?;
+
+    execute!(
+        stdout,
+        terminal::Clear(terminal::ClearType::All),
+        cursor::MoveTo(0, 0),
+        SetForegroundColor(Color::Cyan),
+        Print("Arma 3 Mod Manager Console - Config Can't Be Read"),
+        SetForegroundColor(Color::Reset),
+        cursor::MoveTo(0, 2),
+        Print(format!("The config at {} is not valid JSON:", path)),
+        cursor::MoveTo(0, 3),
+        SetForegroundColor(Color::Red),
+        Print(message),
+        SetForegroundColor(Color::Reset),
+    )?;
+
+    let mut options = Vec::new();
+    if has_backup {
+        options.push("  <B> Restore the backup from the last save");
+    }
+    options.push("  <R> Start over with a new config, the old one is kept as .corrupt");
+    options.push("  <Q> Quit to fix the file by hand");
+    for (i, option) in options.iter().enumerate() {
+        execute!(stdout, cursor::MoveTo(0, 5 + i as u16), Print(option))?;
+    }
+    stdout.flush()?;
+
+    loop {
+        if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+            match code {
+                KeyCode::Char('b') if has_backup => return Ok(ConfigRecovery::RestoreBackup),
+                KeyCode::Char('r') => return Ok(ConfigRecovery::Reset),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(ConfigRecovery::Quit),
+                _ => {}
+            }
+        }
+    }
+}
+
+pub struct Terminal<'a> {
+    mod_manager: &'a mut ModManager,
+    selected_index: usize,
+    /// Filters the mod list by name or identifier
+    search_query: String,
+    searching: bool,
+    /// Match the query as a subsequence and rank the mods by how well they match
+    fuzzy_search: bool,
+    /// Shown in a banner until the next key press
+    error_message: Option<String>,
+    /// Like `error_message`, for information that is not an error
+    notice: Option<String>,
+    /// The soloed mod and the selection before soloing it
+    solo: Option<(String, Vec<String>)>,
+    /// Position in the visible mods where the visual selection started
+    selection_anchor: Option<usize>,
+    /// The game launched from the manager, until it exits
+    game: Option<Child>,
+    /// Active preset and enabled mods before the last bulk change, restored with `u`
+    undo: Option<(String, Vec<String>)>,
+    /// Mods being scanned in the background, picked up by `finish_refresh`
+    refresh: Option<Receiver<RefreshUpdate>>,
+    /// How far the background refresh got, from 0 to 1
+    refresh_progress: f32,
+    /// When the mods were last scanned, in seconds since the Unix epoch
+    last_refreshed: i64,
+    /// Feedback on the last action and when it was shown, cleared after `STATUS_DURATION`
+    status: Option<(String, Instant)>,
+    /// Dependencies of newly enabled mods being fetched in the background,
+    /// picked up by `finish_dependency_checks`
+    #[cfg(feature = "network")]
+    dependency_checks: Vec<Receiver<DependencyCheck>>,
+}
+
+/// Messages from the background refresh to `finish_refresh`
+enum RefreshUpdate {
+    /// Folders scanned so far, out of the total
+    Progress(usize, usize),
+    Done(AppResult<Vec<Mod>>),
+}
+
+/// Sent by the background dependency check of a newly enabled mod
+#[cfg(feature = "network")]
+struct DependencyCheck {
+    mod_name: String,
+    result: AppResult<dependency_manager::DependencyGraph>,
+}
+
+/// Writes the fetched dependencies to disk. The cache only saves requests,
+/// so failing to write it is not worth reporting.
+#[cfg(feature = "network")]
+fn save_dependency_cache(cache: &dependency_manager::DependencyCache) {
+    let _ = cache.save();
+}
+
+/// Shown when the dependency graph was cut short at its depth or request limit
+#[cfg(feature = "network")]
+const TRUNCATED_NOTE: &str =
+    "Stopped following dependencies at the limit, more may be required than listed";
+
+/// Tells that dependencies may be missing from the graph, `None` when it is complete
+#[cfg(feature = "network")]
+fn incomplete_graph_note(graph: &dependency_manager::DependencyGraph) -> Option<String> {
+    if let Some((id, e)) = graph.failed.first() {
+        Some(format!(
+            "Could not check {} dependencies (e.g. {}: {}), more may be required",
+            graph.failed.len(),
+            id,
+            e
+        ))
+    } else if graph.truncated {
+        Some(TRUNCATED_NOTE.to_string())
+    } else {
+        None
+    }
+}
+
+/// How long a status message stays on the bottom row
+const STATUS_DURATION: Duration = Duration::from_secs(3);
+
+/// Width of progress bars between the brackets
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// A callback for long operations to report how many of their steps are done,
+/// drawing `label [=====>    ] 42%` over the row
+#[cfg(feature = "network")]
+fn progress_reporter<'a>(
+    stdout: &'a mut Stdout,
+    row: u16,
+    label: &'a str,
+) -> impl FnMut(usize, usize) + 'a {
+    move |done, total| {
+        let fraction = if total == 0 {
+            1.0
         } else {
-            self.main_loop(&mut stdout)?;
+            done as f32 / total as f32
+        };
+        // Drawing errors show up again on the next render
+        let _ = execute!(
+            stdout,
+            cursor::MoveTo(0, row),
+            terminal::Clear(terminal::ClearType::CurrentLine),
+            Print(format!(
+                "{} {}",
+                label,
+                utils::progress_bar(fraction, PROGRESS_BAR_WIDTH)
+            )),
+        );
+        let _ = stdout.flush();
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+impl<'a> Terminal<'a> {
+    pub fn new(mod_manager: &'a mut ModManager) -> Self {
+        let missing_mods: Vec<&str> = mod_manager
+            .missing_mods()
+            .iter()
+            .map(|m| m.identifier.as_str())
+            .collect();
+        let notice = (!missing_mods.is_empty()).then(|| {
+            format!(
+                "Enabled mods are no longer installed: {} - <DEL> to remove them",
+                missing_mods.join(", ")
+            )
+        });
+
+        Terminal {
+            mod_manager,
+            selected_index: 0,
+            search_query: String::new(),
+            searching: false,
+            fuzzy_search: false,
+            error_message: None,
+            notice,
+            solo: None,
+            selection_anchor: None,
+            game: None,
+            undo: None,
+            refresh: None,
+            refresh_progress: 0.0,
+            status: None,
+            last_refreshed: now(),
+            #[cfg(feature = "network")]
+            dependency_checks: Vec::new(),
         }
+    }
 
-        terminal::disable_raw_mode()?;
+    pub fn run(&mut self) -> AppResult<()> {
+        let mut stdout = io::stdout();
+        // Without the alternate screen the UI is rendered inline, keeping the scrollback intact
+        let alternate_screen = self.mod_manager.config.get_alternate_screen();
 
-        execute!(stdout, terminal::LeaveAlternateScreen)?;
-        execute!(stdout, cursor::RestorePosition)?;
-        execute!(stdout, crossterm::cursor::Show)?;
+        let _guard = TerminalGuard::new(&mut stdout, alternate_screen)?;
 
-        Ok(())
+        if !self.mod_manager.config.is_valid() && !self.run_setup_wizard(&mut stdout)? {
+            return Ok(());
+        }
+
+        self.main_loop(&mut stdout)
     }
 
-    fn run_setup_wizard(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+    /// Asks for the paths and executable name until they are valid.
+    /// Returns false when the setup was quit.
+    fn run_setup_wizard(&mut self, stdout: &mut Stdout) -> AppResult<bool> {
         // Try to auto-detect defaults
         let (default_workshop, default_game) = match super::utils::setup_steam_paths() {
             Ok((w, g)) => (w, g),
@@ -103,7 +495,23 @@ impl<'a> Terminal<'a> {
             game_path = default_game;
         }
 
+        let mut executable_name = self.mod_manager.config.get_executable_name().to_string();
+
         loop {
+            let workshop_valid = Path::new(&workshop_path).is_dir();
+            let game_valid = Path::new(&game_path).is_dir();
+            let executable_path =
+                launcher::get_executable_path(Path::new(&game_path), &executable_name);
+            let executable_valid = executable_path.is_file();
+
+            let status = |valid: bool| {
+                if valid {
+                    (Color::Green, "[OK]")
+                } else {
+                    (Color::Red, "[Not found]")
+                }
+            };
+
             self.clear_screen(stdout)?;
 
             execute!(
@@ -116,15 +524,53 @@ impl<'a> Terminal<'a> {
                 Print("It seems your configuration is invalid or missing."),
                 cursor::MoveTo(0, 3),
                 Print("Please verify your Steam paths below."),
-                cursor::MoveTo(0, 5),
-                Print(format!("1. Workshop Path: {}", workshop_path)),
-                cursor::MoveTo(0, 6),
-                Print(format!("2. Game Path:     {}", game_path)),
-                cursor::MoveTo(0, 8),
+            )?;
+
+            // Steam creates the workshop folder with the first workshop download
+            let workshop_status = if workshop_valid {
+                status(true)
+            } else {
+                (Color::Yellow, "[Not found, no workshop mods yet]")
+            };
+            let fields = [
+                ("1. Workshop Path: ", &workshop_path, workshop_status),
+                ("2. Game Path:     ", &game_path, status(game_valid)),
+                (
+                    "3. Executable:    ",
+                    &executable_name,
+                    status(executable_valid),
+                ),
+            ];
+            for (i, (label, value, (color, note))) in fields.into_iter().enumerate() {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, 5 + i as u16),
+                    Print(format!("{}{} ", label, value)),
+                    SetForegroundColor(color),
+                    Print(note),
+                    SetForegroundColor(Color::Reset),
+                )?;
+            }
+
+            if !executable_valid {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, 8),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print(format!("   {}", executable_path.display())),
+                    SetForegroundColor(Color::Reset),
+                )?;
+            }
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 10),
                 Print("Press <1> to edit Workshop Path"),
-                cursor::MoveTo(0, 9),
-                Print("Press <2> to edit Game Path"),
                 cursor::MoveTo(0, 11),
+                Print("Press <2> to edit Game Path"),
+                cursor::MoveTo(0, 12),
+                Print("Press <3> to edit the Executable Name"),
+                cursor::MoveTo(0, 14),
                 Print(format!(
                     "Press <ENTER> to Save ({}) and Continue",
                     match Config::get_save_path() {
@@ -132,47 +578,72 @@ impl<'a> Terminal<'a> {
                         Err(e) => format!("Error: {}", e),
                     }
                 )),
-                cursor::MoveTo(0, 12),
+                cursor::MoveTo(0, 15),
+                Print("Press <R> to re-detect Steam paths"),
+                cursor::MoveTo(0, 16),
                 Print("Press <ESC> or <Q> to Quit"),
             )?;
             stdout.flush()?;
 
             if event::poll(Duration::from_millis(500))? {
-                if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
                     match code {
                         KeyCode::Char('1') => {
-                            workshop_path = self.input_screen(
+                            if let Some(path) = self.input_screen(
                                 stdout,
                                 "Edit Workshop Path",
                                 "Enter Path:",
                                 &workshop_path,
-                            )?;
+                            )? {
+                                workshop_path = utils::normalize_path_input(&path);
+                            }
                         }
                         KeyCode::Char('2') => {
-                            game_path = self.input_screen(
+                            if let Some(path) = self.input_screen(
                                 stdout,
                                 "Edit Game Path",
                                 "Enter Path:",
                                 &game_path,
-                            )?;
+                            )? {
+                                game_path = utils::normalize_path_input(&path);
+                            }
+                        }
+                        KeyCode::Char('3') => {
+                            if let Some(name) = self.input_screen(
+                                stdout,
+                                "Edit Executable Name",
+                                "Enter Name:",
+                                &executable_name,
+                            )? {
+                                executable_name = utils::strip_input_quotes(&name).to_string();
+                            }
+                        }
+                        KeyCode::Char('r') => {
+                            if let Ok((w, g)) = super::utils::setup_steam_paths() {
+                                workshop_path = w;
+                                game_path = g;
+                            }
                         }
                         KeyCode::Enter => {
                             self.mod_manager
                                 .config
                                 .set_workshop_path(workshop_path.clone());
                             self.mod_manager.config.set_game_path(game_path.clone());
+                            self.mod_manager
+                                .config
+                                .set_executable_name(executable_name.clone());
 
-                            if self.mod_manager.config.is_valid() {
+                            if self.mod_manager.config.is_valid() && executable_valid {
                                 self.mod_manager.config.save()?;
                                 self.mod_manager.refresh_mods()?;
-                                break;
+                                return Ok(true);
                             } else {
                                 self.clear_screen(stdout)?;
                                 execute!(
                                     stdout,
                                     cursor::MoveTo(0, 0),
                                     SetForegroundColor(Color::Red),
-                                    Print("Error: Paths are invalid! check if directories exist."),
+                                    Print("Error: Paths are invalid! Check that the game directory and the executable exist."),
                                     SetForegroundColor(Color::Reset),
                                     cursor::MoveTo(0, 2),
                                     Print("Press any key to try again...")
@@ -180,22 +651,19 @@ impl<'a> Terminal<'a> {
                                 stdout.flush()?;
                                 loop {
                                     if event::poll(Duration::from_millis(500))? {
-                                        if let Event::Key(_) = event::read()? {
+                                        if let Some(Event::Key(_)) = read_event()? {
                                             break;
                                         }
                                     }
                                 }
                             }
                         }
-                        KeyCode::Esc | KeyCode::Char('q') => {
-                            return Ok(()); // Exit app essentially, or return to empty loop which exits
-                        }
+                        KeyCode::Esc | KeyCode::Char('q') => return Ok(false),
                         _ => {}
                     }
                 }
             }
         }
-        Ok(())
     }
 
     fn input_screen(
@@ -204,9 +672,20 @@ impl<'a> Terminal<'a> {
         title: &str,
         prompt: &str,
         initial_value: &str,
-    ) -> AppResult<String> {
-        let mut input_string = initial_value.to_string();
-        let mut current_pos = input_string.len() as u16;
+    ) -> AppResult<Option<String>> {
+        self.input_screen_with_error(stdout, title, prompt, initial_value, None)
+    }
+
+    /// Like `input_screen`, with an error about the previous input shown below the prompt
+    fn input_screen_with_error(
+        &self,
+        stdout: &mut Stdout,
+        title: &str,
+        prompt: &str,
+        initial_value: &str,
+        error: Option<&str>,
+    ) -> AppResult<Option<String>> {
+        let mut input = TextInput::new(initial_value);
 
         // Set up the terminal
         execute!(stdout, cursor::Show)?;
@@ -229,106 +708,210 @@ impl<'a> Terminal<'a> {
             Print("Press <ENTER> to confirm, <ESC> to cancel"),
         )?;
 
-        let prompt_left = 4;
         let prompt_top = 4;
-        let prompt_left_padding = prompt_left - 3;
+
+        if let Some(error) = error {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, prompt_top + 2),
+                SetForegroundColor(Color::Red),
+                Print(error),
+                SetForegroundColor(Color::Reset)
+            )?;
+        }
+
+        self.draw_text_input(stdout, prompt_top, prompt, &input)?;
+
+        let confirmed = loop {
+            if event::poll(Duration::from_millis(500))? {
+                match input.handle_event(event::read()?) {
+                    InputAction::Confirm => break true,
+                    InputAction::Cancel => break false,
+                    InputAction::Edited | InputAction::Moved => {
+                        self.draw_text_input(stdout, prompt_top, prompt, &input)?;
+                    }
+                    InputAction::Key(_) | InputAction::Ignored => {}
+                }
+            }
+        };
+        // Restore terminal state
+        execute!(stdout, cursor::Hide)?;
+        execute!(stdout, SetCursorStyle::DefaultUserShape)?;
+
+        Ok(confirmed.then(|| input.into_text()))
+    }
+
+    /// Draws a text input at the row after the prompt, and moves the cursor into it
+    fn draw_text_input(
+        &self,
+        stdout: &mut Stdout,
+        top: u16,
+        prompt: &str,
+        input: &TextInput,
+    ) -> AppResult<()> {
+        let left = 4;
+        let input_left = if prompt.is_empty() {
+            left
+        } else {
+            left + prompt.chars().count() as u16 + 1
+        };
 
         execute!(
             stdout,
+            cursor::MoveTo(0, top),
+            terminal::Clear(terminal::ClearType::CurrentLine),
             SetForegroundColor(Color::Red),
-            cursor::MoveTo(prompt_left_padding, prompt_top),
+            cursor::MoveTo(left - 3, top),
             Print(">"),
-            SetForegroundColor(Color::Reset)
+            SetForegroundColor(Color::Reset),
+            cursor::MoveTo(left, top),
+            Print(prompt),
+            cursor::MoveTo(input_left, top),
+            Print(input.text()),
+            cursor::MoveTo(input_left + input.cursor_column(), top),
         )?;
+        stdout.flush()?;
 
-        execute!(
-            stdout,
-            cursor::MoveTo(prompt_left, prompt_top),
-            Print(format!("{} ", prompt))
-        )?;
+        Ok(())
+    }
 
-        let input_start_col = prompt_left + prompt.len() as u16 + 1;
+    /// Lists all keybindings, the config location and the paths in use
+    fn help_screen(&self, stdout: &mut Stdout) -> AppResult<()> {
+        self.clear_screen(stdout)?;
 
-        // Initial render
         execute!(
             stdout,
-            cursor::MoveTo(input_start_col, prompt_top),
-            Print(&input_string)
+            cursor::MoveTo(0, 0),
+            SetForegroundColor(Color::Cyan),
+            Print(format!(
+                "Arma 3 Mod Manager Console ({}) - Help",
+                env!("CARGO_PKG_VERSION")
+            )),
+            SetForegroundColor(Color::Reset),
         )?;
 
+        let config = &self.mod_manager.config;
+        let paths = [
+            (
+                "Config file",
+                match Config::get_save_path() {
+                    Ok(path) => path.display().to_string(),
+                    Err(e) => format!("Error: {}", e),
+                },
+            ),
+            ("Game path", config.get_game_path().display().to_string()),
+            (
+                "Workshop path",
+                config.get_workshop_path().display().to_string(),
+            ),
+            (
+                "Custom mods paths",
+                match config.get_custom_mods_paths().as_slice() {
+                    [] => "(none)".to_string(),
+                    paths => paths
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                },
+            ),
+        ];
+
+        for (i, (label, path)) in paths.iter().enumerate() {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 2 + i as u16),
+                Print(format!("{:<18}{}", label, path)),
+            )?;
+        }
+
+        // Keybindings fill columns below the paths, as many as the terminal fits
+        let top = 3 + paths.len() as u16;
+        let (_, rows) = terminal::size()?;
+        let column_height = rows.saturating_sub(top + 2).max(1) as usize;
+        let column_width = 60;
+
+        for (i, (action, keybinding, _)) in KEYBINDINGS.iter().enumerate() {
+            let column = (i / column_height) as u16;
+            let row = (i % column_height) as u16;
+
+            execute!(
+                stdout,
+                cursor::MoveTo(column * column_width, top + row),
+                SetForegroundColor(Color::Cyan),
+                Print(format!("{:<25}{}", action, keybinding)),
+                SetForegroundColor(Color::Reset),
+            )?;
+        }
+
         execute!(
             stdout,
-            cursor::MoveTo(input_start_col + current_pos, prompt_top)
+            cursor::MoveTo(0, top + KEYBINDINGS.len().min(column_height) as u16 + 1),
+            Print("Press any key to return..."),
         )?;
+        stdout.flush()?;
 
         loop {
             if event::poll(Duration::from_millis(500))? {
-                if let Event::Key(KeyEvent { code, .. }) = event::read()? {
-                    match code {
-                        KeyCode::Esc => {
-                            // Restore terminal state before returning
-                            execute!(stdout, cursor::Hide)?;
-                            execute!(stdout, SetCursorStyle::DefaultUserShape)?;
-                            return Ok(initial_value.to_string());
-                        }
-                        KeyCode::Enter => {
-                            break;
-                        }
-                        KeyCode::Backspace => {
-                            if !input_string.is_empty() && current_pos > 0 {
-                                input_string.pop();
-                                current_pos -= 1;
-                            }
-                        }
-                        KeyCode::Char(c) => {
-                            input_string.push(c);
-                            current_pos += 1;
-                        }
-                        _ => {}
-                    }
-
-                    execute!(stdout, terminal::Clear(terminal::ClearType::CurrentLine))?;
+                if let Some(Event::Key(_)) = read_event()? {
+                    break;
+                }
+            }
+        }
 
-                    execute!(
-                        stdout,
-                        SetForegroundColor(Color::Red),
-                        cursor::MoveTo(prompt_left_padding, prompt_top),
-                        Print(">"),
-                        SetForegroundColor(Color::Reset)
-                    )?;
+        Ok(())
+    }
 
-                    execute!(
-                        stdout,
-                        cursor::MoveTo(prompt_left, prompt_top),
-                        Print(format!("{} ", prompt))
-                    )?;
+    fn message_screen(&self, stdout: &mut Stdout, color: Color, message: &str) -> AppResult<()> {
+        self.clear_screen(stdout)?;
 
-                    execute!(
-                        stdout,
-                        cursor::MoveTo(input_start_col, prompt_top),
-                        Print(&input_string)
-                    )?;
+        execute!(stdout, SetForegroundColor(color))?;
+        for (i, line) in message.lines().enumerate() {
+            execute!(stdout, cursor::MoveTo(0, i as u16), Print(line))?;
+        }
 
-                    // Move cursor to the new position
-                    execute!(
-                        stdout,
-                        cursor::MoveTo(input_start_col + current_pos, prompt_top)
-                    )?;
+        execute!(
+            stdout,
+            SetForegroundColor(Color::Reset),
+            cursor::MoveTo(0, message.lines().count() as u16 + 1),
+            Print("Press any key to return...")
+        )?;
+        stdout.flush()?;
 
-                    stdout.flush()?;
+        loop {
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(_)) = read_event()? {
+                    break;
                 }
             }
         }
-        // Restore terminal state
-        execute!(stdout, cursor::Hide)?;
-        execute!(stdout, SetCursorStyle::DefaultUserShape)?;
 
-        Ok(input_string)
+        Ok(())
     }
 
-    fn render(&self, stdout: &mut Stdout) -> AppResult<()> {
+    fn render(&mut self, stdout: &mut Stdout) -> AppResult<()> {
         self.clear_screen(stdout)?;
 
+        let (columns, rows) = terminal::size()?;
+        let Some(layout) = Layout::new(columns, rows) else {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print(format!(
+                    "Window too small, resize to at least {}x{}",
+                    MIN_COLUMNS, MIN_ROWS
+                )),
+                SetForegroundColor(Color::Reset),
+            )?;
+            stdout.flush()?;
+            return Ok(());
+        };
+        let page_size = match self.mod_manager.config.get_page_size() {
+            Some(max_page_size) => layout.page_size.min(max_page_size),
+            None => layout.page_size,
+        };
+        self.fit_page_size(page_size);
+
         let mut top_offset = 0;
 
         execute!(
@@ -339,6 +922,12 @@ impl<'a> Terminal<'a> {
                 "Arma 3 Mod Manager Console ({})",
                 env!("CARGO_PKG_VERSION")
             )),
+            SetForegroundColor(Color::Green),
+            Print(match &self.game {
+                Some(game) => format!("    Game running (pid {})", game.id()),
+                None => String::new(),
+            }),
+            SetForegroundColor(Color::Cyan),
             cursor::MoveTo(0, top_offset + 1),
             Print(format!(
                 "Config file: {}",
@@ -350,27 +939,91 @@ impl<'a> Terminal<'a> {
             SetForegroundColor(Color::Reset)
         )?;
 
+        execute!(
+            stdout,
+            cursor::MoveTo(0, top_offset + 2),
+            Print(self.type_counts_line())
+        )?;
+
         top_offset += 3;
 
-        let enabled_mods = self.mod_manager.loaded_mods.filter(|m| m.enabled).len();
-        let total_mods = self.mod_manager.loaded_mods.all_items().len();
-
-        let page_number = self.mod_manager.loaded_mods.current_page + 1;
-        let total_pages = self.mod_manager.loaded_mods.total_pages();
-
-        let preset_name = self.mod_manager.config.get_active_preset_name().to_string();
-
         execute!(
             stdout,
             cursor::MoveTo(0, top_offset),
-            Print(&format!(
-                "Mods: {:<2}/{:<2}    Preset: [{}]    Page: {:<2}/{:<2}",
-                enabled_mods, total_mods, preset_name, page_number, total_pages
-            )),
+            Print(self.header_line())
         )?;
 
+        if self.searching || !self.search_query.is_empty() {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print(format!("    {}{}", self.search_label(), self.search_query)),
+                SetForegroundColor(Color::Reset),
+            )?;
+        }
+
+        if self.mod_manager.config.get_favorites_only() {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print("    [Favorites only]"),
+                SetForegroundColor(Color::Reset),
+            )?;
+        }
+
+        let mod_filter = self.mod_manager.config.get_mod_filter();
+        if mod_filter != ModFilter::All {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print(format!("    [{}]", mod_filter.label())),
+                SetForegroundColor(Color::Reset),
+            )?;
+        }
+
+        if let Some(tag) = self.mod_manager.config.get_tag_filter() {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print(format!("    [Tag: {}]", tag)),
+                SetForegroundColor(Color::Reset),
+            )?;
+        }
+
+        if self.selection_anchor.is_some() {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print("    -- VISUAL --"),
+                SetForegroundColor(Color::Reset),
+            )?;
+        }
+
+        let missing_mods = self.mod_manager.missing_mods().len();
+        if missing_mods > 0 {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, top_offset + 1),
+                SetForegroundColor(Color::Red),
+                Print(format!("Missing mods: {} - <DEL> to purge", missing_mods)),
+                SetForegroundColor(Color::Reset),
+            )?;
+        }
+
         top_offset += 2;
 
+        // Missing mods are left out when launching
+        let load_order: Vec<&str> = self
+            .mod_manager
+            .enabled_mods()
+            .into_iter()
+            .filter(|m| !m.is_missing)
+            .map(|m| m.identifier.as_str())
+            .collect();
+
+        let visual_range = self.visual_range();
+        let toggled_mods = self.mod_manager.toggled_mods();
+
         for (i, m) in self
             .mod_manager
             .loaded_mods
@@ -380,116 +1033,297 @@ impl<'a> Terminal<'a> {
         {
             let mut str: String = String::new();
 
-            let cursor = if i == self.selected_index {
-                " > "
+            let position = self.mod_manager.loaded_mods.window_start() + i;
+            let (cursor, cursor_color) = if i == self.cursor_offset() {
+                (" > ", Color::Red)
+            } else if visual_range.as_ref().is_some_and(|r| r.contains(&position)) {
+                (" | ", Color::Yellow)
             } else {
-                "   "
+                ("   ", Color::Reset)
             };
 
             execute!(
                 stdout,
                 cursor::MoveTo(0, top_offset),
-                SetForegroundColor(Color::Red),
+                SetForegroundColor(cursor_color),
                 Print(cursor),
                 SetForegroundColor(Color::Reset)
             )?;
 
-            let mut color = Color::Grey;
+            let color = row_color(m);
 
+            // Toggled since the last save, which launching would change
+            let toggled = toggled_mods.contains(m.identifier.as_str());
             if m.enabled {
-                color = Color::White;
-                str += "[X]";
+                str += if toggled { "[+]" } else { "[X]" };
             } else {
-                str += "[ ]";
+                str += if toggled { "[-]" } else { "[ ]" };
             }
 
-            str += &format!(" {}", m.name);
+            if m.is_favorite {
+                str += " *";
+            }
 
-            str.truncate(36);
+            str += " ";
+            let name_start = str.chars().count();
+            str += &m.display_name();
 
-            execute!(
-                stdout,
-                cursor::MoveTo(3, top_offset),
-                SetForegroundColor(color),
-                Print(str),
-                SetForegroundColor(Color::Reset)
-            )?;
+            let str = utils::truncate_chars(&str, layout.name_width);
+
+            // Characters of the name matched by a fuzzy search, offset to the row
+            let highlighted: Vec<usize> = if self.fuzzy_search && !self.search_query.is_empty() {
+                utils::fuzzy_match(&self.search_query, &m.name)
+                    .map(|(_, positions)| positions)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|position| name_start + position)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            // Print runs of matched and unmatched characters in their color
+            let mut runs: Vec<(bool, String)> = Vec::new();
+            for (i, c) in str.chars().enumerate() {
+                let matched = highlighted.contains(&i) && c != '…';
+                match runs.last_mut() {
+                    Some((run_matched, run)) if *run_matched == matched => run.push(c),
+                    _ => runs.push((matched, c.to_string())),
+                }
+            }
+
+            execute!(stdout, cursor::MoveTo(3, top_offset))?;
+            for (matched, run) in runs {
+                execute!(
+                    stdout,
+                    SetForegroundColor(if matched { Color::Yellow } else { color }),
+                    Print(run)
+                )?;
+            }
+            execute!(stdout, SetForegroundColor(Color::Reset))?;
 
             if m.is_cdlc {
                 execute!(
                     stdout,
-                    cursor::MoveTo(41, top_offset),
-                    SetForegroundColor(Color::Blue),
+                    cursor::MoveTo(layout.tag_column, top_offset),
+                    // Red when the CDLC doesn't look owned
+                    SetForegroundColor(if m.is_unowned {
+                        Color::Red
+                    } else {
+                        Color::Blue
+                    }),
                     Print("CDLC"),
                     SetForegroundColor(Color::Reset)
                 )?;
+            } else if m.is_server_mod {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(layout.tag_column, top_offset),
+                    SetForegroundColor(Color::Magenta),
+                    Print("SRV"),
+                    SetForegroundColor(Color::Reset)
+                )?;
+            } else if m.is_missing {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(layout.tag_column, top_offset),
+                    SetForegroundColor(Color::Red),
+                    Print("MISSING"),
+                    SetForegroundColor(Color::Reset)
+                )?;
+            }
+
+            if let Some(position) = load_order.iter().position(|id| *id == m.identifier) {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(layout.order_column, top_offset),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print(format!("#{}", position + 1)),
+                    SetForegroundColor(Color::Reset)
+                )?;
             }
 
             top_offset += 1;
         }
 
         // Show pagination direction
-        if (page_number < total_pages) && (page_number > 1) {
+        let has_previous = self.mod_manager.loaded_mods.has_previous();
+        let has_next = self.mod_manager.loaded_mods.has_next();
+        if has_previous && has_next {
             execute!(
                 stdout,
                 cursor::MoveTo(0, top_offset),
-                Print(&format!("{}{:^38}{}", "<--", "", "-->")),
+                Print(&format!(
+                    "{}{:^width$}{}",
+                    "<--",
+                    "",
+                    "-->",
+                    width = layout.name_width + 2
+                )),
             )?;
-        } else if page_number < total_pages {
+        } else if has_next {
             execute!(
                 stdout,
                 cursor::MoveTo(0, top_offset),
-                Print(&format!("{}{:^38}{}", "   ", "", "-->")),
+                Print(&format!(
+                    "{}{:^width$}{}",
+                    "   ",
+                    "",
+                    "-->",
+                    width = layout.name_width + 2
+                )),
             )?;
-        } else if page_number > 1 {
+        } else if has_previous {
             execute!(
                 stdout,
                 cursor::MoveTo(0, top_offset),
-                Print(&format!("{}{:^38}{}", "<--", "", "   ")),
+                Print(&format!(
+                    "{}{:^width$}{}",
+                    "<--",
+                    "",
+                    "   ",
+                    width = layout.name_width + 2
+                )),
+            )?;
+        }
+
+        let selected_timestamp = self
+            .selected_mod_index()
+            .and_then(|index| self.mod_manager.loaded_mods.all_items()[index].timestamp);
+        if let Some(timestamp) = selected_timestamp {
+            execute!(
+                stdout,
+                cursor::MoveTo(3, top_offset + 1),
+                SetForegroundColor(Color::DarkGrey),
+                Print(format!("Updated {}", utils::format_age(timestamp, now()))),
+                SetForegroundColor(Color::Reset)
             )?;
         }
 
         top_offset = 2;
-        let info_left_offset = 50;
-        let info_text_padding = 25;
 
-        execute!(
-            stdout,
-            cursor::MoveTo(info_left_offset, top_offset),
-            Print(&format!(
-                "{:<padding$}{}",
-                "Action",
-                "Keybindings",
-                padding = info_text_padding
-            )),
-        )?;
+        // Left out on narrow terminals, the help screen lists the keybindings as well
+        if let Some(info_left_offset) = layout.panel_column {
+            execute!(
+                stdout,
+                cursor::MoveTo(info_left_offset, top_offset),
+                Print(&format!(
+                    "{:<padding$}{}",
+                    "Action",
+                    "Keybindings",
+                    padding = PANEL_PADDING
+                )),
+            )?;
 
-        let actions_keybindings = vec![
-            ("Navigation", "<WASD>, <HJKL> or <ARROW KEYS>"),
-            ("Toggle Selected Mod", "<SPACE>"),
-            ("Toggle All Mods", "<CTRL> + <SPACE>"),
-            ("Cycle Presets", "<TAB> / <SHIFT+TAB>"),
-            ("Manage Presets", "T"),
-            ("Check Dependencies", "C"),
-            ("Refresh Mods", "R"),
-            ("Set Custom Parameters", "F"),
-            ("Set Executable Name", "E"),
-            ("Save Config", "<ENTER>"),
-            ("Launch Game", "P"),
-        ];
+            let actions_keybindings: Vec<_> = KEYBINDINGS
+                .iter()
+                .filter(|(_, _, in_panel)| *in_panel)
+                .take(layout.panel_rows)
+                .collect();
+
+            for (i, (action, keybinding, _)) in actions_keybindings.iter().enumerate() {
+                let y_offset = top_offset + 2 + i as u16;
+
+                execute!(
+                    stdout,
+                    cursor::MoveTo(info_left_offset, y_offset),
+                    SetForegroundColor(Color::Cyan),
+                    Print(&format!(
+                        "{:<padding$}{}",
+                        action,
+                        keybinding,
+                        padding = PANEL_PADDING
+                    )),
+                    SetForegroundColor(Color::Reset),
+                )?;
+            }
 
-        for (i, (action, keybinding)) in actions_keybindings.iter().enumerate() {
-            let y_offset = top_offset + 2 + i as u16; // Adjust starting y offset as needed
+            // The full name of the selected mod, which may be cut off in the list
+            let detail_top = top_offset + 3 + actions_keybindings.len() as u16;
+            let detail_rows = rows.saturating_sub(detail_top + 3) as usize;
+            let selected_mod = self
+                .selected_mod_index()
+                .map(|index| &self.mod_manager.loaded_mods.all_items()[index]);
+            if let Some(m) = selected_mod.filter(|_| detail_rows > 0) {
+                let width = (columns - info_left_offset) as usize;
+                let mut name_lines = utils::wrap_text(&m.display_name(), width);
+                if name_lines.len() > detail_rows {
+                    name_lines.truncate(detail_rows);
+                    if let Some(last) = name_lines.last_mut() {
+                        *last = utils::truncate_chars(&format!("{}…", last), width);
+                    }
+                }
+
+                execute!(
+                    stdout,
+                    cursor::MoveTo(info_left_offset, detail_top),
+                    Print("Selected Mod"),
+                )?;
+                for (i, line) in name_lines.iter().enumerate() {
+                    execute!(
+                        stdout,
+                        cursor::MoveTo(info_left_offset, detail_top + 2 + i as u16),
+                        Print(line),
+                    )?;
+                }
+                if detail_rows > name_lines.len() {
+                    execute!(
+                        stdout,
+                        cursor::MoveTo(info_left_offset, detail_top + 2 + name_lines.len() as u16),
+                        SetForegroundColor(Color::DarkGrey),
+                        Print(utils::truncate_chars(&m.identifier, width)),
+                        SetForegroundColor(Color::Reset),
+                    )?;
+                }
+            }
+        }
 
+        if let Some(error_message) = &self.error_message {
             execute!(
                 stdout,
-                cursor::MoveTo(info_left_offset, y_offset),
-                SetForegroundColor(Color::Cyan),
-                Print(&format!(
-                    "{:<padding$}{}",
-                    action,
-                    keybinding,
-                    padding = info_text_padding
+                cursor::MoveTo(0, rows.saturating_sub(1)),
+                SetForegroundColor(Color::Red),
+                Print(format!(
+                    "Error: {} - press any key to dismiss",
+                    error_message
+                )),
+                SetForegroundColor(Color::Reset),
+            )?;
+        } else if let Some(notice) = &self.notice {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, rows.saturating_sub(1)),
+                SetForegroundColor(Color::Yellow),
+                Print(format!("{} - press any key to dismiss", notice)),
+                SetForegroundColor(Color::Reset),
+            )?;
+        } else if self.refresh.is_some() {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, rows.saturating_sub(1)),
+                SetForegroundColor(Color::Yellow),
+                Print(format!(
+                    "Refreshing {}",
+                    utils::progress_bar(self.refresh_progress, PROGRESS_BAR_WIDTH)
+                )),
+                SetForegroundColor(Color::Reset),
+            )?;
+        } else if let Some((status, _)) = &self.status {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, rows.saturating_sub(1)),
+                SetForegroundColor(Color::Green),
+                Print(status),
+                SetForegroundColor(Color::Reset),
+            )?;
+        } else {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, rows.saturating_sub(1)),
+                SetForegroundColor(Color::DarkGrey),
+                Print(format!(
+                    "Last refreshed: {} UTC",
+                    utils::format_clock(self.last_refreshed)
                 )),
                 SetForegroundColor(Color::Reset),
             )?;
@@ -500,616 +1334,3156 @@ impl<'a> Terminal<'a> {
         Ok(())
     }
 
-    fn main_loop(&mut self, stdout: &mut Stdout) -> AppResult<()> {
-        self.render(stdout)?;
-        stdout.flush()?;
+    /// Row of the cursor among the shown mods.
+    /// When scrolling `selected_index` is the position in the whole (filtered) list.
+    fn cursor_offset(&self) -> usize {
+        let loaded_mods = &self.mod_manager.loaded_mods;
+        if loaded_mods.scrolling {
+            self.selected_index
+                .saturating_sub(loaded_mods.window_start())
+        } else {
+            self.selected_index
+        }
+    }
 
-        loop {
-            if poll(Duration::from_millis(1000))? {
-                match event::read()? {
-                    Event::Key(event) => match event.code {
-                        KeyCode::Char('w') | KeyCode::Char('k') | KeyCode::Up => {
-                            if self.selected_index > 0 {
-                                self.selected_index -= 1;
-                            }
-                        }
-                        KeyCode::Char('s') | KeyCode::Char('j') | KeyCode::Down => {
-                            let length = self.mod_manager.loaded_mods.current_page_items().len();
+    /// Index in `all_items` of the mod under the cursor
+    fn selected_mod_index(&self) -> Option<usize> {
+        self.mod_manager
+            .loaded_mods
+            .global_index(self.cursor_offset())
+    }
 
-                            if self.selected_index < length - 1 {
-                                self.selected_index += 1;
-                            }
-                        }
+    /// Position of the cursor in the visible mods
+    fn selected_position(&self) -> usize {
+        self.mod_manager.loaded_mods.window_start() + self.cursor_offset()
+    }
 
-                        KeyCode::Char('a') | KeyCode::Char('h') | KeyCode::Left => {
-                            self.mod_manager.loaded_mods.prev_page();
-                            self.selected_index = 0;
-                        }
+    /// Positions in the visible mods between the visual selection anchor and the cursor
+    fn visual_range(&self) -> Option<RangeInclusive<usize>> {
+        let anchor = self.selection_anchor?;
+        let position = self.selected_position();
+        Some(anchor.min(position)..=anchor.max(position))
+    }
 
-                        KeyCode::Char('d') | KeyCode::Char('l') | KeyCode::Right => {
-                            self.mod_manager.loaded_mods.next_page();
-                            self.selected_index = 0;
-                        }
+    /// Enables all mods in the visual selection, or disables them when all are enabled
+    fn toggle_visual_range(&mut self) {
+        let Some(range) = self.visual_range() else {
+            return;
+        };
+        self.selection_anchor = None;
 
-                        KeyCode::Char(' ') if event.modifiers == KeyModifiers::CONTROL => {
-                            let value = !self
-                                .mod_manager
-                                .loaded_mods
-                                .all_items()
-                                .iter()
-                                .all(|m| m.enabled);
+        let loaded_mods = &mut self.mod_manager.loaded_mods;
+        let indices: Vec<usize> = range
+            .filter_map(|position| loaded_mods.visible_index(position))
+            .collect();
 
-                            self.mod_manager
-                                .loaded_mods
-                                .all_items_mut()
-                                .iter_mut()
-                                .for_each(|m| m.enabled = value);
-                        }
+        let value = !indices.iter().all(|&i| loaded_mods.all_items()[i].enabled);
+        for i in indices {
+            loaded_mods.all_items_mut()[i].enabled = value;
+        }
+    }
 
-                        KeyCode::Char(' ') => {
-                            let current_page = self.mod_manager.loaded_mods.current_page;
-                            let page_size = self.mod_manager.loaded_mods.page_size;
-                            let index = self.selected_index + (current_page * page_size);
+    /// Moves the cursor by `delta` mods, scrolling the list along with it
+    fn scroll_selection(&mut self, delta: isize) {
+        let last = self.mod_manager.loaded_mods.visible_len().saturating_sub(1);
+        self.selected_index = self.selected_index.saturating_add_signed(delta).min(last);
+        self.mod_manager.loaded_mods.scroll_to(self.selected_index);
+    }
 
-                            let selected_mod =
-                                &mut self.mod_manager.loaded_mods.all_items_mut()[index];
-                            selected_mod.enabled = !selected_mod.enabled;
-                        }
+    /// Moves a page down or up, keeping the cursor on the same row when the new page is
+    /// long enough
+    fn move_page(&mut self, forward: bool) {
+        let loaded_mods = &mut self.mod_manager.loaded_mods;
+        if loaded_mods.scrolling {
+            let page_size = loaded_mods.page_size as isize;
+            self.selected_index = loaded_mods.scroll_by(
+                self.selected_index,
+                if forward { page_size } else { -page_size },
+            );
+            return;
+        }
 
-                        KeyCode::Tab => {
-                            // Save current selection to active preset
-                            let enabled_mods =
-                                self.mod_manager.loaded_mods.filter(|m| m.enabled);
-                            self.mod_manager.config.update_mods(
-                                enabled_mods.iter().map(|m| m.identifier.clone()).collect(),
-                            );
-                            // Cycle to next preset
-                            let names = self.mod_manager.config.get_preset_names();
-                            if let Some(idx) = names
-                                .iter()
-                                .position(|n| n == self.mod_manager.config.get_active_preset_name())
-                            {
-                                let next = (idx + 1) % names.len();
-                                self.mod_manager.switch_preset(&names[next]);
-                            }
-                            self.selected_index = 0;
-                            self.mod_manager.loaded_mods.current_page = 0;
-                        }
-                        KeyCode::BackTab => {
-                            // Save current selection to active preset
-                            let enabled_mods =
-                                self.mod_manager.loaded_mods.filter(|m| m.enabled);
-                            self.mod_manager.config.update_mods(
-                                enabled_mods.iter().map(|m| m.identifier.clone()).collect(),
-                            );
-                            // Cycle to previous preset
-                            let names = self.mod_manager.config.get_preset_names();
-                            if let Some(idx) = names
-                                .iter()
-                                .position(|n| n == self.mod_manager.config.get_active_preset_name())
-                            {
-                                let prev = if idx == 0 { names.len() - 1 } else { idx - 1 };
-                                self.mod_manager.switch_preset(&names[prev]);
-                            }
-                            self.selected_index = 0;
-                            self.mod_manager.loaded_mods.current_page = 0;
-                        }
-                        KeyCode::Char('t') => {
-                            self.preset_manager_screen(stdout)?;
-                        }
-                        KeyCode::Char('r') => {
-                            self.mod_manager.refresh_mods()?;
-                        }
-                        KeyCode::Char('c') => {
-                            self.check_dependencies_screen(stdout)?;
-                        }
-                        KeyCode::Char('f') => {
-                            self.set_custom_parameters_screen(stdout)?;
-                        }
-                        KeyCode::Char('e') => {
-                            self.set_executable_name_screen(stdout)?;
-                        }
-                        KeyCode::Char('p') => {
-                            self.start_game()?;
-                        }
+        if forward {
+            loaded_mods.next_page();
+        } else {
+            loaded_mods.prev_page();
+        }
+        let length = loaded_mods.current_page_items().len();
+        self.selected_index = self.selected_index.min(length.saturating_sub(1));
+    }
 
-                        KeyCode::Enter => {
-                            let enabled_mods = self.mod_manager.loaded_mods.filter(|m| m.enabled);
-                            self.mod_manager.config.update_mods(
-                                enabled_mods.iter().map(|m| m.identifier.clone()).collect(),
-                            );
-                            self.mod_manager.config.save()?;
-                        }
+    /// Moves the cursor to the mod, when it is visible
+    fn select_mod(&mut self, identifier: &str) {
+        if let Some(index) = self
+            .mod_manager
+            .loaded_mods
+            .position(|m| m.identifier == identifier)
+        {
+            self.select_index(index);
+        }
+    }
 
-                        KeyCode::Esc | KeyCode::Char('q') => break,
+    /// Moves the cursor to the mod at the index in `all_items`, when it is visible
+    fn select_index(&mut self, index: usize) {
+        let loaded_mods = &mut self.mod_manager.loaded_mods;
+        let Some(offset) = loaded_mods.goto_item(index) else {
+            return;
+        };
 
-                        _ => continue,
-                    },
+        self.selected_index = if loaded_mods.scrolling {
+            loaded_mods.window_start() + offset
+        } else {
+            offset
+        };
+    }
 
-                    _ => continue,
-                }
-                self.render(stdout)?;
-                stdout.flush()?;
-            }
+    /// Resizes the pages to fit the terminal, keeping the selected mod under the cursor
+    fn fit_page_size(&mut self, page_size: usize) {
+        if self.mod_manager.loaded_mods.page_size == page_size {
+            return;
         }
 
-        Ok(())
-    }
+        let selected = self.selected_mod_index();
+        self.mod_manager.loaded_mods.set_page_size(page_size);
 
-    fn start_game(&mut self) -> AppResult<()> {
-        let enabled_mods = self.mod_manager.loaded_mods.filter(|m| m.enabled);
-        let game_path = self.mod_manager.config.get_game_path();
-        let workshop_path = self.mod_manager.config.get_workshop_path();
-        let custom_mods_path = self.mod_manager.config.get_custom_mods_path();
+        match selected {
+            Some(index) => self.select_index(index),
+            None => self.selected_index = 0,
+        }
+    }
 
-        let executable_name = self.mod_manager.config.get_executable_name();
-        let executable_path = Self::get_executable_path(game_path, executable_name);
-        let executable_path_str = executable_path.to_string_lossy().to_string();
+    /// Shows a message on the bottom row for a few seconds, as feedback on an action
+    fn set_status(&mut self, message: String) {
+        self.status = Some((message, Instant::now()));
+    }
 
-        if !executable_path.exists() {
-            return Err(AppError::InvalidPath(executable_path_str.to_owned()));
+    /// Whether a refresh or a dependency check is running in the background
+    fn has_background_work(&self) -> bool {
+        #[cfg(feature = "network")]
+        if !self.dependency_checks.is_empty() {
+            return true;
         }
+        self.refresh.is_some()
+    }
 
-        let mut command = Command::new(&executable_path_str);
-        command.current_dir(game_path);
+    /// Scans the mod folders on a background thread, keeping the UI responsive
+    fn start_refresh(&mut self) {
+        if self.refresh.is_some() {
+            return;
+        }
 
-        // Remove existing symlinks from the game directory
-        super::file_handler::remove_dir_symlinks(game_path)?;
+        let config = self.mod_manager.config.clone();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // Sending fails when the manager quit before the scan finished
+            let result = ModManager::scan_installed_mods(&config, |done, total| {
+                let _ = sender.send(RefreshUpdate::Progress(done, total));
+            });
+            let _ = sender.send(RefreshUpdate::Done(result));
+        });
+        self.refresh = Some(receiver);
+        self.refresh_progress = 0.0;
+    }
 
-        if !enabled_mods.is_empty() {
-            // Exclude CDLCS when creating sym links since they already are in the game folder
-            // only for workshop + custom mods
-            let mod_paths: Vec<_> = enabled_mods
-                .iter()
-                .filter_map(|m| {
-                    if m.is_cdlc {
-                        None
-                    } else if m.is_custom {
-                        custom_mods_path.map(|cmp| m.get_path(cmp))
-                    } else {
-                        Some(m.get_path(workshop_path))
-                    }
-                })
-                .collect();
+    /// Shows the scanned mods once the background refresh is done.
+    /// Returns whether the mod list or the progress changed.
+    fn finish_refresh(&mut self) -> AppResult<bool> {
+        let Some(receiver) = &self.refresh else {
+            return Ok(false);
+        };
 
-            super::file_handler::create_sym_links(game_path, mod_paths)?;
+        let mut progressed = false;
+        let result = loop {
+            match receiver.try_recv() {
+                Ok(RefreshUpdate::Progress(done, total)) => {
+                    self.refresh_progress = done as f32 / total.max(1) as f32;
+                    progressed = true;
+                }
+                Ok(RefreshUpdate::Done(result)) => break result,
+                Err(TryRecvError::Empty) => return Ok(progressed),
+                // The scan panicked, which was already reported
+                Err(TryRecvError::Disconnected) => {
+                    self.refresh = None;
+                    return Ok(true);
+                }
+            }
+        };
+        self.refresh = None;
 
-            // Save the enabled mods so it loads next time
-            self.mod_manager
-                .config
-                .update_mods(enabled_mods.iter().map(|m| m.identifier.clone()).collect());
-            self.mod_manager.config.save()?;
+        let selected = self.selected_mod_index().map(|i| {
+            self.mod_manager.loaded_mods.all_items()[i]
+                .identifier
+                .clone()
+        });
 
-            // Build args
-            let default_args = self.mod_manager.config.get_default_args();
-            if !default_args.is_empty() {
-                command.arg(default_args);
-            }
+        self.mod_manager.set_installed_mods(result?);
+        self.apply_search();
+        self.last_refreshed = now();
 
-            let mod_list = enabled_mods
-                .iter()
-                .map(|m| m.identifier.as_str())
-                .collect::<Vec<_>>()
-                .join(";");
+        let installed = self
+            .mod_manager
+            .loaded_mods
+            .all_items()
+            .iter()
+            .filter(|m| !m.is_missing)
+            .count();
+        self.set_status(format!("Refreshed {} mods", installed));
 
-            command.arg(format!("-mod={}", mod_list));
+        // Back on the same mod, when it is still installed
+        if let Some(identifier) = selected {
+            self.select_mod(&identifier);
         }
 
-        #[cfg(target_os = "macos")]
-        {
-            if let Some(overlay_path) = super::utils::get_steam_overlay_path() {
-                command.env("DYLD_INSERT_LIBRARIES", overlay_path);
-                command.env("DYLD_FORCE_FLAT_NAMESPACE", "1");
+        Ok(true)
+    }
+
+    /// Opens the folder or the workshop page of the selected mod
+    fn open_selected(&mut self, workshop_page: bool) -> AppResult<()> {
+        let Some(index) = self.selected_mod_index() else {
+            return Ok(());
+        };
+        let m = &self.mod_manager.loaded_mods.all_items()[index];
+
+        let target = if workshop_page {
+            m.workshop_url().map(OsString::from)
+        } else {
+            self.mod_manager.mod_dir(m).map(OsString::from)
+        };
+
+        match target {
+            Some(target) => utils::open_with_default_app(&target)?,
+            None if workshop_page => {
+                self.notice = Some(format!("{} has no workshop page", m.name));
+            }
+            None => self.notice = Some(format!("{} is not installed", m.name)),
+        }
+
+        Ok(())
+    }
+
+    /// Moves the cursor to the first mod of the current page after jumping to it
+    fn select_page_start(&mut self) {
+        let loaded_mods = &self.mod_manager.loaded_mods;
+        self.selected_index = 0;
+
+        if loaded_mods.scrolling {
+            let position = loaded_mods.current_page * loaded_mods.page_size;
+            self.scroll_selection(position as isize);
+        }
+    }
+
+    /// Asks for a page number and jumps to it
+    fn goto_page_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let total_pages = self.mod_manager.loaded_mods.total_pages().max(1);
+        let prompt = format!("Page (1-{}):", total_pages);
+
+        let Some(input) = self.input_screen(stdout, "Go to Page", &prompt, "")? else {
+            return Ok(());
+        };
+
+        let page: usize = input
+            .trim()
+            .parse()
+            .map_err(|_| AppError::InvalidPageNumber(input.clone()))?;
+
+        self.mod_manager
+            .loaded_mods
+            .goto_page(page.saturating_sub(1));
+        self.select_page_start();
+        Ok(())
+    }
+
+    /// Remembers the active preset and enabled mods before a bulk change
+    fn save_undo(&mut self) {
+        let preset = self.mod_manager.config.get_active_preset_name().to_string();
+        self.undo = Some((preset, self.mod_manager.enabled_identifiers()));
+    }
+
+    /// Restores the preset and enabled mods from before the last bulk change
+    fn undo(&mut self) {
+        let Some((preset, enabled)) = self.undo.take() else {
+            self.notice = Some("Nothing to undo".to_string());
+            return;
+        };
+
+        if preset != self.mod_manager.config.get_active_preset_name() {
+            self.mod_manager.switch_preset(&preset);
+            self.apply_search();
+        }
+
+        for m in self.mod_manager.loaded_mods.all_items_mut() {
+            m.enabled = enabled.contains(&m.identifier);
+        }
+
+        // Purged missing mods are listed again, to be saved back to the preset
+        let listed: Vec<String> = self
+            .mod_manager
+            .loaded_mods
+            .all_items()
+            .iter()
+            .map(|m| m.identifier.clone())
+            .collect();
+        let purged: Vec<Mod> = enabled
+            .into_iter()
+            .filter(|id| !listed.contains(id))
+            .map(Mod::missing)
+            .collect();
+        self.mod_manager.loaded_mods.extend(purged);
+    }
+
+    /// Enables only the mod at the index, or restores the previous selection
+    /// when it is already soloed
+    fn toggle_solo(&mut self, index: usize) {
+        let identifier = self.mod_manager.loaded_mods.all_items()[index]
+            .identifier
+            .clone();
+
+        let (enabled, solo) = match self.solo.take() {
+            Some((soloed, previous)) if soloed == identifier => (previous, None),
+            // Soloing another mod keeps the selection from before soloing
+            Some((_, previous)) => (vec![identifier.clone()], Some((identifier, previous))),
+            None => {
+                let previous = self.mod_manager.enabled_identifiers();
+                (vec![identifier.clone()], Some((identifier, previous)))
+            }
+        };
+
+        for m in self.mod_manager.loaded_mods.all_items_mut() {
+            m.enabled = enabled.contains(&m.identifier);
+        }
+        self.solo = solo;
+    }
+
+    /// Filters the mod list by the search query, the tag and the filter of the active preset,
+    /// resetting the cursor
+    fn apply_search(&mut self) {
+        let query = self.search_query.to_lowercase();
+        let favorites_only = self.mod_manager.config.get_favorites_only();
+        let mod_filter = self.mod_manager.config.get_mod_filter();
+        let tagged_mods = self
+            .mod_manager
+            .config
+            .get_tag_filter()
+            .map(|tag| self.mod_manager.config.get_tagged_mods(tag).to_vec());
+
+        if query.is_empty()
+            && !favorites_only
+            && mod_filter == ModFilter::All
+            && tagged_mods.is_none()
+        {
+            self.mod_manager.loaded_mods.clear_filter();
+        } else {
+            let filtered = |m: &Mod| {
+                (m.is_favorite || !favorites_only)
+                    && mod_filter.matches(m.enabled)
+                    && tagged_mods
+                        .as_ref()
+                        .is_none_or(|ids| ids.contains(&m.identifier))
+            };
+
+            if self.fuzzy_search && !query.is_empty() {
+                self.mod_manager.loaded_mods.set_ranked_filter(|m| {
+                    if !filtered(m) {
+                        return None;
+                    }
+                    let name = utils::fuzzy_match(&query, &m.name).map(|(score, _)| score);
+                    let identifier =
+                        utils::fuzzy_match(&query, &m.identifier).map(|(score, _)| score);
+                    name.max(identifier)
+                });
+            } else {
+                self.mod_manager.loaded_mods.set_filter(|m| {
+                    filtered(m)
+                        && (m.name.to_lowercase().contains(&query)
+                            || m.identifier.to_lowercase().contains(&query))
+                });
+            }
+        }
+
+        self.selected_index = 0;
+        self.selection_anchor = None;
+    }
+
+    fn search_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        self.searching = true;
+        let mut input = TextInput::new(&self.search_query);
+
+        execute!(stdout, cursor::Show)?;
+        execute!(stdout, SetCursorStyle::BlinkingUnderScore)?;
+
+        loop {
+            self.render(stdout)?;
+
+            // Place the cursor in the query in the header
+            let cursor_column = self.header_line().chars().count()
+                + "    ".len()
+                + self.search_label().len()
+                + input.cursor_column() as usize;
+            execute!(stdout, cursor::MoveTo(cursor_column as u16, 3))?;
+            stdout.flush()?;
+
+            if event::poll(Duration::from_millis(500))? {
+                match input.handle_event(event::read()?) {
+                    InputAction::Cancel => {
+                        self.search_query.clear();
+                        self.apply_search();
+                        break;
+                    }
+                    InputAction::Confirm => break,
+                    InputAction::Edited => {
+                        self.search_query = input.text().to_string();
+                        self.apply_search();
+                    }
+                    InputAction::Key(KeyEvent {
+                        code: KeyCode::Tab, ..
+                    }) => {
+                        self.fuzzy_search = !self.fuzzy_search;
+                        self.apply_search();
+                    }
+                    InputAction::Moved | InputAction::Key(_) | InputAction::Ignored => {}
+                }
+            }
+        }
+
+        self.searching = false;
+
+        // Restore terminal state
+        execute!(stdout, cursor::Hide)?;
+        execute!(stdout, SetCursorStyle::DefaultUserShape)?;
+
+        Ok(())
+    }
+
+    fn search_label(&self) -> &'static str {
+        if self.fuzzy_search {
+            "Search (fuzzy): "
+        } else {
+            "Search: "
+        }
+    }
+
+    fn header_line(&self) -> String {
+        let enabled_mods = self.mod_manager.enabled_mods();
+        let enabled_size: u64 = enabled_mods.iter().map(|m| m.disk_size).sum();
+        let total_mods = self.mod_manager.loaded_mods.all_items().len();
+
+        let preset_name = self.mod_manager.config.get_active_preset_name();
+
+        let loaded_mods = &self.mod_manager.loaded_mods;
+        let position = if loaded_mods.scrolling {
+            let selected = (self.selected_index + 1).min(loaded_mods.visible_len());
+            format!("Mod: {:<2}/{:<2}", selected, loaded_mods.visible_len())
+        } else {
+            format!(
+                "Page: {:<2}/{:<2}",
+                loaded_mods.current_page + 1,
+                loaded_mods.total_pages()
+            )
+        };
+
+        let sort_order = self.mod_manager.config.get_sort_order().label();
+
+        format!(
+            "Mods: {:<2}/{:<2} ({})    Preset: [{}]    {}    Sort: {}",
+            enabled_mods.len(),
+            total_mods,
+            utils::format_size(enabled_size),
+            preset_name,
+            position,
+            sort_order
+        )
+    }
+
+    /// Enabled and total mods of each type
+    fn type_counts_line(&self) -> String {
+        let mut counts = [(0, 0); 3];
+        for m in self.mod_manager.loaded_mods.all_items() {
+            let kind = if m.is_cdlc {
+                2
+            } else if m.is_custom {
+                1
+            } else {
+                0
+            };
+            counts[kind].0 += m.enabled as usize;
+            counts[kind].1 += 1;
+        }
+
+        let [workshop, custom, cdlc] = counts;
+        let line = format!(
+            "Workshop {}/{}    Custom {}/{}    CDLC {}/{}",
+            workshop.0, workshop.1, custom.0, custom.1, cdlc.0, cdlc.1
+        );
+
+        // Explains why dependencies aren't checked
+        #[cfg(feature = "network")]
+        if self.mod_manager.config.get_offline() {
+            return format!("{}    Offline", line);
+        }
+        line
+    }
+
+    fn main_loop(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        // Takes the place of the missing mods notice, since it explains them
+        if !self.mod_manager.config.has_workshop_path() {
+            self.notice = Some(format!(
+                "Workshop folder not found, press <CTRL> + W to set it: {}",
+                self.mod_manager.config.get_workshop_path().display()
+            ));
+        }
+        self.notice_case_corrections();
+        if self.notice.is_none() {
+            let stale = launcher::find_stale_linked_mods(
+                &self.mod_manager.config,
+                &self.mod_manager.enabled_mods(),
+            );
+            if !stale.is_empty() {
+                self.notice = Some(format!(
+                    "{} mods from an earlier launch are still linked into the game directory - <CTRL> + L to remove them",
+                    stale.len()
+                ));
+            }
+        }
+        self.apply_search();
+        self.restore_position();
+        self.render(stdout)?;
+        stdout.flush()?;
+
+        loop {
+            // Check on background work more often than on the game
+            let timeout = if self.has_background_work() {
+                100
+            } else {
+                1000
+            };
+
+            if poll(Duration::from_millis(timeout))? {
+                let event = match read_event()? {
+                    Some(Event::Key(event)) => event,
+                    Some(Event::Resize(..)) => {
+                        self.render(stdout)?;
+                        continue;
+                    }
+                    _ => continue,
+                };
+
+                // Any key dismisses the error banner
+                self.error_message = None;
+                self.notice = None;
+
+                match self.handle_key(stdout, event) {
+                    Ok(true) => {}
+                    Ok(false) => break,
+                    Err(e) => self.error_message = Some(e.to_string()),
+                }
+
+                self.render(stdout)?;
+                stdout.flush()?;
+            } else if self.game.is_some() && self.game_pid().is_none() {
+                // The game exited, so the status is out of date
+                self.render(stdout)?;
+                stdout.flush()?;
+            }
+
+            let refreshed = self.finish_refresh().unwrap_or_else(|e| {
+                self.error_message = Some(e.to_string());
+                true
+            });
+            let status_expired = self
+                .status
+                .take_if(|(_, shown)| shown.elapsed() >= STATUS_DURATION)
+                .is_some();
+            #[cfg(feature = "network")]
+            let checked = self.finish_dependency_checks();
+            #[cfg(not(feature = "network"))]
+            let checked = false;
+            if refreshed || checked || status_expired {
+                self.render(stdout)?;
+                stdout.flush()?;
+            }
+        }
+
+        self.save_position();
+        Ok(())
+    }
+
+    /// Tells about identifiers in the presets that were corrected to the folder name
+    fn notice_case_corrections(&mut self) {
+        let corrections = self.mod_manager.take_case_corrections();
+        if corrections.is_empty() {
+            return;
+        }
+
+        let renamed: Vec<String> = corrections
+            .iter()
+            .map(|(old, new)| format!("{} -> {}", old, new))
+            .collect();
+        let correction_notice = format!(
+            "Corrected mods to match their folders, <ENTER> to save: {}",
+            renamed.join(", ")
+        );
+        // Added to the notice already shown rather than hiding it
+        self.notice = Some(match self.notice.take() {
+            Some(notice) => format!("{} - {}", notice, correction_notice),
+            None => correction_notice,
+        });
+    }
+
+    /// Back on the mod the manager was closed on.
+    /// Falls back to the page when the mod is gone, clamped to the pages there are now.
+    fn restore_position(&mut self) {
+        let (page, selected) = self.mod_manager.config.get_last_position();
+        let index = selected.and_then(|identifier| {
+            self.mod_manager
+                .loaded_mods
+                .position(|m| m.identifier == identifier)
+        });
+
+        match index {
+            Some(index) => self.select_index(index),
+            None => {
+                self.mod_manager.loaded_mods.goto_page(page);
+                self.select_page_start();
+            }
+        }
+    }
+
+    /// Remembers the page and the mod under the cursor for `restore_position`.
+    /// Only the position is written to the saved config, so declined changes stay unsaved.
+    /// Nothing is written when the position is unchanged, and quitting never fails on it.
+    fn save_position(&mut self) {
+        let page = self.selected_position() / self.mod_manager.loaded_mods.page_size;
+        let selected = self.selected_mod_index().map(|i| {
+            self.mod_manager.loaded_mods.all_items()[i]
+                .identifier
+                .clone()
+        });
+
+        if self.mod_manager.config.get_last_position() == (page, selected.as_deref()) {
+            return;
+        }
+        self.mod_manager
+            .config
+            .set_last_position(page, selected.clone());
+        if let Ok(mut saved) = Config::read() {
+            saved.set_last_position(page, selected);
+            let _ = saved.save();
+        }
+    }
+
+    /// Handle a key press on the main screen.
+    /// Returns false when the manager should quit.
+    fn handle_key(&mut self, stdout: &mut Stdout, event: KeyEvent) -> AppResult<bool> {
+        match event.code {
+            KeyCode::Char('g') if event.modifiers == KeyModifiers::CONTROL => {
+                self.set_path_screen(stdout, true)?;
+            }
+            KeyCode::Char('w') if event.modifiers == KeyModifiers::CONTROL => {
+                self.set_path_screen(stdout, false)?;
+            }
+            KeyCode::Char('f') if event.modifiers == KeyModifiers::CONTROL => {
+                self.custom_folders_screen(stdout)?;
+            }
+            KeyCode::Char('d') if event.modifiers == KeyModifiers::CONTROL => {
+                self.move_page(true);
+            }
+            KeyCode::Char('u') if event.modifiers == KeyModifiers::CONTROL => {
+                self.move_page(false);
+            }
+            KeyCode::PageDown => self.move_page(true),
+            KeyCode::PageUp => self.move_page(false),
+            #[cfg(feature = "network")]
+            KeyCode::Char('t') if event.modifiers == KeyModifiers::CONTROL => {
+                self.dependency_tree_screen(stdout)?;
+            }
+            KeyCode::Char('o') if event.modifiers == KeyModifiers::CONTROL => {
+                self.open_selected(false)?;
+            }
+            KeyCode::Char('b') if event.modifiers == KeyModifiers::CONTROL => {
+                self.open_selected(true)?;
+            }
+            KeyCode::Char('e') if event.modifiers == KeyModifiers::CONTROL => {
+                let use_battleye = !self.mod_manager.config.get_use_battleye();
+                self.mod_manager.config.set_use_battleye(use_battleye);
+                self.mod_manager.config.save()?;
+                self.set_status(format!(
+                    "BattlEye {}",
+                    if use_battleye { "on" } else { "off" }
+                ));
+            }
+            KeyCode::Char('l') if event.modifiers == KeyModifiers::CONTROL => {
+                self.ensure_game_stopped()?;
+                let removed = launcher::remove_linked_mods(&self.mod_manager.config)?;
+                self.notice = Some(format!(
+                    "Removed {} linked mods from the game directory",
+                    removed
+                ));
+            }
+            KeyCode::Char('w') | KeyCode::Char('k') | KeyCode::Up => {
+                if self.mod_manager.loaded_mods.scrolling {
+                    self.scroll_selection(-1);
+                } else if self.selected_index > 0 {
+                    self.selected_index -= 1;
+                }
+            }
+            KeyCode::Char('s') | KeyCode::Char('j') | KeyCode::Down => {
+                if self.mod_manager.loaded_mods.scrolling {
+                    self.scroll_selection(1);
+                } else {
+                    let length = self.mod_manager.loaded_mods.current_page_items().len();
+
+                    if self.selected_index + 1 < length {
+                        self.selected_index += 1;
+                    }
+                }
+            }
+
+            KeyCode::Char('a') | KeyCode::Char('h') | KeyCode::Left => {
+                if self.mod_manager.loaded_mods.scrolling {
+                    let page_size = self.mod_manager.loaded_mods.page_size as isize;
+                    self.scroll_selection(-page_size);
+                } else {
+                    self.mod_manager.loaded_mods.prev_page();
+                    self.selected_index = 0;
+                }
+            }
+
+            KeyCode::Char('d') | KeyCode::Char('l') | KeyCode::Right => {
+                if self.mod_manager.loaded_mods.scrolling {
+                    let page_size = self.mod_manager.loaded_mods.page_size as isize;
+                    self.scroll_selection(page_size);
+                } else {
+                    self.mod_manager.loaded_mods.next_page();
+                    self.selected_index = 0;
+                }
+            }
+
+            KeyCode::Char('g') => {
+                self.mod_manager.loaded_mods.goto_first();
+                self.select_page_start();
+            }
+            KeyCode::Char('G') => {
+                self.mod_manager.loaded_mods.goto_last();
+                self.select_page_start();
+            }
+            KeyCode::Char(':') => self.goto_page_screen(stdout)?,
+
+            KeyCode::Char(' ') if event.modifiers == KeyModifiers::CONTROL => {
+                self.save_undo();
+                let value = !self
+                    .mod_manager
+                    .loaded_mods
+                    .all_items()
+                    .iter()
+                    .all(|m| m.enabled);
+
+                self.mod_manager
+                    .loaded_mods
+                    .all_items_mut()
+                    .iter_mut()
+                    .for_each(|m| m.enabled = value);
+            }
+
+            KeyCode::Char(c @ '1'..='4') => {
+                let category = match c {
+                    '1' => ModCategory::Workshop,
+                    '2' => ModCategory::Custom,
+                    '3' => ModCategory::Cdlc,
+                    _ => ModCategory::ServerMod,
+                };
+
+                self.save_undo();
+                self.notice = Some(match self.mod_manager.toggle_category(category) {
+                    Some(true) => format!("Enabled all {}", category.name()),
+                    Some(false) => format!("Disabled all {}", category.name()),
+                    None => format!("No {} installed", category.name()),
+                });
+            }
+
+            KeyCode::Char(' ') if self.selection_anchor.is_some() => {
+                self.save_undo();
+                self.toggle_visual_range();
+            }
+
+            KeyCode::Char(' ') => {
+                if let Some(index) = self.selected_mod_index() {
+                    let selected_mod = &mut self.mod_manager.loaded_mods.all_items_mut()[index];
+                    selected_mod.enabled = !selected_mod.enabled;
+
+                    #[cfg(feature = "network")]
+                    if selected_mod.enabled
+                        && !selected_mod.is_cdlc
+                        && !selected_mod.is_custom
+                        && !selected_mod.is_missing
+                    {
+                        self.start_dependency_check(index);
+                    }
+                }
+            }
+
+            KeyCode::Char('V') => {
+                self.selection_anchor = match self.selection_anchor {
+                    Some(_) => None,
+                    None => Some(self.selected_position()),
+                };
+            }
+
+            KeyCode::Char('*') => {
+                if let Some(index) = self.selected_mod_index() {
+                    self.mod_manager.toggle_favorite(index);
+                    self.mod_manager.config.save()?;
+                    self.mod_manager.apply_sort_order();
+                    self.apply_search();
+                }
+            }
+            KeyCode::F(2) => {
+                let filter = self.mod_manager.config.get_mod_filter().next();
+                self.mod_manager.config.set_mod_filter(filter);
+                self.mod_manager.config.save()?;
+                self.apply_search();
+            }
+            KeyCode::F(3) => {
+                // Cycles through the tags, and back to all mods after the last one
+                let tags = self.mod_manager.config.get_tags();
+                if tags.is_empty() {
+                    self.notice = Some("No tags yet, press # to tag the selected mod".to_string());
+                } else {
+                    let next = match self.mod_manager.config.get_tag_filter() {
+                        Some(tag) => tags.iter().position(|t| t == tag).map_or(0, |i| i + 1),
+                        None => 0,
+                    };
+                    self.mod_manager
+                        .config
+                        .set_tag_filter(tags.get(next).cloned());
+                    self.mod_manager.config.save()?;
+                    self.apply_search();
+                }
+            }
+            KeyCode::F(4) => {
+                let favorites_only = !self.mod_manager.config.get_favorites_only();
+                self.mod_manager.config.set_favorites_only(favorites_only);
+                self.mod_manager.config.save()?;
+                self.apply_search();
+            }
+            KeyCode::Char('#') => {
+                if let Some(index) = self.selected_mod_index() {
+                    self.tag_screen(stdout, index)?;
+                }
+            }
+
+            KeyCode::Char('o') => {
+                if let Some(index) = self.selected_mod_index() {
+                    self.save_undo();
+                    self.toggle_solo(index);
+                }
+            }
+            KeyCode::Char('u') => self.undo(),
+
+            KeyCode::Char('K') | KeyCode::Char('J') => {
+                if let Some(index) = self.selected_mod_index() {
+                    let selected_mod = &self.mod_manager.loaded_mods.all_items()[index];
+                    let identifier = selected_mod.identifier.clone();
+                    let saved = self.mod_manager.config.get_enabled_mods();
+                    let unsaved = selected_mod.enabled && !saved.contains(&identifier);
+                    let moved = self
+                        .mod_manager
+                        .move_in_load_order(&identifier, event.code == KeyCode::Char('K'));
+                    if !moved && unsaved {
+                        self.set_status("Save the mod to the preset to move it".to_string());
+                    }
+                }
+            }
+
+            KeyCode::Tab => {
+                self.save_undo();
+                // Save current selection to active preset
+                let enabled_mods = self.mod_manager.enabled_identifiers();
+                self.mod_manager.config.update_mods(enabled_mods);
+                // Cycle to next preset
+                let names = self.mod_manager.config.get_preset_names();
+                if let Some(idx) = names
+                    .iter()
+                    .position(|n| n == self.mod_manager.config.get_active_preset_name())
+                {
+                    let next = (idx + 1) % names.len();
+                    self.mod_manager.switch_preset(&names[next]);
+                    self.set_status(format!("Loaded preset {}", names[next]));
+                }
+                self.apply_search();
+            }
+            KeyCode::BackTab => {
+                self.save_undo();
+                // Save current selection to active preset
+                let enabled_mods = self.mod_manager.enabled_identifiers();
+                self.mod_manager.config.update_mods(enabled_mods);
+                // Cycle to previous preset
+                let names = self.mod_manager.config.get_preset_names();
+                if let Some(idx) = names
+                    .iter()
+                    .position(|n| n == self.mod_manager.config.get_active_preset_name())
+                {
+                    let prev = if idx == 0 { names.len() - 1 } else { idx - 1 };
+                    self.mod_manager.switch_preset(&names[prev]);
+                    self.set_status(format!("Loaded preset {}", names[prev]));
+                }
+                self.apply_search();
+            }
+            KeyCode::Char('t') => {
+                self.preset_manager_screen(stdout)?;
+            }
+            KeyCode::Char('n') => {
+                self.server_screen(stdout)?;
+            }
+            KeyCode::Char('N') => {
+                self.profile_screen(stdout)?;
+            }
+            KeyCode::Char('m') => {
+                self.start_screen(stdout)?;
+            }
+            KeyCode::Char('r') => self.start_refresh(),
+            KeyCode::Char('O') => {
+                let selected = self.selected_mod_index().map(|i| {
+                    self.mod_manager.loaded_mods.all_items()[i]
+                        .identifier
+                        .clone()
+                });
+
+                let order = self.mod_manager.config.get_sort_order().next();
+                self.mod_manager.config.set_sort_order(order);
+                self.mod_manager.config.save()?;
+                self.mod_manager.apply_sort_order();
+                self.apply_search();
+
+                if let Some(identifier) = selected {
+                    self.select_mod(&identifier);
+                }
+            }
+            KeyCode::Char('?') => {
+                self.help_screen(stdout)?;
+            }
+            KeyCode::Char('/') => {
+                self.search_screen(stdout)?;
+            }
+            #[cfg(feature = "network")]
+            KeyCode::Char('c') => {
+                self.check_dependencies_screen(stdout)?;
+            }
+            #[cfg(feature = "network")]
+            KeyCode::Char('C') => {
+                self.batch_dependencies_screen(stdout)?;
+            }
+            KeyCode::Char('f') => {
+                self.set_custom_parameters_screen(stdout)?;
+            }
+            KeyCode::Char('e') => {
+                self.set_executable_name_screen(stdout)?;
+            }
+            KeyCode::Char('p') => {
+                if self.launch_screen(stdout)? {
+                    self.start_game(stdout)?;
+                }
+            }
+            KeyCode::Char('P') => {
+                self.safe_mode_screen(stdout)?;
+            }
+            KeyCode::Char('b') => {
+                self.bisect_screen(stdout)?;
+            }
+            KeyCode::Char('x') => {
+                let name = self.mod_manager.config.get_active_preset_name().to_string();
+                self.export_preset_screen(stdout, &name, "html")?;
+            }
+
+            KeyCode::Enter => {
+                let enabled_mods = self.mod_manager.enabled_identifiers();
+                self.mod_manager.config.update_mods(enabled_mods);
+                self.mod_manager.config.save()?;
+                self.set_status("Saved".to_string());
+            }
+
+            KeyCode::Delete => {
+                if !self.mod_manager.missing_mods().is_empty() {
+                    self.save_undo();
+                    self.mod_manager.purge_missing_mods();
+                    self.mod_manager.config.save()?;
+
+                    let loaded_mods = &self.mod_manager.loaded_mods;
+                    let length = if loaded_mods.scrolling {
+                        loaded_mods.visible_len()
+                    } else {
+                        loaded_mods.current_page_items().len()
+                    };
+                    self.selected_index = self.selected_index.min(length.saturating_sub(1));
+                }
+            }
+
+            KeyCode::Esc if self.selection_anchor.is_some() => {
+                self.selection_anchor = None;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(!self.quit_screen(stdout)?),
+
+            // Unbound key
+            _ => return Ok(true),
+        }
+
+        Ok(true)
+    }
+
+    fn start_game(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        self.ensure_game_stopped()?;
+
+        let enabled_mods = self.mod_manager.enabled_mods();
+
+        let command = launcher::prepare_launch(&self.mod_manager.config, &enabled_mods)?;
+        self.spawn_game(stdout, command)?;
+
+        // Save the enabled mods so it loads next time, also when none are enabled,
+        // so no toggles are left pending
+        let identifiers = self.mod_manager.enabled_identifiers();
+        self.mod_manager.config.update_mods(identifiers);
+        self.mod_manager.config.save()
+    }
+
+    /// Launch vanilla Arma 3 with minimal arguments.
+    /// Neither the config nor the current mod selection are touched.
+    fn start_game_safe_mode(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        self.ensure_game_stopped()?;
+
+        let mut command = launcher::game_command(&self.mod_manager.config)?;
+
+        // Make sure no previously linked mods are picked up from the game directory
+        launcher::remove_linked_mods(&self.mod_manager.config)?;
+
+        command.args(SAFE_MODE_ARGS);
+        self.spawn_game(stdout, command)
+    }
+
+    /// The process id of the game launched from the manager, while it is running
+    fn game_pid(&mut self) -> Option<u32> {
+        let game = self.game.as_mut()?;
+        match game.try_wait() {
+            Ok(None) => Some(game.id()),
+            // Exited, or can't be checked anymore
+            _ => {
+                self.game = None;
+                None
+            }
+        }
+    }
+
+    /// Launching replaces the linked mods, which the running game still uses
+    fn ensure_game_stopped(&mut self) -> AppResult<()> {
+        match self.game_pid() {
+            Some(pid) => Err(AppError::GameRunning(pid)),
+            None => Ok(()),
+        }
+    }
+
+    /// Starts the game with its output logged to a file.
+    /// Waits for it to exit when `wait_for_game` is set, otherwise keeps it to show that it is running.
+    fn spawn_game(&mut self, stdout: &mut Stdout, mut command: Command) -> AppResult<()> {
+        // The game is started without a log rather than not at all
+        let log_notice = match launcher::log_output(&mut command) {
+            Ok(log_path) => format!("Game output is logged to {}", log_path.display()),
+            Err(e) => format!("Game output is not logged: {}", e),
+        };
+        let mut game = command.spawn()?;
+        self.notice = Some(log_notice);
+
+        // Steam hands the launch off to the running client and exits
+        if launcher::launches_through_steam(&self.mod_manager.config) {
+            return Ok(());
+        }
+
+        if !self.mod_manager.config.get_wait_for_game() {
+            self.game = Some(game);
+            return Ok(());
+        }
+
+        self.clear_screen(stdout)?;
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            SetForegroundColor(Color::Green),
+            Print(format!(
+                "Game running (pid {}), waiting for it to exit...",
+                game.id()
+            )),
+            SetForegroundColor(Color::Reset),
+        )?;
+        stdout.flush()?;
+
+        game.wait()?;
+        Ok(())
+    }
+
+    /// Whether the mod selection differs from the one in the saved config
+    fn has_unsaved_changes(&self) -> bool {
+        let active_preset = self.mod_manager.config.get_active_preset_name();
+        let enabled_mods = self.mod_manager.enabled_identifiers();
+
+        match Config::read() {
+            Ok(saved) => {
+                saved.get_active_preset_name() != active_preset
+                    || saved.get_enabled_mods() != enabled_mods
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// Asks to save unsaved changes before quitting.
+    /// Returns false when quitting was cancelled.
+    fn quit_screen(&mut self, stdout: &mut Stdout) -> AppResult<bool> {
+        if !self.has_unsaved_changes() {
+            return Ok(true);
+        }
+
+        self.clear_screen(stdout)?;
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            SetForegroundColor(Color::Yellow),
+            Print("You have unsaved changes, save before quitting?"),
+            SetForegroundColor(Color::Reset),
+            cursor::MoveTo(0, 2),
+            Print("<Y> Yes  <N> No  <ESC> Cancel"),
+        )?;
+        stdout.flush()?;
+
+        loop {
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Char('y') | KeyCode::Enter => {
+                            let enabled_mods = self.mod_manager.enabled_identifiers();
+                            self.mod_manager.config.update_mods(enabled_mods);
+                            self.mod_manager.config.save()?;
+                            return Ok(true);
+                        }
+                        KeyCode::Char('n') => return Ok(true),
+                        KeyCode::Esc | KeyCode::Char('c') => return Ok(false),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Shows what the game will be launched with and asks for confirmation.
+    /// Nothing in the game directory is touched before confirming.
+    fn launch_screen(&self, stdout: &mut Stdout) -> AppResult<bool> {
+        // Leave room for the arguments below the list
+        const MAX_LISTED_MODS: usize = 15;
+
+        let config = &self.mod_manager.config;
+        let enabled_mods = self.mod_manager.enabled_mods();
+
+        self.clear_screen(stdout)?;
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            SetForegroundColor(Color::Cyan),
+            Print(format!(
+                "Launch Arma 3 [{}] with {} mods",
+                config.get_active_preset_name(),
+                enabled_mods.len()
+            )),
+            SetForegroundColor(Color::Reset),
+        )?;
+
+        let invalid_mods = launcher::invalid_mod_arguments(config, &enabled_mods);
+
+        // Every row below the title, a long row takes as many lines as it wraps over
+        let mut lines: Vec<(String, Color)> = Vec::new();
+        for m in enabled_mods.iter().take(MAX_LISTED_MODS) {
+            let (color, note) = if m.is_missing {
+                (Color::Red, " - missing, skipped")
+            } else if invalid_mods.iter().any(|i| i.identifier == m.identifier) {
+                (Color::Red, " - name contains ; or \", skipped")
+            } else if m.is_unowned {
+                (Color::Red, " - CDLC, not owned")
+            } else if m.is_cdlc {
+                (Color::Blue, " - CDLC")
+            } else if m.is_server_mod {
+                (Color::Magenta, " - server mod")
+            } else {
+                (Color::Reset, "")
+            };
+
+            let line = if m.is_missing {
+                format!("  {}{}", m.identifier, note)
+            } else {
+                format!("  {} ({}){}", m.name, m.identifier, note)
+            };
+            lines.push((line, color));
+        }
+
+        if enabled_mods.len() > MAX_LISTED_MODS {
+            lines.push((
+                format!("  ... and {} more", enabled_mods.len() - MAX_LISTED_MODS),
+                Color::Reset,
+            ));
+        }
+
+        let link_mode = match config.get_link_mode() {
+            LinkMode::Symlink => "symlink - mods are linked into the game directory",
+            LinkMode::Copy => {
+                "copy - mods are copied into the game directory, which takes longer and uses disk space unless hard linked"
+            }
+            LinkMode::Paths => "paths - mods are passed by their full path, the game directory is left alone",
+        };
+
+        let args = launcher::build_launch_args(config, &enabled_mods);
+        let mod_args: Vec<&str> = args
+            .iter()
+            .filter(|arg| arg.starts_with("-mod=") || arg.starts_with("-serverMod="))
+            .map(|arg| arg.as_str())
+            .collect();
+        let mod_argument = if mod_args.is_empty() {
+            "Mod argument: (none)".to_string()
+        } else {
+            format!("Mod argument: {}", mod_args.join(" "))
+        };
+
+        // Listed separately, so a CDLC enabled by mistake stands out in a long list
+        let cdlc_names = |unowned_only: bool| -> Vec<String> {
+            enabled_mods
+                .iter()
+                .filter(|m| m.is_cdlc && (m.is_unowned || !unowned_only))
+                .map(|m| format!("{} ({})", m.name, m.identifier))
+                .collect()
+        };
+        let cdlcs = match cdlc_names(false) {
+            names if names.is_empty() => "(none)".to_string(),
+            names => names.join(", "),
+        };
+        let unowned = cdlc_names(true);
+
+        let start_args: Vec<&str> = args
+            .iter()
+            .filter(|arg| arg.starts_with("-world=") || arg.starts_with("-init="))
+            .map(|arg| arg.as_str())
+            .collect();
+        let start_in = if start_args.is_empty() {
+            "main menu".to_string()
+        } else {
+            start_args.join(" ")
+        };
+
+        lines.push((String::new(), Color::Reset));
+        lines.push((format!("Link mode: {}", link_mode), Color::Reset));
+        lines.push((format!("Args: {}", config.get_launch_args()), Color::Reset));
+        lines.push((
+            format!(
+                "Server: {}",
+                config
+                    .get_server()
+                    .map(|server| server.address())
+                    .unwrap_or_else(|| "(none)".to_string())
+            ),
+            Color::Reset,
+        ));
+        lines.push((
+            format!("Profile: {}", config.get_profile().unwrap_or("(default)")),
+            Color::Reset,
+        ));
+        lines.push((format!("Start in: {}", start_in), Color::Reset));
+        lines.push((format!("CDLCs: {}", cdlcs), Color::Reset));
+        lines.push((
+            format!(
+                "BattlEye: {}",
+                if config.get_use_battleye() {
+                    "on - started through the BattlEye launcher"
+                } else {
+                    "off"
+                }
+            ),
+            Color::Reset,
+        ));
+
+        if !unowned.is_empty() {
+            lines.push((
+                format!("Not owned, launching may fail: {}", unowned.join(", ")),
+                Color::Red,
+            ));
+        }
+
+        if let Some(warning) = launcher::disk_space_warning(config, &enabled_mods) {
+            lines.push((warning, Color::Yellow));
+        }
+
+        lines.push((mod_argument, Color::Reset));
+        lines.push((String::new(), Color::Reset));
+        lines.push(("Launch? <Y> Yes  <N> No".to_string(), Color::Reset));
+
+        let (columns, _) = terminal::size()?;
+        let mut y = 2;
+        for (line, color) in &lines {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, y),
+                SetForegroundColor(*color),
+                Print(line),
+                SetForegroundColor(Color::Reset),
+            )?;
+            y += line.len().div_ceil(columns.max(1) as usize).max(1) as u16;
+        }
+        stdout.flush()?;
+
+        loop {
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Char('y') | KeyCode::Enter => return Ok(true),
+                        KeyCode::Char('n') | KeyCode::Esc => return Ok(false),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn safe_mode_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        self.start_game_safe_mode(stdout)?;
+
+        self.clear_screen(stdout)?;
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            SetForegroundColor(Color::Cyan),
+            Print("Arma 3 Mod Manager Console - Safe Mode"),
+            SetForegroundColor(Color::Reset),
+            cursor::MoveTo(0, 2),
+            Print(format!(
+                "Launched without mods using: {}",
+                SAFE_MODE_ARGS.join(" ")
+            )),
+            cursor::MoveTo(0, 4),
+            Print("If the game works without mods, one of the enabled mods is likely the problem."),
+            cursor::MoveTo(0, 6),
+            Print("Press <B> to bisect the enabled mods, any other key to return..."),
+        )?;
+        stdout.flush()?;
+
+        loop {
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    if code == KeyCode::Char('b') {
+                        self.bisect_screen(stdout)?;
+                    }
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bisect_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let candidates: Vec<String> = self
+            .mod_manager
+            .loaded_mods
+            .filter(|m| m.enabled && !m.is_missing)
+            .iter()
+            .map(|m| m.identifier.clone())
+            .collect();
+
+        if candidates.len() < 2 {
+            self.clear_screen(stdout)?;
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Yellow),
+                Print("Enable at least two mods to bisect."),
+                SetForegroundColor(Color::Reset),
+                cursor::MoveTo(0, 2),
+                Print("Press any key to return...")
+            )?;
+            stdout.flush()?;
+
+            loop {
+                if event::poll(Duration::from_millis(500))? {
+                    if let Some(Event::Key(_)) = read_event()? {
+                        break;
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        let mut bisection = Bisection::new(candidates);
+        let mut launched = false;
+        let mut warning: Option<String> = None;
+
+        loop {
+            let mod_name = |identifier: &str| {
+                self.mod_manager
+                    .loaded_mods
+                    .all_items()
+                    .iter()
+                    .find(|m| m.identifier == identifier)
+                    .map(|m| m.name.clone())
+                    .unwrap_or_else(|| identifier.to_string())
+            };
+
+            self.clear_screen(stdout)?;
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Cyan),
+                Print("Arma 3 Mod Manager Console - Bisect Mods"),
+                SetForegroundColor(Color::Reset),
+            )?;
+
+            if let Some(culprit) = bisection.culprit() {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, 2),
+                    Print(format!(
+                        "Found the problem after {} launches:",
+                        bisection.steps()
+                    )),
+                    cursor::MoveTo(0, 4),
+                    SetForegroundColor(Color::Red),
+                    Print(format!("  {} ({})", mod_name(culprit), culprit)),
+                    SetForegroundColor(Color::Reset),
+                    cursor::MoveTo(0, 6),
+                    Print("Press any key to return..."),
+                )?;
+                stdout.flush()?;
+
+                loop {
+                    if event::poll(Duration::from_millis(500))? {
+                        if let Some(Event::Key(_)) = read_event()? {
+                            break;
+                        }
+                    }
+                }
+                break;
+            }
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 2),
+                Print(format!(
+                    "Candidates: {}    Launches so far: {}    Launches left: ~{}",
+                    bisection.candidates().len(),
+                    bisection.steps(),
+                    bisection.steps_left()
+                )),
+                cursor::MoveTo(0, 4),
+                Print("Next launch uses:"),
+            )?;
+
+            let mut y_offset: u16 = 5;
+            for identifier in bisection.test_set() {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    Print(format!("  {}", mod_name(identifier))),
+                )?;
+                y_offset += 1;
+            }
+
+            y_offset += 1;
+            let instructions = if launched {
+                "Did the problem occur? <Y> Yes  <N> No  <ENTER> Launch again  <ESC> Abort"
+            } else {
+                "<ENTER> Launch  <ESC> Abort"
+            };
+            execute!(stdout, cursor::MoveTo(0, y_offset), Print(instructions))?;
+
+            if let Some(warning) = &warning {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset + 2),
+                    SetForegroundColor(Color::Yellow),
+                    Print(warning),
+                    SetForegroundColor(Color::Reset),
+                )?;
+            }
+            stdout.flush()?;
+
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Enter => {
+                            if let Some(pid) = self.game_pid() {
+                                warning = Some(AppError::GameRunning(pid).to_string());
+                                continue;
+                            }
+
+                            let test_set = bisection.test_set();
+                            let mods = self
+                                .mod_manager
+                                .loaded_mods
+                                .filter(|m| test_set.contains(&m.identifier));
+                            let command =
+                                launcher::prepare_launch(&self.mod_manager.config, &mods)?;
+                            self.spawn_game(stdout, command)?;
+                            launched = true;
+                            warning = None;
+                        }
+                        KeyCode::Char('y') if launched => {
+                            bisection.record(true);
+                            launched = false;
+                        }
+                        KeyCode::Char('n') if launched => {
+                            bisection.record(false);
+                            launched = false;
+                        }
+                        KeyCode::Esc => break,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear_screen(&self, stdout: &mut Stdout) -> AppResult<()> {
+        execute!(stdout, cursor::MoveTo(0, 0))?;
+        execute!(stdout, terminal::Clear(terminal::ClearType::All))?;
+
+        Ok(())
+    }
+
+    /// Describes which launch arguments the custom parameters screen is editing
+    fn args_label(&self, editing_preset: bool) -> String {
+        let preset = self.mod_manager.config.get_active_preset_name();
+        if editing_preset {
+            format!(
+                "Editing: arguments of preset [{}], leave empty to use the default",
+                preset
+            )
+        } else if self.mod_manager.config.get_preset_args().is_some() {
+            format!(
+                "Editing: default arguments, preset [{}] has its own",
+                preset
+            )
+        } else {
+            "Editing: default arguments".to_string()
+        }
+    }
+
+    /// Lists likely mistakes in the launch arguments from the row down. Saving is still
+    /// allowed, the game takes more arguments than can be checked.
+    fn draw_arg_warnings(&self, stdout: &mut Stdout, top: u16, launch_args: &str) -> AppResult<()> {
+        execute!(
+            stdout,
+            cursor::MoveTo(0, top),
+            terminal::Clear(terminal::ClearType::FromCursorDown),
+            SetForegroundColor(Color::Yellow),
+        )?;
+        for (i, warning) in launcher::launch_arg_warnings(launch_args)
+            .iter()
+            .enumerate()
+        {
+            execute!(
+                stdout,
+                cursor::MoveTo(0, top + i as u16),
+                Print(format!("Warning: {}", warning)),
+            )?;
+        }
+        execute!(stdout, SetForegroundColor(Color::Reset))?;
+        Ok(())
+    }
+
+    fn set_custom_parameters_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let config = &self.mod_manager.config;
+        let mut editing_preset = config.get_preset_args().is_some();
+        let mut input = TextInput::new(config.get_launch_args());
+
+        // Set up the terminal
+        execute!(stdout, cursor::Show)?;
+        execute!(stdout, SetCursorStyle::BlinkingUnderScore)?;
+        stdout.flush()?;
+
+        self.clear_screen(stdout)?;
+
+        execute!(
+            stdout,
+            SetForegroundColor(Color::Cyan),
+            cursor::MoveTo(0, 0),
+            Print("Arma 3 Mod Manager Console"),
+            SetForegroundColor(Color::Reset),
+        )?;
+
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 2),
+            Print(
+                "Press <ENTER> to save, <TAB> to switch between the preset and default arguments"
+            ),
+            cursor::MoveTo(0, 3),
+            Print(self.args_label(editing_preset)),
+        )?;
+
+        let arg_string_top = 5;
+
+        execute!(
+            stdout,
+            cursor::MoveTo(0, arg_string_top + 2),
+            Print("For more information visit: https://community.bistudio.com/wiki/Arma_3:_Startup_Parameters")
+        )?;
+
+        self.draw_arg_warnings(stdout, arg_string_top + 4, input.text())?;
+        self.draw_text_input(stdout, arg_string_top, "", &input)?;
+
+        loop {
+            if event::poll(Duration::from_millis(500))? {
+                match input.handle_event(event::read()?) {
+                    InputAction::Cancel => break,
+                    InputAction::Confirm => {
+                        let args_string = input.text().trim().to_string();
+                        let config = &mut self.mod_manager.config;
+                        if !editing_preset {
+                            config.set_default_args(args_string);
+                        } else if args_string.is_empty() {
+                            config.set_preset_args(None);
+                        } else {
+                            config.set_preset_args(Some(args_string));
+                        }
+                        config.save()?;
+
+                        break;
+                    }
+                    InputAction::Key(KeyEvent {
+                        code: KeyCode::Tab, ..
+                    }) => {
+                        editing_preset = !editing_preset;
+                        let config = &self.mod_manager.config;
+                        input.set_text(if editing_preset {
+                            config.get_preset_args().unwrap_or_default()
+                        } else {
+                            config.get_default_args()
+                        });
+
+                        execute!(
+                            stdout,
+                            cursor::MoveTo(0, 3),
+                            terminal::Clear(terminal::ClearType::CurrentLine),
+                            Print(self.args_label(editing_preset)),
+                        )?;
+                        self.draw_arg_warnings(stdout, arg_string_top + 4, input.text())?;
+                        self.draw_text_input(stdout, arg_string_top, "", &input)?;
+                    }
+                    InputAction::Edited => {
+                        self.draw_arg_warnings(stdout, arg_string_top + 4, input.text())?;
+                        self.draw_text_input(stdout, arg_string_top, "", &input)?;
+                    }
+                    InputAction::Moved => {
+                        self.draw_text_input(stdout, arg_string_top, "", &input)?;
+                    }
+                    InputAction::Key(_) | InputAction::Ignored => {}
+                }
+            }
+        }
+        // Restore terminal state
+        execute!(stdout, cursor::Hide)?;
+        execute!(stdout, SetCursorStyle::DefaultUserShape)?;
+
+        Ok(())
+    }
+
+    /// Lists the recent servers to pick the one to join when launching
+    fn server_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let mut selected: usize = 0;
+
+        loop {
+            let config = &self.mod_manager.config;
+            let current = config.get_server().map(|server| server.address());
+            let recent: Vec<String> = config
+                .get_recent_servers()
+                .iter()
+                .map(|server| server.address())
+                .collect();
+
+            if selected >= recent.len() {
+                selected = recent.len().saturating_sub(1);
+            }
+
+            self.clear_screen(stdout)?;
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Cyan),
+                Print("Arma 3 Mod Manager Console - Join Server"),
+                SetForegroundColor(Color::Reset),
+                cursor::MoveTo(0, 2),
+                Print(format!(
+                    "Joining: {}",
+                    current.as_deref().unwrap_or("(none)")
+                )),
+            )?;
+
+            let mut y_offset: u16 = 4;
+
+            if recent.is_empty() {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print("   No recent servers"),
+                    SetForegroundColor(Color::Reset),
+                )?;
+                y_offset += 1;
+            }
+
+            for (i, address) in recent.iter().enumerate() {
+                let cursor = if i == selected { " > " } else { "   " };
+                let marker = if current.as_ref() == Some(address) {
+                    "[*]"
+                } else {
+                    "[ ]"
+                };
+
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::Red),
+                    Print(cursor),
+                    SetForegroundColor(Color::Reset),
+                    Print(format!("{} {}", marker, address)),
+                )?;
+                y_offset += 1;
+            }
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, y_offset + 1),
+                Print("  <N> New  <ENTER> Join  <C> Clear  <D> Remove  <ESC> Back"),
+            )?;
+            stdout.flush()?;
+
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            selected = selected.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j')
+                            if selected < recent.len().saturating_sub(1) =>
+                        {
+                            selected += 1;
+                        }
+                        KeyCode::Enter => {
+                            let server = self
+                                .mod_manager
+                                .config
+                                .get_recent_servers()
+                                .get(selected)
+                                .cloned();
+                            if server.is_some() {
+                                self.mod_manager.config.set_server(server);
+                                self.mod_manager.config.save()?;
+                                return Ok(());
+                            }
+                        }
+                        KeyCode::Char('n') => {
+                            self.new_server_screen(stdout)?;
+                            selected = 0;
+                        }
+                        KeyCode::Char('c') => {
+                            self.mod_manager.config.set_server(None);
+                            self.mod_manager.config.save()?;
+                        }
+                        KeyCode::Char('d') => {
+                            self.mod_manager.config.remove_recent_server(selected);
+                            self.mod_manager.config.save()?;
+                        }
+                        KeyCode::Esc => return Ok(()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Lists the tags to add to or remove from the mod at the index, and adds new tags
+    fn tag_screen(&mut self, stdout: &mut Stdout, index: usize) -> AppResult<()> {
+        let m = &self.mod_manager.loaded_mods.all_items()[index];
+        let (identifier, name) = (m.identifier.clone(), m.name.clone());
+        let mut selected: usize = 0;
+
+        loop {
+            let config = &self.mod_manager.config;
+            let tags = config.get_tags();
+            selected = selected.min(tags.len().saturating_sub(1));
+
+            self.clear_screen(stdout)?;
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Cyan),
+                Print(format!("Arma 3 Mod Manager Console - Tags of {}", name)),
+                SetForegroundColor(Color::Reset),
+            )?;
+
+            let mut y_offset: u16 = 2;
+
+            if tags.is_empty() {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print("   No tags yet"),
+                    SetForegroundColor(Color::Reset),
+                )?;
+                y_offset += 1;
+            }
+
+            for (i, tag) in tags.iter().enumerate() {
+                let cursor = if i == selected { " > " } else { "   " };
+                let marker = if config.has_tag(tag, &identifier) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::Red),
+                    Print(cursor),
+                    SetForegroundColor(Color::Reset),
+                    Print(format!(
+                        "{} {} ({} mods)",
+                        marker,
+                        tag,
+                        config.get_tagged_mods(tag).len()
+                    )),
+                )?;
+                y_offset += 1;
+            }
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, y_offset + 1),
+                Print("  <N> New Tag  <SPACE> Add / Remove  <ESC> Back"),
+            )?;
+            stdout.flush()?;
+
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            selected = selected.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j')
+                            if selected < tags.len().saturating_sub(1) =>
+                        {
+                            selected += 1;
+                        }
+                        KeyCode::Char(' ') | KeyCode::Enter if !tags.is_empty() => {
+                            self.mod_manager
+                                .config
+                                .toggle_tag(&tags[selected], &identifier);
+                            self.mod_manager.config.save()?;
+                        }
+                        KeyCode::Char('n') => {
+                            let Some(tag) =
+                                self.input_screen(stdout, "New Tag", "Tag name:", "")?
+                            else {
+                                continue;
+                            };
+                            let tag = tag.trim();
+                            if !tag.is_empty() && !self.mod_manager.config.has_tag(tag, &identifier)
+                            {
+                                self.mod_manager.config.toggle_tag(tag, &identifier);
+                                self.mod_manager.config.save()?;
+                            }
+                        }
+                        KeyCode::Esc => break,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        self.apply_search();
+        self.select_mod(&identifier);
+
+        Ok(())
+    }
+
+    /// Sets the mission or the editor world the game starts in, instead of the main menu
+    fn start_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        loop {
+            let config = &self.mod_manager.config;
+
+            self.clear_screen(stdout)?;
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Cyan),
+                Print("Arma 3 Mod Manager Console - Start in Mission / Editor"),
+                SetForegroundColor(Color::Reset),
+                cursor::MoveTo(0, 2),
+                Print(format!(
+                    "Init command (-init=): {}",
+                    config.get_init_command().unwrap_or("(none)")
+                )),
+                cursor::MoveTo(0, 3),
+                Print(format!(
+                    "Editor world (-world=): {}",
+                    config
+                        .get_editor_world()
+                        .unwrap_or("(from the launch arguments)")
+                )),
+                cursor::MoveTo(0, 5),
+                SetForegroundColor(Color::DarkGrey),
+                Print("  e.g. playMission[\"\",\"MyMission.Altis\"] or Altis, VR, Stratis"),
+                SetForegroundColor(Color::Reset),
+                cursor::MoveTo(0, 7),
+                Print("  <I> Set Init Command  <W> Set World  <C> Clear Both  <ESC> Back"),
+            )?;
+            stdout.flush()?;
+
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Char('i') => {
+                            let current = config.get_init_command().unwrap_or("").to_string();
+                            if let Some(command) = self.input_screen(
+                                stdout,
+                                "Init Command",
+                                "Command (empty to clear):",
+                                &current,
+                            )? {
+                                let command = command.trim();
+                                self.mod_manager.config.set_init_command(
+                                    (!command.is_empty()).then(|| command.to_string()),
+                                );
+                                self.mod_manager.config.save()?;
+                            }
+                        }
+                        KeyCode::Char('w') => {
+                            let current = config.get_editor_world().unwrap_or("").to_string();
+                            if let Some(world) = self.input_screen(
+                                stdout,
+                                "Editor World",
+                                "World (empty to clear):",
+                                &current,
+                            )? {
+                                let world = world.trim();
+                                self.mod_manager.config.set_editor_world(
+                                    (!world.is_empty()).then(|| world.to_string()),
+                                );
+                                self.mod_manager.config.save()?;
+                            }
+                        }
+                        KeyCode::Char('c') => {
+                            self.mod_manager.config.set_init_command(None);
+                            self.mod_manager.config.set_editor_world(None);
+                            self.mod_manager.config.save()?;
+                        }
+                        KeyCode::Esc => return Ok(()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Lists the Arma profiles to pick the one to play as
+    fn profile_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let profiles_path = utils::get_profiles_path()?;
+        let current = self.mod_manager.config.get_profile().map(str::to_string);
+
+        // The default profile comes first, and the selected profile is listed even when its
+        // directory is gone, so it can be changed back
+        let mut profiles: Vec<Option<String>> = vec![None];
+        if current.is_some() {
+            profiles.push(current.clone());
+        }
+        for profile in utils::find_profiles(&profiles_path) {
+            if !profiles.contains(&Some(profile.clone())) {
+                profiles.push(Some(profile));
+            }
+        }
+        profiles[1..].sort_by_key(|profile| profile.as_deref().map(str::to_lowercase));
+
+        let mut selected = profiles.iter().position(|p| *p == current).unwrap_or(0);
+
+        loop {
+            self.clear_screen(stdout)?;
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Cyan),
+                Print("Arma 3 Mod Manager Console - Profile"),
+                SetForegroundColor(Color::Reset),
+                cursor::MoveTo(0, 2),
+                Print(format!("Profiles in: {}", profiles_path.display())),
+            )?;
+
+            let mut y_offset: u16 = 4;
+
+            for (i, profile) in profiles.iter().enumerate() {
+                let cursor = if i == selected { " > " } else { "   " };
+                let marker = if *profile == current { "[*]" } else { "[ ]" };
+
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::Red),
+                    Print(cursor),
+                    SetForegroundColor(Color::Reset),
+                    Print(format!(
+                        "{} {}",
+                        marker,
+                        profile.as_deref().unwrap_or("(default)")
+                    )),
+                )?;
+                y_offset += 1;
+            }
+
+            if profiles.len() == 1 {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print("   No other profiles found"),
+                    SetForegroundColor(Color::Reset),
+                )?;
+                y_offset += 1;
+            }
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, y_offset + 1),
+                Print("  <ENTER> Select  <ESC> Back"),
+            )?;
+            stdout.flush()?;
+
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            selected = selected.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') if selected < profiles.len() - 1 => {
+                            selected += 1;
+                        }
+                        KeyCode::Enter => {
+                            self.mod_manager
+                                .config
+                                .set_profile(profiles[selected].clone());
+                            self.mod_manager.config.save()?;
+                            return Ok(());
+                        }
+                        KeyCode::Esc => return Ok(()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Asks for the address and password of a server to join
+    fn new_server_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let title = "Arma 3 Mod Manager Console - Join Server";
+
+        let mut address = String::new();
+        let mut error: Option<String> = None;
+
+        let server = loop {
+            let Some(input) = self.input_screen_with_error(
+                stdout,
+                title,
+                "Address (host:port):",
+                &address,
+                error.as_deref(),
+            )?
+            else {
+                return Ok(());
+            };
+
+            match Server::parse(&input, None) {
+                Ok(server) => break server,
+                Err(e) => {
+                    error = Some(e.to_string());
+                    address = input;
+                }
+            }
+        };
+
+        let Some(password) = self.input_screen(stdout, title, "Password (empty for none):", "")?
+        else {
+            return Ok(());
+        };
+
+        self.mod_manager.config.set_server(Some(Server {
+            password: Some(password).filter(|p| !p.is_empty()),
+            ..server
+        }));
+        self.mod_manager.config.save()
+    }
+
+    /// Edit the game or workshop path until a valid path is entered or the edit is cancelled
+    fn set_path_screen(&mut self, stdout: &mut Stdout, game_path: bool) -> AppResult<()> {
+        let (title, current_path) = if game_path {
+            (
+                "Arma 3 Mod Manager Console - Game Path",
+                self.mod_manager.config.get_game_path(),
+            )
+        } else {
+            (
+                "Arma 3 Mod Manager Console - Workshop Path",
+                self.mod_manager.config.get_workshop_path(),
+            )
+        };
+
+        let mut path = current_path.to_string_lossy().to_string();
+        let mut error: Option<String> = None;
+
+        loop {
+            let Some(new_path) =
+                self.input_screen_with_error(stdout, title, "Path:", &path, error.as_deref())?
+            else {
+                return Ok(());
+            };
+            let new_path = utils::normalize_path_input(&new_path);
+
+            let result = if game_path {
+                self.mod_manager.change_game_path(new_path.clone())
+            } else {
+                self.mod_manager.change_workshop_path(new_path.clone())
+            };
+
+            match result {
+                Ok(()) => {
+                    self.apply_search();
+                    return Ok(());
+                }
+                Err(e) => {
+                    error = Some(e.to_string());
+                    path = new_path;
+                }
+            }
+        }
+    }
+
+    /// Lists the custom mods folders to add to or remove from
+    fn custom_folders_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let title = "Arma 3 Mod Manager Console - Custom Mod Folders";
+        let mut selected: usize = 0;
+        let mut installed: Option<String> = None;
+
+        loop {
+            let paths: Vec<String> = self
+                .mod_manager
+                .config
+                .get_custom_mods_paths()
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            selected = selected.min(paths.len().saturating_sub(1));
+
+            self.clear_screen(stdout)?;
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Cyan),
+                Print(title),
+                SetForegroundColor(Color::Reset),
+            )?;
+
+            let mut y_offset: u16 = 2;
+
+            if paths.is_empty() {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print("   No custom mod folders"),
+                    SetForegroundColor(Color::Reset),
+                )?;
+                y_offset += 1;
+            }
+
+            for (i, path) in paths.iter().enumerate() {
+                let cursor = if i == selected { " > " } else { "   " };
+
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::Red),
+                    Print(cursor),
+                    SetForegroundColor(Color::Reset),
+                    Print(path),
+                )?;
+                if !Path::new(path).is_dir() {
+                    execute!(
+                        stdout,
+                        SetForegroundColor(Color::Yellow),
+                        Print(" [Not found]"),
+                        SetForegroundColor(Color::Reset),
+                    )?;
+                }
+                y_offset += 1;
+            }
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, y_offset + 1),
+                Print("  <N> Add  <D> Remove  <I> Install mod from zip  <ESC> Back"),
+            )?;
+            stdout.flush()?;
+
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            selected = selected.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j')
+                            if selected < paths.len().saturating_sub(1) =>
+                        {
+                            selected += 1;
+                        }
+                        KeyCode::Char('n') => self.add_custom_folder_screen(stdout, title)?,
+                        KeyCode::Char('d') if !paths.is_empty() => {
+                            self.mod_manager.remove_custom_mods_path(selected)?;
+                        }
+                        KeyCode::Char('i') if !paths.is_empty() => {
+                            if let Some(identifier) =
+                                self.install_zip_screen(stdout, title, selected)?
+                            {
+                                installed = Some(identifier);
+                            }
+                        }
+                        KeyCode::Esc => break,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        self.apply_search();
+        if let Some(identifier) = installed {
+            self.select_mod(&identifier);
+            self.set_status(format!("Installed {}", identifier));
+        }
+
+        Ok(())
+    }
+
+    /// Asks for a zip archive until a mod is installed from it into the custom mods folder at
+    /// the index, or it is cancelled. Returns the identifier of the installed mod.
+    fn install_zip_screen(
+        &mut self,
+        stdout: &mut Stdout,
+        title: &str,
+        index: usize,
+    ) -> AppResult<Option<String>> {
+        let mut path = String::new();
+        let mut error: Option<String> = None;
+
+        loop {
+            let Some(zip_path) =
+                self.input_screen_with_error(stdout, title, "Zip:", &path, error.as_deref())?
+            else {
+                return Ok(None);
+            };
+            let zip_path = utils::normalize_path_input(&zip_path);
+
+            match self
+                .mod_manager
+                .install_custom_mod_from_zip(Path::new(&zip_path), index)
+            {
+                Ok(identifier) => return Ok(Some(identifier)),
+                Err(e) => {
+                    error = Some(e.to_string());
+                    path = zip_path;
+                }
+            }
+        }
+    }
+
+    /// Asks for a custom mods folder until an existing folder is entered or it is cancelled
+    fn add_custom_folder_screen(&mut self, stdout: &mut Stdout, title: &str) -> AppResult<()> {
+        let mut path = String::new();
+        let mut error: Option<String> = None;
+
+        loop {
+            let Some(new_path) =
+                self.input_screen_with_error(stdout, title, "Path:", &path, error.as_deref())?
+            else {
+                return Ok(());
+            };
+            let new_path = utils::normalize_path_input(&new_path);
+
+            match self.mod_manager.add_custom_mods_path(new_path.clone()) {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    error = Some(e.to_string());
+                    path = new_path;
+                }
+            }
+        }
+    }
+
+    fn set_executable_name_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let mut input = TextInput::new(self.mod_manager.config.get_executable_name());
+
+        // Set up the terminal
+        execute!(stdout, cursor::Show)?;
+        execute!(stdout, SetCursorStyle::BlinkingUnderScore)?;
+        stdout.flush()?;
+
+        self.clear_screen(stdout)?;
+
+        execute!(
+            stdout,
+            SetForegroundColor(Color::Cyan),
+            cursor::MoveTo(0, 0),
+            Print("Arma 3 Mod Manager Console - Executable Name"),
+            SetForegroundColor(Color::Reset),
+        )?;
+
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 2),
+            Print("Press <ENTER> to save, <ESC> to cancel"),
+        )?;
+
+        let name_top = 4;
+
+        let instruction_text = if cfg!(target_os = "macos") {
+            "Enter the name of the Arma 3 executable (without .app extension)"
+        } else {
+            "Enter the name of the Arma 3 executable"
+        };
+
+        execute!(
+            stdout,
+            cursor::MoveTo(0, name_top + 2),
+            Print(instruction_text)
+        )?;
+
+        self.print_executable_status(stdout, input.text(), name_top + 4)?;
+        self.draw_text_input(stdout, name_top, "", &input)?;
+
+        loop {
+            if event::poll(Duration::from_millis(500))? {
+                match input.handle_event(event::read()?) {
+                    InputAction::Cancel => break,
+                    InputAction::Confirm => {
+                        let executable_name = utils::strip_input_quotes(input.text());
+                        self.mod_manager
+                            .config
+                            .set_executable_name(executable_name.to_string());
+                        self.mod_manager.config.save()?;
+                        break;
+                    }
+                    InputAction::Edited => {
+                        self.print_executable_status(stdout, input.text(), name_top + 4)?;
+                        self.draw_text_input(stdout, name_top, "", &input)?;
+                    }
+                    InputAction::Moved => {
+                        self.draw_text_input(stdout, name_top, "", &input)?;
+                    }
+                    InputAction::Key(_) | InputAction::Ignored => {}
+                }
+            }
+        }
+        // Restore terminal state
+        execute!(stdout, cursor::Hide)?;
+        execute!(stdout, SetCursorStyle::DefaultUserShape)?;
+
+        Ok(())
+    }
+
+    /// Shows whether the executable with the name exists in the game directory
+    fn print_executable_status(
+        &self,
+        stdout: &mut Stdout,
+        executable_name: &str,
+        top: u16,
+    ) -> AppResult<()> {
+        let game_path = self.mod_manager.config.get_game_path();
+        let executable_path = launcher::get_executable_path(game_path, executable_name);
+
+        let (color, status) = if !executable_name.is_empty() && executable_path.is_file() {
+            (Color::Green, "Found")
+        } else {
+            (Color::Red, "Not found")
+        };
+
+        execute!(
+            stdout,
+            cursor::MoveTo(0, top),
+            terminal::Clear(terminal::ClearType::CurrentLine),
+            SetForegroundColor(color),
+            Print(format!("{}: {}", status, executable_path.display())),
+            SetForegroundColor(Color::Reset)
+        )?;
+
+        Ok(())
+    }
+
+    fn preset_manager_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let mut selected: usize = 0;
+
+        loop {
+            let names = self.mod_manager.config.get_preset_names();
+            let active_name = self.mod_manager.config.get_active_preset_name().to_string();
+
+            // Clamp selected index
+            if selected >= names.len() {
+                selected = names.len().saturating_sub(1);
+            }
+
+            self.clear_screen(stdout)?;
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Cyan),
+                Print("Arma 3 Mod Manager Console - Preset Manager"),
+                SetForegroundColor(Color::Reset),
+            )?;
+
+            let mut y_offset: u16 = 2;
+
+            for (i, name) in names.iter().enumerate() {
+                let cursor = if i == selected { " > " } else { "   " };
+                let active_marker = if *name == active_name {
+                    "[*]"
+                } else {
+                    "[ ]"
+                };
+                let mod_count = self.mod_manager.config.get_preset_mod_count(name);
+
+                let color = if *name == active_name {
+                    Color::White
+                } else {
+                    Color::Grey
+                };
+
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::Red),
+                    Print(cursor),
+                    SetForegroundColor(color),
+                    Print(format!("{} {:<20} ({:>2} mods)", active_marker, name, mod_count)),
+                    SetForegroundColor(Color::Reset),
+                )?;
+
+                y_offset += 1;
             }
-            command.env("SteamAppId", "107410");
-        }
 
-        command.spawn()?;
+            y_offset += 1;
+            execute!(
+                stdout,
+                cursor::MoveTo(0, y_offset),
+                Print("  <N> New  <ENTER> Load  <S> Save Current  <R> Rename  <D> Delete  <ESC> Back"),
+                cursor::MoveTo(0, y_offset + 1),
+                Print("  <I> Import .preset2 / .html / .txt  <X> Export .preset2"),
+            )?;
+
+            stdout.flush()?;
+
+            if event::poll(Duration::from_millis(500))? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
+                    match code {
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            selected = selected.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j')
+                            if selected < names.len().saturating_sub(1) =>
+                        {
+                            selected += 1;
+                        }
+                        KeyCode::Enter => {
+                            // Load selected preset and return to main screen
+                            self.save_undo();
+                            let name = names[selected].clone();
+                            self.mod_manager.switch_preset(&name);
+                            self.apply_search();
+                            break;
+                        }
+                        KeyCode::Char('n') => {
+                            // Create new preset from current mod selection
+                            let new_name = self
+                                .input_screen(stdout, "New Preset", "Preset name:", "")?
+                                .unwrap_or_default();
+                            if !new_name.is_empty() {
+                                let current_mods = self.mod_manager.enabled_identifiers();
+                                self.mod_manager
+                                    .config
+                                    .save_preset(new_name.clone(), current_mods);
+                                self.mod_manager.switch_preset(&new_name);
+                                self.apply_search();
+                                self.mod_manager.config.save()?;
+                                // Update selected to point to the new preset
+                                let updated_names = self.mod_manager.config.get_preset_names();
+                                if let Some(idx) = updated_names.iter().position(|n| *n == new_name)
+                                {
+                                    selected = idx;
+                                }
+                            }
+                        }
+                        KeyCode::Char('s') => {
+                            // Overwrite selected preset with current mod selection
+                            let name = names[selected].clone();
+                            let current_mods = self.mod_manager.enabled_identifiers();
+                            self.mod_manager
+                                .config
+                                .save_preset(name.clone(), current_mods);
+                            // If this is the active preset, sync enabled_mods
+                            if name == self.mod_manager.config.get_active_preset_name() {
+                                let mods = self.mod_manager.config.get_enabled_mods();
+                                self.mod_manager.config.update_mods(mods);
+                            }
+                            self.mod_manager.config.save()?;
+                        }
+                        KeyCode::Char('r') => {
+                            // Rename selected preset
+                            let old_name = names[selected].clone();
+                            let new_name = self
+                                .input_screen(stdout, "Rename Preset", "New name:", &old_name)?
+                                .unwrap_or_default();
+                            if !new_name.is_empty() && new_name != old_name {
+                                self.mod_manager.config.rename_preset(&old_name, new_name.clone());
+                                self.mod_manager.config.save()?;
+                                // Update selected
+                                let updated_names = self.mod_manager.config.get_preset_names();
+                                if let Some(idx) =
+                                    updated_names.iter().position(|n| *n == new_name)
+                                {
+                                    selected = idx;
+                                }
+                            }
+                        }
+                        KeyCode::Char('d') => {
+                            // Delete selected preset (blocked if last)
+                            let name = names[selected].clone();
+                            self.mod_manager.config.delete_preset(&name);
+                            self.mod_manager.config.save()?;
+                        }
+                        KeyCode::Char('i') => {
+                            if let Some(path) =
+                                self.input_screen(stdout, "Import Preset", "Preset file:", "")?
+                            {
+                                let path = utils::normalize_path_input(&path);
+                                if let Some(name) = self.import_preset(stdout, Path::new(&path))? {
+                                    let updated_names = self.mod_manager.config.get_preset_names();
+                                    if let Some(idx) = updated_names.iter().position(|n| *n == name)
+                                    {
+                                        selected = idx;
+                                    }
+                                }
+                            }
+                        }
+                        KeyCode::Char('x') => {
+                            let name = names[selected].clone();
+                            self.export_preset_screen(stdout, &name, "preset2")?;
+                        }
+                        KeyCode::Esc => break,
+                        _ => {}
+                    }
+                }
+            }
+        }
 
         Ok(())
     }
 
-    fn clear_screen(&self, stdout: &mut Stdout) -> AppResult<()> {
-        execute!(stdout, cursor::MoveTo(0, 0))?;
-        execute!(stdout, terminal::Clear(terminal::ClearType::All))?;
+    /// Import a preset file into a new preset named after the file.
+    /// Returns the name of the created preset.
+    fn import_preset(&mut self, stdout: &mut Stdout, path: &Path) -> AppResult<Option<String>> {
+        let identifiers = match file_handler::parse_preset(path) {
+            Ok(identifiers) => identifiers,
+            Err(e) => {
+                self.message_screen(stdout, Color::Red, &format!("Import failed: {}", e))?;
+                return Ok(None);
+            }
+        };
 
-        Ok(())
+        let base_name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Imported".to_string());
+
+        // Don't overwrite an existing preset with the same name
+        let existing_names = self.mod_manager.config.get_preset_names();
+        let mut name = base_name.clone();
+        let mut suffix = 2;
+        while existing_names.contains(&name) {
+            name = format!("{} {}", base_name, suffix);
+            suffix += 1;
+        }
+
+        // Exports list workshop mods by their published id, which may not be the folder name
+        let identifiers: Vec<String> = identifiers
+            .into_iter()
+            .map(|id| match self.mod_manager.installed_identifier(&id) {
+                Some(installed) => installed.to_string(),
+                None => id,
+            })
+            .collect();
+        let missing: Vec<&str> = identifiers
+            .iter()
+            .filter(|id| self.mod_manager.installed_identifier(id).is_none())
+            .map(|id| id.as_str())
+            .collect();
+        let mut message = format!(
+            "Imported {} mods into preset [{}] ({} not installed)",
+            identifiers.len(),
+            name,
+            missing.len()
+        );
+        if !missing.is_empty() {
+            message += &format!(
+                "\nNot installed: {}",
+                missing[..missing.len().min(10)].join(", ")
+            );
+            if missing.len() > 10 {
+                message += &format!(" and {} more", missing.len() - 10);
+            }
+        }
+
+        self.mod_manager
+            .config
+            .save_preset(name.clone(), identifiers);
+        self.mod_manager.config.save()?;
+
+        self.message_screen(stdout, Color::Green, &message)?;
+
+        Ok(Some(name))
     }
 
-    fn set_custom_parameters_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
-        let mut args_string = self.mod_manager.config.get_default_args().to_string();
-        let mut current_pos = args_string.len() as u16;
+    /// Export a preset to a file in the home directory.
+    /// The format follows the extension of the chosen file.
+    fn export_preset_screen(
+        &mut self,
+        stdout: &mut Stdout,
+        name: &str,
+        extension: &str,
+    ) -> AppResult<()> {
+        let file_name = format!("{}.{}", name, extension);
+        let default_path = match utils::get_home_path() {
+            Ok(home_path) => PathBuf::from(home_path).join(file_name),
+            Err(_) => PathBuf::from(file_name),
+        };
 
-        // Set up the terminal
-        execute!(stdout, cursor::Show)?;
-        execute!(stdout, SetCursorStyle::BlinkingUnderScore)?;
-        stdout.flush()?;
+        let Some(path) = self.input_screen(
+            stdout,
+            &format!("Export Preset [{}]", name),
+            "Export to:",
+            &default_path.to_string_lossy(),
+        )?
+        else {
+            return Ok(());
+        };
+        let path = utils::normalize_path_input(&path);
 
-        self.clear_screen(stdout)?;
+        // The active preset may have unsaved changes, so export the live selection
+        let identifiers = if name == self.mod_manager.config.get_active_preset_name() {
+            self.mod_manager.enabled_identifiers()
+        } else {
+            self.mod_manager.config.get_preset_mods(name)
+        };
+
+        // Keep the load order of the preset
+        let installed = self.mod_manager.loaded_mods.all_items();
+        let mods: Vec<&Mod> = identifiers
+            .iter()
+            .filter_map(|id| installed.iter().find(|m| m.identifier == *id))
+            .collect();
+
+        match file_handler::write_preset(
+            Path::new(&path),
+            name,
+            &mods,
+            &self.mod_manager.config.get_custom_mods_paths(),
+        ) {
+            Ok(()) => self.message_screen(
+                stdout,
+                Color::Green,
+                &format!("Exported {} mods to {}", mods.len(), path),
+            ),
+            Err(e) => self.message_screen(stdout, Color::Red, &format!("Export failed: {}", e)),
+        }
+    }
+
+    #[cfg(feature = "network")]
+    fn dependency_cache(&self) -> dependency_manager::DependencyCache {
+        dependency_manager::DependencyCache::load(
+            self.mod_manager.config.get_dependency_cache_ttl(),
+            self.mod_manager.config.get_network_retries(),
+            self.mod_manager.config.get_offline(),
+        )
+    }
+
+    /// Shown while resolving dependencies, which only come from the cache when offline
+    #[cfg(feature = "network")]
+    fn fetch_label(&self) -> &'static str {
+        if self.mod_manager.config.get_offline() {
+            "Reading cached dependencies..."
+        } else {
+            "Fetching data from Steam Workshop..."
+        }
+    }
+
+    /// Fetches the dependencies of a newly enabled mod on a background thread, so retries and
+    /// rate limits don't hold up the UI. The result is picked up by `finish_dependency_checks`.
+    #[cfg(feature = "network")]
+    fn start_dependency_check(&mut self, index: usize) {
+        let selected_mod = &self.mod_manager.loaded_mods.all_items()[index];
+        let mod_id = selected_mod.workshop_id().to_string();
+        let mod_name = selected_mod.name.clone();
+
+        let cache = self.dependency_cache();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let result = dependency_manager::resolve_all_dependencies(&[mod_id], &cache, |_, _| {});
+            save_dependency_cache(&cache);
+            // Sending fails when the manager quit before the check finished
+            let _ = sender.send(DependencyCheck { mod_name, result });
+        });
+        self.dependency_checks.push(receiver);
+    }
+
+    /// Lists the dependencies that aren't installed for the finished background checks.
+    /// Installed dependencies are enabled as well when `auto_enable_dependencies` is set.
+    /// Returns whether any check finished.
+    #[cfg(feature = "network")]
+    fn finish_dependency_checks(&mut self) -> bool {
+        let mut finished = Vec::new();
+        self.dependency_checks
+            .retain(|receiver| match receiver.try_recv() {
+                Ok(check) => {
+                    finished.push(check);
+                    false
+                }
+                Err(TryRecvError::Empty) => true,
+                // The check panicked, which was already reported
+                Err(TryRecvError::Disconnected) => false,
+            });
+
+        let any_finished = !finished.is_empty();
+        for DependencyCheck { mod_name, result } in finished {
+            let graph = match result {
+                Ok(graph) => graph,
+                // Not cached, checking dependencies on enabling is skipped quietly
+                Err(AppError::Offline) => continue,
+                Err(e) => {
+                    self.set_status(format!(
+                        "Couldn't check the dependencies of {}: {}",
+                        mod_name, e
+                    ));
+                    continue;
+                }
+            };
+
+            let dependencies = &graph.dependencies;
+            // Dependencies that couldn't be followed may be missing from the list
+            let incomplete = if graph.is_incomplete() {
+                " (not all could be checked)"
+            } else {
+                ""
+            };
+
+            if self.mod_manager.config.get_auto_enable_dependencies() {
+                // Only cascades when enabling, since dependencies may be shared with other mods
+                for m in self.mod_manager.loaded_mods.all_items_mut() {
+                    if !m.is_missing && dependencies.iter().any(|dep| dep.id == m.workshop_id()) {
+                        m.enabled = true;
+                    }
+                }
+            }
+
+            let installed_mods = self.mod_manager.loaded_mods.all_items();
+            let missing: Vec<String> = dependencies
+                .iter()
+                .filter(|dep| {
+                    !installed_mods
+                        .iter()
+                        .any(|m| m.workshop_id() == dep.id && !m.is_missing)
+                })
+                .map(|dep| format!("{} ({})", dep.name, dep.id))
+                .collect();
+
+            if missing.is_empty() {
+                self.set_status(format!(
+                    "All {} dependencies of {} are installed{}",
+                    dependencies.len(),
+                    mod_name,
+                    incomplete
+                ));
+            } else {
+                self.notice = Some(format!(
+                    "{} requires {} mods that are not installed{}: {}",
+                    mod_name,
+                    missing.len(),
+                    incomplete,
+                    missing.join(", ")
+                ));
+            }
+        }
+        any_finished
+    }
+
+    /// Resolves the dependencies of all enabled workshop mods into one report
+    #[cfg(feature = "network")]
+    fn batch_dependencies_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let enabled_mods = self
+            .mod_manager
+            .loaded_mods
+            .filter(|m| m.enabled && !m.is_cdlc && !m.is_custom);
+        let enabled_ids: Vec<String> = enabled_mods
+            .iter()
+            .map(|m| m.workshop_id().to_string())
+            .collect();
+
+        if enabled_ids.is_empty() {
+            return self.message_screen(
+                stdout,
+                Color::Yellow,
+                "No enabled workshop mods to check dependencies for.",
+            );
+        }
 
+        self.clear_screen(stdout)?;
         execute!(
             stdout,
-            SetForegroundColor(Color::Cyan),
             cursor::MoveTo(0, 0),
-            Print("Arma 3 Mod Manager Console"),
+            SetForegroundColor(Color::Cyan),
+            Print("Checking dependencies for all enabled mods"),
             SetForegroundColor(Color::Reset),
         )?;
+        stdout.flush()?;
 
-        execute!(stdout, cursor::MoveTo(0, 2), Print("Press <ENTER> to save"),)?;
+        let cache = self.dependency_cache();
+        let graph = dependency_manager::resolve_dependency_graph(
+            &enabled_ids,
+            &cache,
+            progress_reporter(stdout, 2, self.fetch_label()),
+        );
+        save_dependency_cache(&cache);
+
+        // Deduplicated across all enabled mods and their dependencies,
+        // counting how many of them require each one
+        let dependencies = graph.required();
+        // Offline, mods without cached dependencies weren't checked rather than failing
+        let (not_cached, failed): (Vec<_>, Vec<_>) = graph
+            .failed
+            .iter()
+            .partition(|(_, e)| matches!(e, AppError::Offline));
 
-        let arg_string_left = 4;
-        let arg_string_top = 4;
-        let arg_string_left_padding = arg_string_left - 3;
+        loop {
+            let installed_mods = self.mod_manager.loaded_mods.all_items();
+            let find_installed = |id: &str| {
+                installed_mods
+                    .iter()
+                    .find(|m| m.workshop_id() == id && !m.is_missing)
+            };
 
-        execute!(
-            stdout,
-            SetForegroundColor(Color::Red),
-            cursor::MoveTo(arg_string_left_padding, arg_string_top),
-            Print(">"),
-            SetForegroundColor(Color::Reset)
-        )?;
+            let enabled: Vec<_> = dependencies
+                .iter()
+                .filter(|(d, _)| find_installed(&d.id).is_some_and(|m| m.enabled))
+                .collect();
+            let disabled: Vec<_> = dependencies
+                .iter()
+                .filter(|(d, _)| find_installed(&d.id).is_some_and(|m| !m.enabled))
+                .collect();
+            let missing: Vec<_> = dependencies
+                .iter()
+                .filter(|(d, _)| find_installed(&d.id).is_none())
+                .collect();
 
-        execute!(
-            stdout,
-            cursor::MoveTo(arg_string_left, arg_string_top),
-            Print(&args_string)
-        )?;
+            self.clear_screen(stdout)?;
+            execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                SetForegroundColor(Color::Cyan),
+                Print(format!(
+                    "Dependencies for {} enabled mods: {} required",
+                    enabled_ids.len(),
+                    dependencies.len()
+                )),
+                SetForegroundColor(Color::Reset),
+            )?;
 
-        execute!(
-            stdout,
-            cursor::MoveTo(0, arg_string_top + 2),
-            Print("For more information visit: https://community.bistudio.com/wiki/Arma_3:_Startup_Parameters")
-        )?;
+            let mut y_offset: u16 = 2;
 
-        execute!(
-            stdout,
-            cursor::MoveTo(current_pos + arg_string_left, arg_string_top)
-        )?;
+            let sections = [
+                ("Installed and enabled", Color::Green, &enabled),
+                ("Installed but not enabled", Color::Yellow, &disabled),
+                ("Missing", Color::Red, &missing),
+            ];
+
+            for (title, color, deps) in sections {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    Print(format!("{} ({})", title, deps.len())),
+                )?;
+                y_offset += 1;
+
+                for (dep, required_by) in deps.iter() {
+                    let line = if color == Color::Red {
+                        format!(
+                            "  {:<40} https://steamcommunity.com/sharedfiles/filedetails/?id={}",
+                            dep.name, dep.id
+                        )
+                    } else {
+                        format!("  {:<40} required by {} mods", dep.name, required_by)
+                    };
+
+                    execute!(
+                        stdout,
+                        cursor::MoveTo(0, y_offset),
+                        SetForegroundColor(color),
+                        Print(line),
+                        SetForegroundColor(Color::Reset),
+                    )?;
+                    y_offset += 1;
+                }
+                y_offset += 1;
+            }
+
+            if let Some((mod_id, e)) = failed.first() {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::Yellow),
+                    Print(format!(
+                        "Could not check {} mods (e.g. {}: {})",
+                        failed.len(),
+                        mod_id,
+                        e
+                    )),
+                    SetForegroundColor(Color::Reset),
+                )?;
+                y_offset += 2;
+            }
+            if !not_cached.is_empty() {
+                let ids: Vec<&str> = not_cached.iter().map(|(id, _)| id.as_str()).collect();
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::Yellow),
+                    Print(format!(
+                        "Offline, the dependencies of {} mods are not cached: {}",
+                        ids.len(),
+                        ids.join(", ")
+                    )),
+                    SetForegroundColor(Color::Reset),
+                )?;
+                y_offset += 2;
+            }
+            if graph.truncated {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, y_offset),
+                    SetForegroundColor(Color::Yellow),
+                    Print(TRUNCATED_NOTE),
+                    SetForegroundColor(Color::Reset),
+                )?;
+                y_offset += 2;
+            }
+
+            execute!(
+                stdout,
+                cursor::MoveTo(0, y_offset),
+                Print("Press <E> to Enable all installed, <ESC> to return."),
+            )?;
+            stdout.flush()?;
+
+            let ids_to_enable: Vec<String> = disabled.iter().map(|(d, _)| d.id.clone()).collect();
 
-        loop {
             if event::poll(Duration::from_millis(500))? {
-                if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
                     match code {
-                        KeyCode::Esc => {
-                            break;
-                        }
-                        KeyCode::Enter => {
-                            self.mod_manager.config.set_default_args(args_string);
-                            self.mod_manager.config.save()?;
-
-                            break;
-                        }
-                        KeyCode::Backspace => {
-                            if !args_string.is_empty() && current_pos > 0 {
-                                args_string.pop();
-                                current_pos -= 1;
+                        KeyCode::Esc => break,
+                        KeyCode::Char('e') => {
+                            self.save_undo();
+                            for m in self.mod_manager.loaded_mods.all_items_mut() {
+                                if ids_to_enable.iter().any(|id| id == m.workshop_id()) {
+                                    m.enabled = true;
+                                }
                             }
                         }
-                        KeyCode::Char(c) => {
-                            args_string.push(c);
-                            current_pos += 1;
-                        }
                         _ => {}
                     }
-
-                    execute!(stdout, terminal::Clear(terminal::ClearType::CurrentLine))?;
-
-                    execute!(
-                        stdout,
-                        SetForegroundColor(Color::Red),
-                        cursor::MoveTo(arg_string_left_padding, arg_string_top),
-                        Print(">"),
-                        SetForegroundColor(Color::Reset)
-                    )?;
-
-                    // Clear the previous line and update display
-                    execute!(
-                        stdout,
-                        cursor::MoveTo(arg_string_left, arg_string_top),
-                        Print(&args_string)
-                    )?;
-
-                    execute!(
-                        stdout,
-                        cursor::MoveTo(0, arg_string_top + 2),
-                        Print("For more information visit: https://community.bistudio.com/wiki/Arma_3:_Startup_Parameters")
-                    )?;
-
-                    // Move cursor to the new position
-                    execute!(
-                        stdout,
-                        cursor::MoveTo(current_pos + arg_string_left, arg_string_top)
-                    )?;
-
-                    stdout.flush()?;
                 }
             }
         }
-        // Restore terminal state
-        execute!(stdout, cursor::Hide)?;
-        execute!(stdout, SetCursorStyle::DefaultUserShape)?;
 
         Ok(())
     }
 
-    fn set_executable_name_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
-        let mut executable_name = self.mod_manager.config.get_executable_name().to_string();
-        let mut current_pos = executable_name.len() as u16;
+    /// Shows the dependencies of the selected mod as a tree, with the status of each one
+    #[cfg(feature = "network")]
+    fn dependency_tree_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
+        let Some(index) = self.selected_mod_index() else {
+            return Ok(());
+        };
+        let selected_mod = &self.mod_manager.loaded_mods.all_items()[index];
 
-        // Set up the terminal
-        execute!(stdout, cursor::Show)?;
-        execute!(stdout, SetCursorStyle::BlinkingUnderScore)?;
-        stdout.flush()?;
+        if selected_mod.is_custom || selected_mod.is_cdlc {
+            return self.message_screen(
+                stdout,
+                Color::Yellow,
+                "Cannot check dependencies for Local/CDLC mods.",
+            );
+        }
 
-        self.clear_screen(stdout)?;
+        let mod_id = selected_mod.workshop_id().to_string();
+        let mod_name = selected_mod.name.clone();
 
+        self.clear_screen(stdout)?;
         execute!(
             stdout,
-            SetForegroundColor(Color::Cyan),
             cursor::MoveTo(0, 0),
-            Print("Arma 3 Mod Manager Console - Executable Name"),
+            SetForegroundColor(Color::Cyan),
+            Print(format!("Dependency tree of: {}", mod_name)),
             SetForegroundColor(Color::Reset),
         )?;
 
-        execute!(
-            stdout,
-            cursor::MoveTo(0, 2),
-            Print("Press <ENTER> to save, <ESC> to cancel"),
-        )?;
-
-        let name_left = 4;
-        let name_top = 4;
-        let name_left_padding = name_left - 3;
-
-        execute!(
-            stdout,
-            SetForegroundColor(Color::Red),
-            cursor::MoveTo(name_left_padding, name_top),
-            Print(">"),
-            SetForegroundColor(Color::Reset)
-        )?;
-
-        execute!(
-            stdout,
-            cursor::MoveTo(name_left, name_top),
-            Print(&executable_name)
-        )?;
-
-        let instruction_text = if cfg!(target_os = "macos") {
-            "Enter the name of the Arma 3 executable (without .app extension)"
-        } else {
-            "Enter the name of the Arma 3 executable"
+        let cache = self.dependency_cache();
+        let result = dependency_manager::resolve_all_dependencies(
+            std::slice::from_ref(&mod_id),
+            &cache,
+            progress_reporter(stdout, 2, self.fetch_label()),
+        );
+        save_dependency_cache(&cache);
+
+        let graph = match result {
+            Ok(graph) => graph,
+            Err(AppError::Offline) => {
+                return self.message_screen(
+                    stdout,
+                    Color::Yellow,
+                    &format!("Offline, the dependencies of {} are not cached", mod_name),
+                );
+            }
+            Err(e) => {
+                return self.message_screen(
+                    stdout,
+                    Color::Red,
+                    &format!("Error fetching dependencies of {}: {}", mod_name, e),
+                );
+            }
         };
 
-        execute!(
-            stdout,
-            cursor::MoveTo(0, name_top + 2),
-            Print(instruction_text)
-        )?;
-
-        execute!(stdout, cursor::MoveTo(current_pos + name_left, name_top))?;
-
-        loop {
-            if event::poll(Duration::from_millis(500))? {
-                if let Event::Key(KeyEvent { code, .. }) = event::read()? {
-                    match code {
-                        KeyCode::Esc => {
-                            break;
-                        }
-                        KeyCode::Enter => {
-                            self.mod_manager.config.set_executable_name(executable_name);
-                            self.mod_manager.config.save()?;
-                            break;
-                        }
-                        KeyCode::Backspace => {
-                            if !executable_name.is_empty() && current_pos > 0 {
-                                executable_name.pop();
-                                current_pos -= 1;
-                            }
-                        }
-                        KeyCode::Char(c) => {
-                            executable_name.push(c);
-                            current_pos += 1;
-                        }
-                        _ => {}
-                    }
-
-                    execute!(stdout, terminal::Clear(terminal::ClearType::CurrentLine))?;
-
-                    execute!(
-                        stdout,
-                        SetForegroundColor(Color::Red),
-                        cursor::MoveTo(name_left_padding, name_top),
-                        Print(">"),
-                        SetForegroundColor(Color::Reset)
-                    )?;
-
-                    // Clear the previous line and update display
-                    execute!(
-                        stdout,
-                        cursor::MoveTo(name_left, name_top),
-                        Print(&executable_name)
-                    )?;
-
-                    execute!(
-                        stdout,
-                        cursor::MoveTo(0, name_top + 2),
-                        Print(instruction_text)
-                    )?;
-
-                    // Move cursor to the new position
-                    execute!(stdout, cursor::MoveTo(current_pos + name_left, name_top))?;
-
-                    stdout.flush()?;
-                }
-            }
-        }
-        // Restore terminal state
-        execute!(stdout, cursor::Hide)?;
-        execute!(stdout, SetCursorStyle::DefaultUserShape)?;
+        let installed_mods = self.mod_manager.loaded_mods.all_items();
+        let lines: Vec<(String, Color)> = graph
+            .tree(&mod_id)
+            .iter()
+            .map(|node| {
+                let dependency = &node.dependency;
+                let found_mod = installed_mods
+                    .iter()
+                    .find(|m| m.workshop_id() == dependency.id && !m.is_missing);
+
+                let (status, color) = match found_mod {
+                    _ if node.cycle => ("cycle", Color::DarkGrey),
+                    None => ("MISSING", Color::Red),
+                    Some(m) if m.enabled => ("Enabled", Color::Green),
+                    Some(_) => ("Disabled", Color::Yellow),
+                };
 
-        Ok(())
-    }
+                let line = format!(
+                    "{}- {} ({}) [{}]",
+                    "  ".repeat(node.depth - 1),
+                    dependency.name,
+                    dependency.id,
+                    status
+                );
+                (line, color)
+            })
+            .collect();
 
-    fn preset_manager_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
         let mut selected: usize = 0;
+        let mut offset: usize = 0;
 
         loop {
-            let names = self.mod_manager.config.get_preset_names();
-            let active_name = self.mod_manager.config.get_active_preset_name().to_string();
-
-            // Clamp selected index
-            if selected >= names.len() {
-                selected = names.len().saturating_sub(1);
+            let (_, rows) = terminal::size()?;
+            // Title, root, and the help and incomplete lines below the tree
+            let height = (rows as usize).saturating_sub(7).max(1);
+
+            if selected < offset {
+                offset = selected;
+            } else if selected >= offset + height {
+                offset = selected + 1 - height;
             }
 
             self.clear_screen(stdout)?;
-
             execute!(
                 stdout,
                 cursor::MoveTo(0, 0),
                 SetForegroundColor(Color::Cyan),
-                Print("Arma 3 Mod Manager Console - Preset Manager"),
+                Print(format!("Dependency tree of: {}", mod_name)),
                 SetForegroundColor(Color::Reset),
+                cursor::MoveTo(0, 2),
+                Print(format!("{} ({})", mod_name, mod_id)),
             )?;
 
-            let mut y_offset: u16 = 2;
+            if lines.is_empty() {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, 3),
+                    Print("No dependencies found or required items not listed."),
+                )?;
+            }
 
-            for (i, name) in names.iter().enumerate() {
+            for (i, (line, color)) in lines.iter().enumerate().skip(offset).take(height) {
+                let y = (3 + i - offset) as u16;
                 let cursor = if i == selected { " > " } else { "   " };
-                let active_marker = if *name == active_name {
-                    "[*]"
-                } else {
-                    "[ ]"
-                };
-                let mod_count = self.mod_manager.config.get_preset_mod_count(name);
-
-                let color = if *name == active_name {
-                    Color::White
-                } else {
-                    Color::Grey
-                };
 
                 execute!(
                     stdout,
-                    cursor::MoveTo(0, y_offset),
+                    cursor::MoveTo(0, y),
                     SetForegroundColor(Color::Red),
                     Print(cursor),
-                    SetForegroundColor(color),
-                    Print(format!("{} {:<20} ({:>2} mods)", active_marker, name, mod_count)),
+                    SetForegroundColor(*color),
+                    Print(line),
                     SetForegroundColor(Color::Reset),
                 )?;
-
-                y_offset += 1;
             }
 
-            y_offset += 1;
+            let shown = lines.len().min(height).max(1) as u16;
             execute!(
                 stdout,
-                cursor::MoveTo(0, y_offset),
-                Print("  <N> New  <ENTER> Load  <S> Save Current  <R> Rename  <D> Delete  <ESC> Back"),
+                cursor::MoveTo(0, 4 + shown),
+                Print(format!(
+                    "{} dependencies - <UP>/<DOWN> to scroll, <ESC> to return",
+                    lines.len()
+                )),
             )?;
-
+            if let Some(note) = incomplete_graph_note(&graph) {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, 5 + shown),
+                    SetForegroundColor(Color::Yellow),
+                    Print(note),
+                    SetForegroundColor(Color::Reset),
+                )?;
+            }
             stdout.flush()?;
 
             if event::poll(Duration::from_millis(500))? {
-                if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
                     match code {
-                        KeyCode::Up | KeyCode::Char('k') => {
-                            if selected > 0 {
-                                selected -= 1;
-                            }
-                        }
-                        KeyCode::Down | KeyCode::Char('j') => {
-                            if selected < names.len().saturating_sub(1) {
-                                selected += 1;
-                            }
-                        }
-                        KeyCode::Enter => {
-                            // Load selected preset and return to main screen
-                            let name = names[selected].clone();
-                            self.mod_manager.switch_preset(&name);
-                            self.selected_index = 0;
-                            self.mod_manager.loaded_mods.current_page = 0;
-                            break;
-                        }
-                        KeyCode::Char('n') => {
-                            // Create new preset from current mod selection
-                            let new_name = self.input_screen(
-                                stdout,
-                                "New Preset",
-                                "Preset name:",
-                                "",
-                            )?;
-                            if !new_name.is_empty() {
-                                let current_mods: Vec<String> = self
-                                    .mod_manager
-                                    .loaded_mods
-                                    .filter(|m| m.enabled)
-                                    .iter()
-                                    .map(|m| m.identifier.clone())
-                                    .collect();
-                                self.mod_manager
-                                    .config
-                                    .save_preset(new_name.clone(), current_mods);
-                                self.mod_manager.switch_preset(&new_name);
-                                self.mod_manager.config.save()?;
-                                // Update selected to point to the new preset
-                                let updated_names = self.mod_manager.config.get_preset_names();
-                                if let Some(idx) = updated_names.iter().position(|n| *n == new_name)
-                                {
-                                    selected = idx;
-                                }
-                            }
-                        }
-                        KeyCode::Char('s') => {
-                            // Overwrite selected preset with current mod selection
-                            let name = names[selected].clone();
-                            let current_mods: Vec<String> = self
-                                .mod_manager
-                                .loaded_mods
-                                .filter(|m| m.enabled)
-                                .iter()
-                                .map(|m| m.identifier.clone())
-                                .collect();
-                            self.mod_manager
-                                .config
-                                .save_preset(name.clone(), current_mods);
-                            // If this is the active preset, sync enabled_mods
-                            if name == self.mod_manager.config.get_active_preset_name() {
-                                let mods = self.mod_manager.config.get_enabled_mods();
-                                self.mod_manager.config.update_mods(mods);
-                            }
-                            self.mod_manager.config.save()?;
-                        }
-                        KeyCode::Char('r') => {
-                            // Rename selected preset
-                            let old_name = names[selected].clone();
-                            let new_name = self.input_screen(
-                                stdout,
-                                "Rename Preset",
-                                "New name:",
-                                &old_name,
-                            )?;
-                            if !new_name.is_empty() && new_name != old_name {
-                                self.mod_manager.config.rename_preset(&old_name, new_name.clone());
-                                self.mod_manager.config.save()?;
-                                // Update selected
-                                let updated_names = self.mod_manager.config.get_preset_names();
-                                if let Some(idx) =
-                                    updated_names.iter().position(|n| *n == new_name)
-                                {
-                                    selected = idx;
-                                }
-                            }
+                        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('k') => {
+                            selected = selected.saturating_sub(1);
                         }
-                        KeyCode::Char('d') => {
-                            // Delete selected preset (blocked if last)
-                            let name = names[selected].clone();
-                            self.mod_manager.config.delete_preset(&name);
-                            self.mod_manager.config.save()?;
+                        KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('j') => {
+                            selected = (selected + 1).min(lines.len().saturating_sub(1));
                         }
-                        KeyCode::Esc => break,
+                        KeyCode::Esc | KeyCode::Char('q') => break,
                         _ => {}
                     }
                 }
@@ -1119,10 +4493,11 @@ impl<'a> Terminal<'a> {
         Ok(())
     }
 
+    #[cfg(feature = "network")]
     fn check_dependencies_screen(&mut self, stdout: &mut Stdout) -> AppResult<()> {
-        let current_page = self.mod_manager.loaded_mods.current_page;
-        let page_size = self.mod_manager.loaded_mods.page_size;
-        let index = self.selected_index + (current_page * page_size);
+        let Some(index) = self.selected_mod_index() else {
+            return Ok(());
+        };
         let selected_mod = &self.mod_manager.loaded_mods.all_items()[index];
 
         if selected_mod.is_custom || selected_mod.is_cdlc {
@@ -1141,7 +4516,7 @@ impl<'a> Terminal<'a> {
 
             loop {
                 if event::poll(Duration::from_millis(500))? {
-                    if let Event::Key(_) = event::read()? {
+                    if let Some(Event::Key(_)) = read_event()? {
                         break;
                     }
                 }
@@ -1149,7 +4524,7 @@ impl<'a> Terminal<'a> {
             return Ok(());
         }
 
-        let mod_id = selected_mod.identifier.clone();
+        let mod_id = selected_mod.workshop_id().to_string();
         let mod_name = selected_mod.name.clone();
 
         self.clear_screen(stdout)?;
@@ -1159,19 +4534,31 @@ impl<'a> Terminal<'a> {
             SetForegroundColor(Color::Cyan),
             Print(format!("Checking dependencies for: {}", mod_name)),
             SetForegroundColor(Color::Reset),
-            cursor::MoveTo(0, 2),
-            Print("Fetching data from Steam Workshop... Please wait."),
         )?;
-        stdout.flush()?;
 
-        let dependencies = match dependency_manager::fetch_dependencies(&mod_id) {
-            Ok(deps) => deps,
+        let cache = self.dependency_cache();
+        let result = dependency_manager::resolve_all_dependencies(
+            &[mod_id],
+            &cache,
+            progress_reporter(stdout, 2, self.fetch_label()),
+        );
+        save_dependency_cache(&cache);
+
+        let graph = match result {
+            Ok(graph) => graph,
             Err(e) => {
+                let (color, message) = match e {
+                    AppError::Offline => (
+                        Color::Yellow,
+                        "Offline, the dependencies of this mod are not cached".to_string(),
+                    ),
+                    e => (Color::Red, format!("Error fetching dependencies: {}", e)),
+                };
                 execute!(
                     stdout,
                     cursor::MoveTo(0, 4),
-                    SetForegroundColor(Color::Red),
-                    Print(format!("Error fetching dependencies: {}", e)),
+                    SetForegroundColor(color),
+                    Print(message),
                     SetForegroundColor(Color::Reset),
                     cursor::MoveTo(0, 6),
                     Print("Press any key to return...")
@@ -1179,7 +4566,7 @@ impl<'a> Terminal<'a> {
                 stdout.flush()?;
                 loop {
                     if event::poll(Duration::from_millis(500))? {
-                        if let Event::Key(_) = event::read()? {
+                        if let Some(Event::Key(_)) = read_event()? {
                             break;
                         }
                     }
@@ -1201,9 +4588,12 @@ impl<'a> Terminal<'a> {
 
         let mut dep_statuses = Vec::new();
         let installed_mods = self.mod_manager.loaded_mods.all_items();
+        let incomplete_note = incomplete_graph_note(&graph);
 
-        for dep in dependencies {
-            let found_mod = installed_mods.iter().find(|m| m.identifier == dep.id);
+        for dep in graph.dependencies {
+            let found_mod = installed_mods
+                .iter()
+                .find(|m| m.workshop_id() == dep.id && !m.is_missing);
             dep_statuses.push(DepStatus {
                 id: dep.id,
                 name: dep.name,
@@ -1278,10 +4668,19 @@ impl<'a> Terminal<'a> {
                 cursor::MoveTo(0, info_y),
                 Print("Press <E> to Enable all installed, <ESC> to return."),
             )?;
+            if let Some(note) = &incomplete_note {
+                execute!(
+                    stdout,
+                    cursor::MoveTo(0, info_y + 1),
+                    SetForegroundColor(Color::Yellow),
+                    Print(note),
+                    SetForegroundColor(Color::Reset),
+                )?;
+            }
             stdout.flush()?;
 
             if event::poll(Duration::from_millis(500))? {
-                if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+                if let Some(Event::Key(KeyEvent { code, .. })) = read_event()? {
                     match code {
                         KeyCode::Esc => break,
                         KeyCode::Char('e') => {
@@ -1294,7 +4693,7 @@ impl<'a> Terminal<'a> {
 
                             if !ids_to_enable.is_empty() {
                                 for m in self.mod_manager.loaded_mods.all_items_mut() {
-                                    if ids_to_enable.contains(&m.identifier) {
+                                    if ids_to_enable.iter().any(|id| id == m.workshop_id()) {
                                         m.enabled = true;
                                     }
                                 }
@@ -1315,3 +4714,20 @@ impl<'a> Terminal<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_mods_are_dimmed_although_enabled() {
+        let missing = Mod::missing("@gone".to_string());
+        assert!(missing.enabled);
+        assert_eq!(row_color(&missing), Color::DarkGrey);
+
+        let mut installed = Mod::new("@ace".to_string(), "ACE".to_string(), false, false);
+        assert_eq!(row_color(&installed), Color::Grey);
+        installed.enabled = true;
+        assert_eq!(row_color(&installed), Color::White);
+    }
+}
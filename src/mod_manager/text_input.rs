@@ -0,0 +1,245 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+/// What a text-entry screen should do after an event was passed to its input
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputAction {
+    Confirm,
+    Cancel,
+    /// The text changed
+    Edited,
+    /// Only the cursor moved
+    Moved,
+    /// A key the input doesn't use, left for the screen to handle
+    Key(KeyEvent),
+    Ignored,
+}
+
+/// A single line of editable text with a cursor
+#[derive(Debug)]
+pub struct TextInput {
+    text: String,
+    /// Position of the cursor in characters, not bytes
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Starts with the cursor after the initial text
+    pub fn new(text: &str) -> Self {
+        TextInput {
+            text: text.to_string(),
+            cursor: text.chars().count(),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_text(self) -> String {
+        self.text
+    }
+
+    /// Replaces the text and moves the cursor to the end
+    pub fn set_text(&mut self, text: &str) {
+        *self = TextInput::new(text);
+    }
+
+    /// Column of the cursor relative to the start of the text
+    pub fn cursor_column(&self) -> u16 {
+        self.cursor as u16
+    }
+
+    pub fn handle_event(&mut self, event: Event) -> InputAction {
+        match event {
+            // Releases are reported on Windows and would type every character twice
+            Event::Key(key) if key.kind == KeyEventKind::Press => self.handle_key(key),
+            Event::Paste(text) => {
+                // Line breaks would otherwise end up in a single line input
+                let text: String = text.chars().filter(|c| !c.is_control()).collect();
+                if text.is_empty() {
+                    return InputAction::Ignored;
+                }
+                self.insert(&text);
+                InputAction::Edited
+            }
+            _ => InputAction::Ignored,
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> InputAction {
+        let length = self.text.chars().count();
+
+        match key.code {
+            KeyCode::Enter => InputAction::Confirm,
+            KeyCode::Esc => InputAction::Cancel,
+            KeyCode::Left if self.cursor > 0 => {
+                self.cursor -= 1;
+                InputAction::Moved
+            }
+            KeyCode::Right if self.cursor < length => {
+                self.cursor += 1;
+                InputAction::Moved
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+                InputAction::Moved
+            }
+            KeyCode::End => {
+                self.cursor = length;
+                InputAction::Moved
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.text.remove(self.byte_index());
+                InputAction::Edited
+            }
+            KeyCode::Delete if self.cursor < length => {
+                self.text.remove(self.byte_index());
+                InputAction::Edited
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Backspace | KeyCode::Delete => {
+                InputAction::Ignored
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.insert(&c.to_string());
+                InputAction::Edited
+            }
+            _ => InputAction::Key(key),
+        }
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.text.insert_str(self.byte_index(), text);
+        self.cursor += text.chars().count();
+    }
+
+    fn byte_index(&self) -> usize {
+        self.text
+            .char_indices()
+            .nth(self.cursor)
+            .map_or(self.text.len(), |(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn type_text(input: &mut TextInput, text: &str) {
+        for c in text.chars() {
+            input.handle_event(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn edits_at_the_cursor() {
+        let mut input = TextInput::new("-noSplah");
+
+        input.handle_event(key(KeyCode::Left));
+        type_text(&mut input, "s");
+        assert_eq!(input.text(), "-noSplash");
+        assert_eq!(input.cursor_column(), 8);
+
+        input.handle_event(key(KeyCode::Home));
+        input.handle_event(key(KeyCode::Delete));
+        assert_eq!(input.text(), "noSplash");
+
+        input.handle_event(key(KeyCode::End));
+        input.handle_event(key(KeyCode::Backspace));
+        assert_eq!(input.text(), "noSplas");
+        assert_eq!(input.cursor_column(), 7);
+    }
+
+    #[test]
+    fn keeps_the_cursor_within_the_text() {
+        let mut input = TextInput::new("ab");
+
+        assert_eq!(
+            input.handle_event(key(KeyCode::Right)),
+            InputAction::Ignored
+        );
+        assert_eq!(
+            input.handle_event(key(KeyCode::Delete)),
+            InputAction::Ignored
+        );
+
+        input.handle_event(key(KeyCode::Home));
+        assert_eq!(input.handle_event(key(KeyCode::Left)), InputAction::Ignored);
+        assert_eq!(
+            input.handle_event(key(KeyCode::Backspace)),
+            InputAction::Ignored
+        );
+        assert_eq!(input.text(), "ab");
+    }
+
+    #[test]
+    fn handles_multibyte_characters() {
+        let mut input = TextInput::new("Åre");
+
+        input.handle_event(key(KeyCode::Home));
+        input.handle_event(key(KeyCode::Right));
+        type_text(&mut input, "ö");
+        input.handle_event(key(KeyCode::Left));
+        input.handle_event(key(KeyCode::Left));
+        input.handle_event(key(KeyCode::Delete));
+
+        assert_eq!(input.text(), "öre");
+    }
+
+    #[test]
+    fn pastes_a_single_line_at_the_cursor() {
+        let mut input = TextInput::new("-mod=");
+
+        input.handle_event(key(KeyCode::Home));
+        let action = input.handle_event(Event::Paste("-world=empty\r\n".to_string()));
+
+        assert_eq!(action, InputAction::Edited);
+        assert_eq!(input.text(), "-world=empty-mod=");
+        assert_eq!(input.cursor_column(), 12);
+    }
+
+    #[test]
+    fn leaves_other_keys_to_the_screen() {
+        let mut input = TextInput::new("");
+
+        assert_eq!(
+            input.handle_event(key(KeyCode::Enter)),
+            InputAction::Confirm
+        );
+        assert_eq!(input.handle_event(key(KeyCode::Esc)), InputAction::Cancel);
+        assert!(matches!(
+            input.handle_event(key(KeyCode::Tab)),
+            InputAction::Key(KeyEvent {
+                code: KeyCode::Tab,
+                ..
+            })
+        ));
+        assert!(matches!(
+            input.handle_event(Event::Key(KeyEvent::new(
+                KeyCode::Char('c'),
+                KeyModifiers::CONTROL
+            ))),
+            InputAction::Key(_)
+        ));
+        assert_eq!(input.text(), "");
+    }
+
+    #[test]
+    fn ignores_key_releases() {
+        let mut input = TextInput::new("");
+
+        input.handle_event(key(KeyCode::Char('a')));
+        let release = Event::Key(KeyEvent::new_with_kind(
+            KeyCode::Char('a'),
+            KeyModifiers::NONE,
+            KeyEventKind::Release,
+        ));
+
+        assert_eq!(input.handle_event(release), InputAction::Ignored);
+        assert_eq!(input.text(), "a");
+    }
+}
@@ -1,8 +1,10 @@
 use std::{
     env,
-    ffi::OsString,
+    ffi::{OsStr, OsString},
     fs,
     path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
 };
 
 use regex::Regex;
@@ -20,7 +22,8 @@ pub fn ensure_directory_exists(path: &PathBuf) -> AppResult<()> {
 }
 
 pub fn get_home_path() -> AppResult<OsString> {
-    match env::var_os("HOME") {
+    // Windows doesn't set HOME by default
+    match env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")) {
         Some(home_path) => Ok(home_path),
         None => Err(AppError::InvalidHomePath),
     }
@@ -29,26 +32,186 @@ pub fn get_home_path() -> AppResult<OsString> {
 pub fn setup_steam_paths() -> AppResult<(String, String)> {
     let home_path = get_home_path()?;
 
-    // Define OS-specific base paths
-    let base_path = match std::env::consts::OS {
-        "macos" => Path::new(&home_path).join("Library/Application Support"),
-        "linux" => Path::new(&home_path).join(".local/share"),
+    // Define OS-specific Steam install locations
+    let steam_paths = match std::env::consts::OS {
+        "macos" => vec![Path::new(&home_path).join("Library/Application Support/Steam")],
+        "linux" => [
+            ".local/share/Steam",
+            // Flatpak
+            ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+            // Snap
+            "snap/steam/common/.local/share/Steam",
+            ".steam/steam",
+        ]
+        .iter()
+        .map(|path| Path::new(&home_path).join(path))
+        .collect(),
+        #[cfg(target_os = "windows")]
+        "windows" => vec![get_windows_steam_path()],
         _ => return Err(AppError::UnsupportedPlatform),
     };
 
-    // Define relative paths
-    let steam_workshop_path = "Steam/steamapps/workshop/content/107410";
-    let steam_game_path = "Steam/steamapps/common/Arma 3";
+    let Some(library) = find_arma3_library(&steam_paths) else {
+        let checked: Vec<String> = steam_paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        return Err(AppError::SteamNotFound(checked.join(", ")));
+    };
 
-    // Construct full paths
-    let workshop_path = construct_path_string(&base_path, steam_workshop_path)?;
-    let game_path = construct_path_string(&base_path, steam_game_path)?;
+    // Define relative paths
+    let workshop_path = construct_path_string(&library, "steamapps/workshop/content/107410")?;
+    let game_path = construct_path_string(&library, "steamapps/common/Arma 3")?;
 
     Ok((workshop_path, game_path))
 }
 
+/// The first Steam library containing Arma 3.
+/// Arma 3 may be installed in another Steam library, e.g. on a second drive.
+fn find_arma3_library(steam_paths: &[PathBuf]) -> Option<PathBuf> {
+    steam_paths
+        .iter()
+        .flat_map(|steam_path| {
+            std::iter::once(steam_path.clone()).chain(discover_steam_libraries(steam_path))
+        })
+        .find(|library| library.join("steamapps/common/Arma 3").exists())
+}
+
+/// Get the Steam library folders listed in `steamapps/libraryfolders.vdf`
+pub fn discover_steam_libraries(steam_path: &Path) -> Vec<PathBuf> {
+    fs::read_to_string(steam_path.join("steamapps").join("libraryfolders.vdf"))
+        .map(|content| parse_library_folders(&content))
+        .unwrap_or_default()
+}
+
+/// Supports both the old format, where libraries are listed as `"1" "D:\\SteamLibrary"`,
+/// and the new format with a block per library containing a `"path"` key
+fn parse_library_folders(content: &str) -> Vec<PathBuf> {
+    let Ok(re) = Regex::new(r#""((?:[^"\\]|\\.)*)"|([{}])"#) else {
+        return Vec::new();
+    };
+
+    let mut libraries = Vec::new();
+    let mut depth = 0;
+    let mut key: Option<String> = None;
+
+    for caps in re.captures_iter(content) {
+        match caps.get(2).map(|m| m.as_str()) {
+            Some("{") => {
+                depth += 1;
+                key = None;
+            }
+            Some(_) => {
+                depth -= 1;
+                key = None;
+            }
+            None => {
+                let value = caps[1].replace("\\\\", "\\").replace("\\\"", "\"");
+                match key.take() {
+                    None => key = Some(value),
+                    Some(key) => {
+                        let is_old_format = depth == 1 && key.chars().all(|c| c.is_ascii_digit());
+                        let is_new_format = depth == 2 && key.eq_ignore_ascii_case("path");
+                        if is_old_format || is_new_format {
+                            libraries.push(PathBuf::from(value));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    libraries
+}
+
+/// App ids of the DLCs Steam installed for Arma 3, read from the app manifest in the
+/// library of the game. `None` when there is no manifest or it lists no DLCs, since
+/// ownership can't be told from it then.
+pub fn installed_dlc_app_ids(game_path: &Path) -> Option<Vec<String>> {
+    // The game is in `steamapps/common/Arma 3`, the manifest in `steamapps`
+    let manifest_path = game_path.parent()?.parent()?.join("appmanifest_107410.acf");
+    let content = fs::read_to_string(manifest_path).ok()?;
+
+    let re = Regex::new(r#"(?i)"dlcappid"\s+"(\d+)""#).ok()?;
+    let app_ids: Vec<String> = re
+        .captures_iter(&content)
+        .map(|caps| caps[1].to_string())
+        .collect();
+
+    (!app_ids.is_empty()).then_some(app_ids)
+}
+
+/// Whether a CDLC folder has its content, and isn't only a stub of a DLC that isn't owned.
+/// The addons of the CDLCs are encrypted `.ebo` files.
+pub fn has_cdlc_content(path: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(path.join("addons")) else {
+        return false;
+    };
+
+    entries.filter_map(|entry| entry.ok()).any(|entry| {
+        entry.path().extension().is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("ebo") || extension.eq_ignore_ascii_case("pbo")
+        })
+    })
+}
+
+/// Get the Steam install path from the registry,
+/// falling back to the default location in `Program Files (x86)`
+#[cfg(target_os = "windows")]
+pub fn get_windows_steam_path() -> PathBuf {
+    // Output: `    SteamPath    REG_SZ    c:/program files (x86)/steam`
+    let registry_path = std::process::Command::new("reg")
+        .args(["query", r"HKCU\Software\Valve\Steam", "/v", "SteamPath"])
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .find_map(|line| line.split_once("REG_SZ"))
+                .map(|(_, value)| value.trim().replace('/', "\\"))
+        })
+        .filter(|path| !path.is_empty());
+
+    match registry_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let program_files = env::var_os("ProgramFiles(x86)")
+                .unwrap_or_else(|| OsString::from(r"C:\Program Files (x86)"));
+            Path::new(&program_files).join("Steam")
+        }
+    }
+}
+
+/// Find the Steam client executable
+#[cfg(target_os = "macos")]
+pub fn find_steam_executable() -> Option<PathBuf> {
+    let path = PathBuf::from("/Applications/Steam.app/Contents/MacOS/steam_osx");
+    path.exists().then_some(path)
+}
+
+#[cfg(target_os = "windows")]
+pub fn find_steam_executable() -> Option<PathBuf> {
+    let path = get_windows_steam_path().join("steam.exe");
+    path.exists().then_some(path)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn find_steam_executable() -> Option<PathBuf> {
+    // Steam is usually installed system wide with a `steam` launcher script
+    env::var_os("PATH").and_then(|paths| {
+        env::split_paths(&paths)
+            .map(|dir| dir.join("steam"))
+            .find(|path| path.is_file())
+    })
+}
+
 pub fn construct_path_string(base_path: &Path, relative_path: &str) -> AppResult<String> {
-    let full_path = base_path.join(relative_path);
+    // Join component-wise to get the platform's separators
+    let full_path = relative_path
+        .split('/')
+        .fold(base_path.to_path_buf(), |path, component| {
+            path.join(component)
+        });
     full_path
         .to_str()
         .ok_or_else(|| AppError::PathConversionError(full_path.to_string_lossy().into()))
@@ -61,6 +224,7 @@ pub fn yield_path_dirs(path: &Path) -> AppResult<impl Iterator<Item = PathBuf>>
         .flatten()
         .filter_map(|entry| entry.ok())
         .map(|entry| entry.path())
+        // `is_dir` follows symlinks, so linked mod folders are included
         .filter(|path| path.is_dir());
 
     Ok(dirs)
@@ -69,38 +233,529 @@ pub fn yield_path_dirs(path: &Path) -> AppResult<impl Iterator<Item = PathBuf>>
 pub fn process_mod_dir(path_buf: PathBuf, is_custom: bool) -> Option<Mod> {
     let file_name = path_buf.file_name()?.to_str()?.to_string();
 
-    // Ensure "meta.cpp" exists
-    let meta_file = path_buf.join("meta.cpp");
-    let meta_content = fs::read(&meta_file).ok()?;
+    let meta_content = fs::read(path_buf.join("meta.cpp")).ok();
+    let mod_content = fs::read(path_buf.join("mod.cpp")).ok();
 
-    // Convert file content to a UTF-8 string
-    let content_str = String::from_utf8_lossy(&meta_content);
+    // Workshop mods come with a meta.cpp, hand-made custom mods often have neither file
+    if meta_content.is_none() && mod_content.is_none() && !is_custom {
+        return None;
+    }
+
+    // Older mods may have UTF-16 or Latin-1 encoded meta files
+    let content_str = decode_text(&meta_content.unwrap_or_default());
+    let mod_content_str = decode_text(&mod_content.unwrap_or_default());
+
+    // The name in meta.cpp is the workshop name, mod.cpp has the name shown in the game.
+    // Without either, fall back to the folder name.
+    let name_re = Regex::new(r#"\bname\s*=\s*"([^"]+)""#).ok()?;
+    let read_name = |content: &str| {
+        name_re
+            .captures(content)
+            .and_then(|caps| caps.get(1))
+            .map(|m| m.as_str().to_string())
+    };
+    let name = read_name(&content_str)
+        .or_else(|| read_name(&mod_content_str))
+        .unwrap_or_else(|| titleize(&file_name));
+
+    let mut m = Mod::new(file_name, name, false, is_custom);
 
-    // Extract the `name` using regex
-    // We look into the meta.cpp file
-    // for Workshop mods it is common for the name to be there
-    // Both for custom mods its not always the case, so just fallback to the file_name instead
-    let name = match Regex::new(r#"name\s*=\s*"([^"]+)""#)
+    m.published_id = Regex::new(r"publishedid\s*=\s*(\d+)")
         .ok()?
         .captures(&content_str)
-        .and_then(|caps| caps.get(1))
-        .map(|m| titleize(m.as_str()))
-    {
-        Some(name) => name,
-        None => titleize(&file_name),
+        .map(|caps| caps[1].to_string())
+        .filter(|id| id != "0");
+
+    m.timestamp = Regex::new(r"timestamp\s*=\s*(\d+)")
+        .ok()?
+        .captures(&content_str)
+        .and_then(|caps| caps[1].parse::<i64>().ok())
+        .map(meta_timestamp_to_unix);
+
+    // Server mods are marked with e.g. `serverMod = 1;` in either file
+    let server_mod_re = Regex::new(r"(?i)\bserver_?mod\s*=\s*(1|true)\b").ok()?;
+    m.is_server_mod =
+        server_mod_re.is_match(&content_str) || server_mod_re.is_match(&mod_content_str);
+
+    m.disk_size = dir_size(&path_buf);
+
+    Some(m)
+}
+
+/// Opens a folder or URL with the default application, e.g. the file manager or browser
+pub fn open_with_default_app(target: &OsStr) -> AppResult<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(windows)]
+    let mut command = Command::new("explorer");
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let mut command = Command::new("xdg-open");
+
+    let mut child = command
+        .arg(target)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| AppError::OpenFailed(target.to_string_lossy().to_string(), e))?;
+
+    // Reap the opener once it is done, it may keep running while the application starts
+    thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+/// Decode a text file that may be UTF-8, UTF-16 (detected by the byte order mark
+/// or by its zero bytes) or Latin-1
+pub fn decode_text(bytes: &[u8]) -> String {
+    let decode_utf16 = |bytes: &[u8], little_endian: bool| {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| {
+                if little_endian {
+                    u16::from_le_bytes([pair[0], pair[1]])
+                } else {
+                    u16::from_be_bytes([pair[0], pair[1]])
+                }
+            })
+            .collect();
+        String::from_utf16_lossy(&units)
     };
 
-    Some(Mod::new(file_name, name, false, is_custom))
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, true),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, false),
+        _ => match std::str::from_utf8(bytes) {
+            Ok(text) if !text.contains('\0') => text.to_string(),
+            // ASCII text in UTF-16 has every other byte set to zero
+            _ if bytes.len() >= 2 && bytes[1] == 0 => decode_utf16(bytes, true),
+            _ if bytes.len() >= 2 && bytes[0] == 0 => decode_utf16(bytes, false),
+            // Every byte is a valid Latin-1 character
+            _ => bytes.iter().map(|&b| b as char).collect(),
+        },
+    }
+}
+
+/// Total size of the files in the directory and its subdirectories, in bytes.
+/// Symlinks are not followed.
+pub fn dir_size(path: &Path) -> u64 {
+    fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Human readable size, e.g. "1.5 GB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
 }
 
+/// Free space available to the user on the drive of the path, in bytes.
+/// `None` when it can't be determined.
+#[cfg(unix)]
+pub fn available_space(path: &Path) -> Option<u64> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `statvfs` is plain data, filled in by the call below
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: the path is nul terminated and `stat` is a valid pointer
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    // The field types differ between platforms
+    #[allow(clippy::unnecessary_cast)]
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+pub fn available_space(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain([0]).collect();
+    let mut available = 0u64;
+    // SAFETY: the path is nul terminated and the totals we don't need may be null
+    let result = unsafe {
+        GetDiskFreeSpaceExW(
+            wide_path.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    (result != 0).then_some(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn available_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Whether both paths are on the same filesystem, so files can be hard linked between them
+#[cfg(unix)]
+pub fn same_filesystem(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub fn same_filesystem(a: &Path, b: &Path) -> bool {
+    // Compares the drive letters, which is as close as the paths alone get
+    let prefix = |path: &Path| {
+        path.components()
+            .next()
+            .map(|component| component.as_os_str().to_ascii_lowercase())
+    };
+    prefix(a).is_some() && prefix(a) == prefix(b)
+}
+
+/// The `timestamp` in `meta.cpp` is a .NET `DateTime` in binary form:
+/// 100ns ticks since 0001-01-01 with the kind stored in the two highest bits
+fn meta_timestamp_to_unix(timestamp: i64) -> i64 {
+    const TICKS_MASK: i64 = 0x3FFF_FFFF_FFFF_FFFF;
+    const UNIX_EPOCH_TICKS: i64 = 621_355_968_000_000_000;
+    const TICKS_PER_SECOND: i64 = 10_000_000;
+
+    ((timestamp & TICKS_MASK) - UNIX_EPOCH_TICKS) / TICKS_PER_SECOND
+}
+
+/// How long ago the Unix timestamp was, e.g. "3 days ago"
+pub fn format_age(timestamp: i64, now: i64) -> String {
+    match (now - timestamp).max(0) / 86_400 {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{} days ago", days),
+    }
+}
+
+/// The Unix timestamp as `YYYYMMDD-HHMMSS` in UTC, for use in file names
+pub fn format_file_timestamp(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86_400);
+    let seconds = timestamp.rem_euclid(86_400);
+
+    // Civil date from days since the epoch, in 400 year eras starting in March
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// The time of day of the Unix timestamp as `HH:MM:SS` in UTC
+pub fn format_clock(timestamp: i64) -> String {
+    let seconds = timestamp.rem_euclid(86_400);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// A bar like `[=====>    ] 42%` for a fraction between 0 and 1, `width` wide between the brackets
+pub fn progress_bar(fraction: f32, width: usize) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let filled = (fraction * width as f32).round() as usize;
+
+    let bar = if filled == 0 {
+        " ".repeat(width)
+    } else if filled == width {
+        "=".repeat(width)
+    } else {
+        format!("{}>{}", "=".repeat(filled - 1), " ".repeat(width - filled))
+    };
+
+    format!("[{}] {:>3}%", bar, (fraction * 100.0).floor() as u32)
+}
+
+/// Matches the characters of `query` in order anywhere in `text`, ignoring case, e.g. "cba"
+/// in "Community Base Addons". Returns the best score, which is higher for consecutive
+/// characters and characters at the start of words, and the character positions in `text`
+/// that matched.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query.chars().collect();
+    let chars: Vec<char> = text.chars().collect();
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let matches = |q: char, c: char| c.to_lowercase().eq(q.to_lowercase());
+    let word_start = |i: usize| {
+        i == 0
+            || !chars[i - 1].is_alphanumeric()
+            || (chars[i - 1].is_lowercase() && chars[i].is_uppercase())
+    };
+    // Skipped characters count against the match, but only a little so that matching the
+    // first letters of words can still beat a run in the middle of one
+    let gap_penalty = |gap: usize| gap.min(3) as i64;
+
+    // best[i][j] is the best score of the first i + 1 query characters with the last one at j,
+    // and the position the previous query character was matched at
+    let mut best: Vec<Vec<Option<(i64, usize)>>> = vec![vec![None; chars.len()]; query.len()];
+
+    for (j, &c) in chars.iter().enumerate() {
+        if matches(query[0], c) {
+            let bonus = if word_start(j) { 3 } else { 0 };
+            best[0][j] = Some((1 + bonus - gap_penalty(j), 0));
+        }
+    }
+
+    for i in 1..query.len() {
+        for j in i..chars.len() {
+            if !matches(query[i], chars[j]) {
+                continue;
+            }
+
+            best[i][j] = (0..j)
+                .filter_map(|k| best[i - 1][k].map(|(score, _)| (score, k)))
+                .map(|(score, k)| {
+                    let bonus = if k + 1 == j {
+                        5
+                    } else if word_start(j) {
+                        3
+                    } else {
+                        0
+                    };
+                    (score + 1 + bonus - gap_penalty(j - k - 1), k)
+                })
+                .max_by_key(|(score, _)| *score);
+        }
+    }
+
+    let (mut position, (score, _)) = best[query.len() - 1]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, entry)| entry.map(|entry| (j, entry)))
+        .max_by_key(|(_, (score, _))| *score)?;
+
+    let mut positions = vec![position];
+    for i in (1..query.len()).rev() {
+        position = best[i][position]?.1;
+        positions.push(position);
+    }
+    positions.reverse();
+
+    Some((score, positions))
+}
+
+/// Turns a folder name into a readable name, e.g. `acex_compat_rhs` into "Acex Compat Rhs"
 fn titleize(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().chain(c).collect(),
+    s.split(['_', '-', ' '])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut c = word.chars();
+            match c.next() {
+                None => String::new(),
+                Some(f) => f.to_uppercase().chain(c).collect(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Shorten `s` to at most `max_chars` characters, ending with an ellipsis when shortened.
+/// Unlike `String::truncate` this never splits a multibyte character.
+pub fn truncate_chars(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        return s.to_string();
+    }
+
+    let mut truncated: String = s.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Splits `s` into lines of at most `width` characters, breaking between words where possible
+pub fn wrap_text(s: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in s.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        let line_length = line.chars().count();
+
+        if line_length > 0 && line_length + 1 + word.len() <= width {
+            line.push(' ');
+            line.extend(&word);
+            continue;
+        }
+        if line_length > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        // Words longer than a line are split
+        while word.len() > width {
+            lines.push(word.drain(..width).collect());
+        }
+        line.extend(word);
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Drops surrounding whitespace and one pair of matching quotes, as left by
+/// copying a path from a file manager or shell
+pub fn strip_input_quotes(input: &str) -> &str {
+    let trimmed = input.trim();
+    ['"', '\'']
+        .iter()
+        .find_map(|quote| {
+            trimmed
+                .strip_prefix(*quote)
+                .and_then(|rest| rest.strip_suffix(*quote))
+        })
+        .map_or(trimmed, str::trim)
+}
+
+/// Cleans up a path typed or pasted by the user and expands a leading `~` to the home directory
+pub fn normalize_path_input(input: &str) -> String {
+    expand_tilde(strip_input_quotes(input))
+}
+
+/// Expands a leading `~` to the home directory
+pub fn expand_tilde(path: &str) -> String {
+    expand_home(path, get_home_path().ok().as_deref())
+}
+
+/// Replaces the home directory at the start of the path with `~`, the opposite of `expand_tilde`
+pub fn contract_home(path: &str, home_path: &Path) -> String {
+    match Path::new(path).strip_prefix(home_path) {
+        Ok(rest) => std::iter::once("~".into())
+            .chain(rest.iter().map(|part| part.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.to_string(),
     }
 }
 
+fn expand_home(path: &str, home_path: Option<&OsStr>) -> String {
+    let Some(home_path) = home_path else {
+        return path.to_string();
+    };
+
+    match path.strip_prefix('~') {
+        Some("") => home_path.to_string_lossy().to_string(),
+        Some(rest) if rest.starts_with(['/', '\\']) => Path::new(home_path)
+            .join(&rest[1..])
+            .to_string_lossy()
+            .to_string(),
+        // `~user` paths are left alone
+        _ => path.to_string(),
+    }
+}
+
+/// Directory the Arma profiles besides the default one are kept in
+pub fn get_profiles_path() -> AppResult<PathBuf> {
+    let home_path = PathBuf::from(get_home_path()?);
+
+    let profiles_path = match std::env::consts::OS {
+        "windows" => home_path.join("Documents").join("Arma 3 - Other Profiles"),
+        "macos" => home_path.join("Library/Application Support/bohemiainteractive/Arma 3"),
+        "linux" => home_path.join(".local/share/bohemiainteractive/Arma 3"),
+        _ => return Err(AppError::UnsupportedPlatform),
+    };
+    Ok(profiles_path)
+}
+
+/// Names of the profiles in the directory, sorted. A profile is a directory with an
+/// `.Arma3Profile` file, named after the profile with special characters percent-encoded.
+pub fn find_profiles(profiles_path: &Path) -> Vec<String> {
+    let Ok(dirs) = yield_path_dirs(profiles_path) else {
+        return Vec::new();
+    };
+
+    let mut profiles: Vec<String> = dirs
+        .filter(|dir| {
+            fs::read_dir(dir).is_ok_and(|mut entries| {
+                entries.any(|entry| {
+                    entry.is_ok_and(|entry| {
+                        entry
+                            .path()
+                            .extension()
+                            .is_some_and(|extension| extension == "Arma3Profile")
+                    })
+                })
+            })
+        })
+        .filter_map(|dir| Some(decode_profile_name(dir.file_name()?.to_str()?)))
+        .collect();
+
+    profiles.sort_by_key(|name| name.to_lowercase());
+    profiles
+}
+
+/// Decodes the `%20` style escapes in the directory name of a profile
+fn decode_profile_name(name: &str) -> String {
+    let bytes = name.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| name.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
 #[cfg(target_os = "macos")]
 pub fn get_steam_overlay_path() -> Option<PathBuf> {
     let home_path = get_home_path().ok()?;
@@ -120,3 +775,500 @@ pub fn get_steam_overlay_path() -> Option<PathBuf> {
 
     paths.into_iter().find(|path| path.exists())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_LIBRARY_FOLDERS: &str = r#""LibraryFolders"
+{
+	"TimeNextStatsReport"		"1700000000"
+	"ContentStatsID"		"-1234567890"
+	"1"		"D:\\SteamLibrary"
+	"2"		"/mnt/games/steam"
+}
+"#;
+
+    const NEW_LIBRARY_FOLDERS: &str = r#""libraryfolders"
+{
+	"0"
+	{
+		"path"		"/home/user/.local/share/Steam"
+		"label"		""
+		"contentid"		"1234567890"
+		"totalsize"		"0"
+		"apps"
+		{
+			"228980"		"1234"
+		}
+	}
+	"1"
+	{
+		"path"		"/mnt/games/SteamLibrary"
+		"label"		"Games"
+		"apps"
+		{
+			"107410"		"45000000000"
+		}
+	}
+}
+"#;
+
+    #[test]
+    fn parses_old_library_folders_format() {
+        assert_eq!(
+            parse_library_folders(OLD_LIBRARY_FOLDERS),
+            vec![
+                PathBuf::from(r"D:\SteamLibrary"),
+                PathBuf::from("/mnt/games/steam")
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_new_library_folders_format() {
+        assert_eq!(
+            parse_library_folders(NEW_LIBRARY_FOLDERS),
+            vec![
+                PathBuf::from("/home/user/.local/share/Steam"),
+                PathBuf::from("/mnt/games/SteamLibrary")
+            ]
+        );
+    }
+
+    #[test]
+    fn truncate_chars_keeps_short_strings() {
+        assert_eq!(truncate_chars("Ünsung", 36), "Ünsung");
+        assert_eq!(truncate_chars("", 5), "");
+    }
+
+    #[test]
+    fn truncate_chars_handles_multibyte() {
+        assert_eq!(truncate_chars("Ünsung Mod Collection", 6), "Ünsun…");
+        assert_eq!(truncate_chars("Русский мод для Арма", 8), "Русский…");
+        assert_eq!(truncate_chars("日本語のモッド", 4), "日本語…");
+    }
+
+    #[test]
+    fn truncate_chars_counts_ellipsis() {
+        let truncated = truncate_chars("[X] A very long mod name that does not fit", 36);
+        assert_eq!(truncated.chars().count(), 36);
+        assert!(truncated.ends_with('…'));
+    }
+
+    #[test]
+    fn wrap_text_breaks_between_words() {
+        assert_eq!(
+            wrap_text("Community Base Addons A3", 10),
+            ["Community", "Base", "Addons A3"]
+        );
+        assert_eq!(
+            wrap_text("@a_very_long_folder", 8),
+            ["@a_very_", "long_fol", "der"]
+        );
+        assert_eq!(wrap_text("RHS  USAF", 20), ["RHS USAF"]);
+        assert!(wrap_text("", 10).is_empty());
+    }
+
+    /// An empty directory in the temp dir, removed again when dropped, also when the test fails
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(name);
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+    }
+
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn process_mod_dir_reads_meta_fields() {
+        let root = TempDir::new("a3mm_meta_fields_test");
+        let dir = root.join("@cba");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("meta.cpp"),
+            "protocol = 1;\npublishedid = 450814997;\nname = \"CBA_A3\";\ntimestamp = 5249207372331487226;\n",
+        )
+        .unwrap();
+
+        let m = process_mod_dir(dir, true).unwrap();
+
+        assert_eq!(m.identifier, "@cba");
+        assert_eq!(m.name, "CBA_A3");
+        assert_eq!(m.published_id.as_deref(), Some("450814997"));
+        assert_eq!(m.workshop_id(), "450814997");
+        // 2021-03-23
+        assert_eq!(m.timestamp, Some(1_616_538_590));
+        assert!(!m.is_server_mod);
+    }
+
+    fn utf16_bytes(text: &str, little_endian: bool, bom: bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        if bom {
+            bytes.extend(if little_endian {
+                [0xFF, 0xFE]
+            } else {
+                [0xFE, 0xFF]
+            });
+        }
+        for unit in text.encode_utf16() {
+            bytes.extend(if little_endian {
+                unit.to_le_bytes()
+            } else {
+                unit.to_be_bytes()
+            });
+        }
+        bytes
+    }
+
+    #[test]
+    fn decode_text_handles_encodings() {
+        let text = "name = \"Ünsung Mod\";";
+
+        assert_eq!(decode_text(text.as_bytes()), text);
+        assert_eq!(
+            decode_text(&[&[0xEF, 0xBB, 0xBF], text.as_bytes()].concat()),
+            text
+        );
+        assert_eq!(decode_text(&utf16_bytes(text, true, true)), text);
+        assert_eq!(decode_text(&utf16_bytes(text, false, true)), text);
+        assert_eq!(decode_text(&utf16_bytes(text, true, false)), text);
+        assert_eq!(decode_text(&utf16_bytes(text, false, false)), text);
+
+        // Latin-1: 0xDC is Ü
+        let latin1: Vec<u8> = text.chars().map(|c| c as u8).collect();
+        assert_eq!(decode_text(&latin1), text);
+    }
+
+    #[test]
+    fn process_mod_dir_reads_utf16_meta() {
+        let root = TempDir::new("a3mm_utf16_meta_test");
+        let dir = root.join("1234");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("meta.cpp"),
+            utf16_bytes(
+                "protocol = 1;\r\npublishedid = 1234;\r\nname = \"Русский мод\";\r\n",
+                true,
+                true,
+            ),
+        )
+        .unwrap();
+
+        let m = process_mod_dir(dir, false).unwrap();
+
+        assert_eq!(m.name, "Русский мод");
+        assert_eq!(m.published_id.as_deref(), Some("1234"));
+    }
+
+    #[test]
+    fn process_mod_dir_detects_server_mods() {
+        let root = TempDir::new("a3mm_server_mod_test");
+        let dir = root.join("@server");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("meta.cpp"), "name = \"Server\";\n").unwrap();
+        fs::write(dir.join("mod.cpp"), "name = \"Server\";\nserverMod = 1;\n").unwrap();
+
+        let m = process_mod_dir(dir, true).unwrap();
+
+        assert!(m.is_server_mod);
+    }
+
+    #[test]
+    fn process_mod_dir_without_meta_fields() {
+        let root = TempDir::new("a3mm_meta_no_fields_test");
+        let dir = root.join("my_mod");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("meta.cpp"), "publishedid = 0;\n").unwrap();
+
+        let m = process_mod_dir(dir, true).unwrap();
+
+        assert_eq!(m.name, "My Mod");
+        assert_eq!(m.published_id, None);
+        assert_eq!(m.workshop_id(), "my_mod");
+        assert_eq!(m.timestamp, None);
+    }
+
+    #[test]
+    fn process_mod_dir_falls_back_to_mod_cpp() {
+        let dir = TempDir::new("a3mm_mod_cpp_test");
+        fs::create_dir_all(dir.join("@local")).unwrap();
+        fs::write(
+            dir.join("@local").join("mod.cpp"),
+            "dir = \"@local\";\nname = \"Local Mod\";\npicture = \"logo.paa\";\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("bare_mod").join("addons")).unwrap();
+
+        let with_mod_cpp = process_mod_dir(dir.join("@local"), false);
+        let custom_without_files = process_mod_dir(dir.join("bare_mod"), true);
+        let workshop_without_files = process_mod_dir(dir.join("bare_mod"), false);
+
+        assert_eq!(with_mod_cpp.unwrap().name, "Local Mod");
+        assert_eq!(custom_without_files.unwrap().name, "Bare Mod");
+        assert!(workshop_without_files.is_none());
+    }
+
+    #[test]
+    fn dir_size_sums_nested_files() {
+        let dir = TempDir::new("a3mm_dir_size_test");
+        fs::create_dir_all(dir.join("addons")).unwrap();
+        fs::write(dir.join("meta.cpp"), vec![0; 100]).unwrap();
+        fs::write(dir.join("addons").join("mod.pbo"), vec![0; 1000]).unwrap();
+
+        assert_eq!(dir_size(&dir), 1100);
+        assert_eq!(dir_size(Path::new("/nonexistent")), 0);
+    }
+
+    #[test]
+    fn available_space_of_existing_paths_only() {
+        let dir = std::env::temp_dir();
+        assert!(available_space(&dir).is_some());
+        assert!(available_space(&dir.join("a3mm_no_such_dir")).is_none());
+        assert!(same_filesystem(&dir, &dir));
+    }
+
+    #[test]
+    fn format_size_picks_unit() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn titleize_splits_words() {
+        assert_eq!(titleize("acex_compat_rhs"), "Acex Compat Rhs");
+        assert_eq!(titleize("my-cool-mod"), "My Cool Mod");
+        assert_eq!(titleize("mixed_words-and spaces"), "Mixed Words And Spaces");
+        assert_eq!(titleize("__leading__and_trailing_"), "Leading And Trailing");
+    }
+
+    #[test]
+    fn titleize_keeps_capitals() {
+        assert_eq!(titleize("ACE_Compat"), "ACE Compat");
+        assert_eq!(titleize("@CBA_A3"), "@CBA A3");
+    }
+
+    #[test]
+    fn titleize_empty() {
+        assert_eq!(titleize(""), "");
+        assert_eq!(titleize("_-_"), "");
+    }
+
+    #[test]
+    fn format_file_timestamp_in_utc() {
+        assert_eq!(format_file_timestamp(0), "19700101-000000");
+        assert_eq!(format_file_timestamp(951_827_696), "20000229-123456");
+        assert_eq!(format_file_timestamp(1_700_000_000), "20231114-221320");
+    }
+
+    #[test]
+    fn format_clock_in_utc() {
+        assert_eq!(format_clock(0), "00:00:00");
+        assert_eq!(format_clock(1_700_000_000), "22:13:20");
+        assert_eq!(format_clock(-1), "23:59:59");
+    }
+
+    #[test]
+    fn progress_bar_fills_up() {
+        assert_eq!(progress_bar(0.0, 10), "[          ]   0%");
+        assert_eq!(progress_bar(0.42, 10), "[===>      ]  42%");
+        assert_eq!(progress_bar(1.0, 10), "[==========] 100%");
+        assert_eq!(progress_bar(1.5, 4), "[====] 100%");
+    }
+
+    #[test]
+    fn fuzzy_match_finds_characters_in_order() {
+        assert_eq!(fuzzy_match("cba", "CBA_A3"), Some((16, vec![0, 1, 2])));
+        assert_eq!(
+            fuzzy_match("ace", "a_ace").map(|(_, p)| p),
+            Some(vec![2, 3, 4])
+        );
+        assert_eq!(fuzzy_match("abc", "CBA_A3"), None);
+        assert_eq!(fuzzy_match("", "ace"), Some((0, vec![])));
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_word_starts_and_runs() {
+        let score = |query, text| fuzzy_match(query, text).unwrap().0;
+
+        assert!(score("ace", "ACE3") > score("ace", "Advanced Combat Environment"));
+        assert!(score("cba", "Community Base Addons") > score("cba", "Tactical Baseline"));
+        assert!(score("rhs", "RHSUSAF") > score("rhs", "Rush Hours"));
+    }
+
+    #[test]
+    fn format_age_in_days() {
+        let now = 1_700_000_000;
+        assert_eq!(format_age(now - 60, now), "today");
+        assert_eq!(format_age(now - 86_400, now), "yesterday");
+        assert_eq!(format_age(now - 10 * 86_400 - 5, now), "10 days ago");
+        assert_eq!(format_age(now + 100, now), "today");
+    }
+
+    #[test]
+    fn find_arma3_library_checks_all_steam_paths() {
+        let root = TempDir::new("a3mm_find_library_test");
+        let native = root.join("native");
+        let flatpak = root.join("flatpak");
+        fs::create_dir_all(native.join("steamapps")).unwrap();
+        fs::create_dir_all(flatpak.join("steamapps/common/Arma 3")).unwrap();
+
+        let found = find_arma3_library(&[root.join("missing"), native.clone(), flatpak.clone()]);
+        assert_eq!(found, Some(flatpak));
+        assert_eq!(find_arma3_library(&[native]), None);
+    }
+
+    #[test]
+    fn discover_steam_libraries_reads_vdf() {
+        let steam = TempDir::new("a3mm_library_folders_test");
+        fs::create_dir_all(steam.join("steamapps")).unwrap();
+        fs::write(
+            steam.join("steamapps").join("libraryfolders.vdf"),
+            NEW_LIBRARY_FOLDERS,
+        )
+        .unwrap();
+
+        assert_eq!(discover_steam_libraries(&steam).len(), 2);
+        assert!(discover_steam_libraries(Path::new("/nonexistent")).is_empty());
+    }
+
+    #[test]
+    fn strip_input_quotes_removes_matching_quotes() {
+        assert_eq!(strip_input_quotes("  \"/games/Arma 3\"  "), "/games/Arma 3");
+        assert_eq!(strip_input_quotes("'/games/Arma 3'\n"), "/games/Arma 3");
+        assert_eq!(strip_input_quotes("\"/games/Arma 3'"), "\"/games/Arma 3'");
+        assert_eq!(strip_input_quotes("arma3_x64 "), "arma3_x64");
+    }
+
+    #[test]
+    fn expand_home_only_expands_a_leading_tilde() {
+        let home = Some(OsStr::new("/home/player"));
+
+        assert_eq!(expand_home("~", home), "/home/player");
+        assert_eq!(
+            expand_home("~/.steam/steam", home),
+            Path::new("/home/player")
+                .join(".steam/steam")
+                .to_string_lossy()
+        );
+        assert_eq!(expand_home("~player/mods", home), "~player/mods");
+        assert_eq!(expand_home("/games/~", home), "/games/~");
+        assert_eq!(expand_home("~/mods", None), "~/mods");
+    }
+
+    #[test]
+    fn contract_home_only_replaces_the_home_directory() {
+        let home = Path::new("/home/player");
+
+        assert_eq!(
+            contract_home("/home/player/.steam/steam", home),
+            "~/.steam/steam"
+        );
+        assert_eq!(contract_home("/home/player", home), "~");
+        assert_eq!(
+            contract_home("/home/player2/mods", home),
+            "/home/player2/mods"
+        );
+        assert_eq!(
+            expand_home(
+                &contract_home("/home/player/mods", home),
+                Some(home.as_os_str())
+            ),
+            Path::new("/home/player").join("mods").to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn installed_dlc_app_ids_reads_the_manifest() {
+        let steamapps = TempDir::new("a3mm_app_manifest_test");
+        let game_path = steamapps.join("common").join("Arma 3");
+        fs::create_dir_all(&game_path).unwrap();
+
+        let without_manifest = installed_dlc_app_ids(&game_path);
+        fs::write(
+            steamapps.join("appmanifest_107410.acf"),
+            r#""AppState"
+{
+	"appid"		"107410"
+	"InstalledDepots"
+	{
+		"107411"
+		{
+			"manifest"		"123"
+		}
+		"1681171"
+		{
+			"manifest"		"456"
+			"dlcappid"		"1681170"
+		}
+	}
+}
+"#,
+        )
+        .unwrap();
+        let with_manifest = installed_dlc_app_ids(&game_path);
+
+        assert_eq!(without_manifest, None);
+        assert_eq!(with_manifest, Some(vec!["1681170".to_string()]));
+    }
+
+    #[test]
+    fn has_cdlc_content_needs_addons() {
+        let dir = TempDir::new("a3mm_cdlc_content_test");
+        fs::create_dir_all(dir.join("WS").join("addons")).unwrap();
+        fs::create_dir_all(dir.join("GM").join("addons")).unwrap();
+        fs::write(dir.join("GM").join("addons").join("gm_core.ebo"), "").unwrap();
+        fs::create_dir_all(dir.join("VN")).unwrap();
+
+        let owned: Vec<bool> = ["WS", "GM", "VN"]
+            .iter()
+            .map(|name| has_cdlc_content(&dir.join(name)))
+            .collect();
+
+        assert_eq!(owned, vec![false, true, false]);
+    }
+
+    #[test]
+    fn find_profiles_lists_directories_with_a_profile() {
+        let dir = TempDir::new("a3mm_profiles_test");
+        for (profile, file) in [
+            ("Modding", Some("Modding.Arma3Profile")),
+            ("John%20Doe", Some("John%20Doe.Arma3Profile")),
+            ("MPMissionsCache", None),
+        ] {
+            fs::create_dir_all(dir.join(profile)).unwrap();
+            if let Some(file) = file {
+                fs::write(dir.join(profile).join(file), "").unwrap();
+            }
+        }
+
+        let profiles = find_profiles(&dir);
+
+        assert_eq!(profiles, vec!["John Doe", "Modding"]);
+        assert!(find_profiles(Path::new("/nonexistent")).is_empty());
+    }
+
+    #[test]
+    fn decode_profile_name_keeps_invalid_escapes() {
+        assert_eq!(decode_profile_name("J%C3%B6rg%20B"), "Jörg B");
+        assert_eq!(decode_profile_name("100%"), "100%");
+        assert_eq!(decode_profile_name("%zz"), "%zz");
+    }
+}